@@ -11,6 +11,10 @@
 // TARGET
 //     Target triple provided by Cargo.
 //
+// CRANELIFT_VERBOSE
+//     If set, print build information and emit extra comments into the
+//     generated ISLE.
+//
 // The build script expects to be run from the directory where this build.rs file lives. The
 // current directory is used to find the sources.
 
@@ -64,6 +68,7 @@ fn main() {
 
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-env-changed=ISLE_SOURCE_DIR");
+    println!("cargo:rerun-if-env-changed=CRANELIFT_VERBOSE");
 
     let isle_dir = if let Ok(path) = std::env::var("ISLE_SOURCE_DIR") {
         // This will canonicalize any relative path in terms of the
@@ -76,7 +81,12 @@ fn main() {
 
     std::fs::create_dir_all(&isle_dir).expect("Could not create ISLE source directory");
 
-    if let Err(err) = meta::generate(&isas, &out_dir, &isle_dir) {
+    let isle_options = meta::IsleOptions {
+        verbose: env::var("CRANELIFT_VERBOSE").is_ok(),
+        ..Default::default()
+    };
+
+    if let Err(err) = meta::generate_with_isle_options(&isas, &out_dir, &isle_dir, &isle_options) {
         eprintln!("Error: {err}");
         process::exit(1);
     }
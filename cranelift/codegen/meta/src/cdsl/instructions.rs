@@ -75,6 +75,11 @@ pub(crate) struct InstructionContent {
     pub other_side_effects: bool,
     /// Despite having other side effects, is this instruction okay to GVN?
     pub side_effects_idempotent: bool,
+    /// Does this binary instruction produce the same result with its two value
+    /// operands swapped?
+    pub is_commutative: bool,
+    /// The Cranelift feature this instruction is only available under, if any.
+    pub feature: Option<&'static str>,
 }
 
 impl InstructionContent {
@@ -136,6 +141,8 @@ pub(crate) struct InstructionBuilder {
     can_trap: bool,
     other_side_effects: bool,
     side_effects_idempotent: bool,
+    is_commutative: bool,
+    feature: Option<&'static str>,
 }
 
 impl InstructionBuilder {
@@ -156,6 +163,8 @@ impl InstructionBuilder {
             can_trap: false,
             other_side_effects: false,
             side_effects_idempotent: false,
+            is_commutative: false,
+            feature: None,
         }
     }
 
@@ -227,6 +236,21 @@ impl InstructionBuilder {
         self
     }
 
+    /// Mark this instruction as commutative in its two value operands.
+    pub fn commutative(mut self) -> Self {
+        self.is_commutative = true;
+        self
+    }
+
+    /// Mark this instruction as only available when the Cranelift `feature`
+    /// is enabled.
+    #[expect(dead_code, reason = "no instruction is feature-gated yet")]
+    pub fn feature(mut self, feature: &'static str) -> Self {
+        assert!(self.feature.is_none());
+        self.feature = Some(feature);
+        self
+    }
+
     fn build(self) -> Instruction {
         let operands_in = self.operands_in.unwrap_or_default();
         let operands_out = self.operands_out.unwrap_or_default();
@@ -250,6 +274,14 @@ impl InstructionBuilder {
             .collect();
 
         verify_format(&self.name, &operands_in, &self.format);
+        assert!(
+            !self.is_commutative
+                || (self.format.num_value_operands == 2
+                    && !self.format.has_value_list
+                    && self.format.imm_fields.is_empty()),
+            "commutative instruction {} must have exactly two value operands and no immediates",
+            self.name
+        );
 
         let polymorphic_info =
             verify_polymorphic(&operands_in, &operands_out, &self.format, &value_opnums);
@@ -276,6 +308,8 @@ impl InstructionBuilder {
             can_trap: self.can_trap,
             other_side_effects: self.other_side_effects,
             side_effects_idempotent: self.side_effects_idempotent,
+            is_commutative: self.is_commutative,
+            feature: self.feature,
         })
     }
 }
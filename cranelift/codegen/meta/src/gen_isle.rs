@@ -4,6 +4,61 @@ use crate::error;
 use cranelift_srcgen::{Formatter, Language, fmtln};
 use std::{borrow::Cow, cmp::Ordering, rc::Rc};
 
+/// The version of the instruction schema that the generated ISLE reflects.
+///
+/// This must be bumped whenever instruction formats change in a way that
+/// affects the generated `InstructionData` bindings, so that consumers of
+/// vendored copies of the generated files can detect stale output.
+pub const ISLE_SCHEMA_VERSION: u32 = 1;
+
+/// Options controlling optional extras in the generated ISLE.
+///
+/// Everything defaults to off, in which case the generated files are the same
+/// as they have always been.
+#[derive(Clone, Debug, Default)]
+pub struct IsleOptions {
+    /// Emit additional explanatory comments into the generated ISLE, for
+    /// example which instruction formats carry each immediate type.
+    pub verbose: bool,
+
+    /// Emit an `InstFormatKind` enum of all instruction formats and an
+    /// `opcode_format` constructor mapping each `Opcode` to its format into
+    /// the lowering prelude.
+    pub opcode_format: bool,
+
+    /// The set of enabled Cranelift features to gate instructions on.
+    ///
+    /// When set, the terms of instructions which are only available under a
+    /// feature are annotated with a `;; cfg(feature = "...")` comment, and
+    /// the terms of instructions whose feature isn't in this list are not
+    /// generated at all. Note that the `Opcode` and `InstructionData` types
+    /// always include every instruction. When `None`, which is the default,
+    /// terms are generated for all instructions.
+    pub cfg_features: Option<Vec<String>>,
+
+    /// Emit a table of contents comment at the top of each generated file
+    /// listing its sections along with the lines they span.
+    pub table_of_contents: bool,
+
+    /// Append a comment to the end of each generated file containing a hash
+    /// of the file's contents above it, so that manual edits can be detected.
+    pub hash_trailer: bool,
+
+    /// Emit an `inst_ctrl_type` term into the lowering prelude which yields
+    /// the controlling type of instructions whose type is inferred from one
+    /// of their value operands.
+    pub inst_ctrl_type: bool,
+
+    /// Emit a `{name}_commuted` extractor into the mid-end prelude for each
+    /// commutative instruction, which matches the instruction's two value
+    /// operands in either order.
+    pub commutative_extractors: bool,
+
+    /// Emit a `;; isle-schema-version: N` comment near the top of the
+    /// generated instruction bindings, where `N` is [`ISLE_SCHEMA_VERSION`].
+    pub schema_version: bool,
+}
+
 /// Which ISLE target are we generating code for?
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum IsleTarget {
@@ -18,6 +73,7 @@ fn gen_common_isle(
     instructions: &AllInstructions,
     fmt: &mut Formatter,
     isle_target: IsleTarget,
+    options: &IsleOptions,
 ) {
     use std::collections::{BTreeMap, BTreeSet};
     use std::fmt::Write;
@@ -34,6 +90,14 @@ fn gen_common_isle(
         "#,
     );
     fmt.empty_line();
+    if options.schema_version {
+        fmt.comment(format!("isle-schema-version: {ISLE_SCHEMA_VERSION}"));
+        fmt.empty_line();
+    }
+
+    // The start of each section, used to generate the table of contents.
+    let toc_index = fmt.num_lines();
+    let mut sections = vec![];
 
     // Collect and deduplicate the immediate types from the instruction fields.
     let rust_name = |f: &FormatField| f.kind.rust_type.rsplit("::").next().unwrap();
@@ -56,10 +120,28 @@ fn gen_common_isle(
 
     // Generate all the extern type declarations we need for the non-`enum`
     // immediates.
+    sections.push(("Immediates", fmt.num_lines()));
     fmt.line(";;;; Extern type declarations for immediates ;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;");
     fmt.empty_line();
+    let mut formats_by_immediate: BTreeMap<_, BTreeSet<_>> = BTreeMap::new();
+    for format in formats {
+        for field in &format.imm_fields {
+            formats_by_immediate
+                .entry(rust_name(field))
+                .or_default()
+                .insert(format.name);
+        }
+    }
     for ty in others.keys() {
         fmtln!(fmt, "(type {} (primitive {}))", ty, ty);
+        if options.verbose {
+            let users = formats_by_immediate[*ty]
+                .iter()
+                .copied()
+                .collect::<Vec<_>>()
+                .join(", ");
+            fmt.comment(format!("Used by formats: {users}"));
+        }
     }
     fmt.empty_line();
 
@@ -73,6 +155,13 @@ fn gen_common_isle(
 
     // Generate all of the value arrays we need for `InstructionData` as well as
     // the constructors and extractors for them.
+    //
+    // Note that these are derived from all formats rather than only from the
+    // instructions included for `isle_target`: `InstructionData` below always
+    // declares every format, which refers to these types, and the prelude's
+    // `pack_value_array_*` and `unpack_value_array_*` implementations must
+    // match the declared terms for every target.
+    sections.push(("Value Arrays", fmt.num_lines()));
     fmt.line(";;;; Value Arrays ;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;");
     fmt.empty_line();
     let value_array_arities: BTreeSet<_> = formats
@@ -109,6 +198,7 @@ fn gen_common_isle(
 
     // Generate all of the block arrays we need for `InstructionData` as well as
     // the constructors and extractors for them.
+    sections.push(("Block Arrays", fmt.num_lines()));
     fmt.line(";;;; Block Arrays ;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;");
     fmt.empty_line();
     let block_array_arities: BTreeSet<_> = formats
@@ -147,6 +237,7 @@ fn gen_common_isle(
     fmt.empty_line();
 
     // Generate the extern type declaration for `Opcode`.
+    sections.push(("`Opcode`", fmt.num_lines()));
     fmt.line(";;;; `Opcode` ;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;");
     fmt.empty_line();
     fmt.line("(type Opcode extern");
@@ -163,6 +254,7 @@ fn gen_common_isle(
     fmt.empty_line();
 
     // Generate the extern type declaration for `InstructionData`.
+    sections.push(("`InstructionData`", fmt.num_lines()));
     fmtln!(
         fmt,
         ";;;; `InstructionData` ;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;",
@@ -212,13 +304,27 @@ fn gen_common_isle(
     fmt.line(")");
     fmt.empty_line();
 
+    if isle_target == IsleTarget::Lower && options.opcode_format {
+        sections.push(("`InstFormatKind`", fmt.num_lines()));
+        gen_opcode_format(formats, instructions, fmt);
+    }
+
     // Generate the helper extractors for each opcode's full instruction.
+    sections.push(("Extractors", fmt.num_lines()));
     fmtln!(
         fmt,
         ";;;; Extracting Opcode, Operands, and Immediates from `InstructionData` ;;;;;;;;",
     );
     fmt.empty_line();
+    gen_root_matcher_doc(isle_target, fmt);
     for inst in instructions {
+        if let (Some(enabled), Some(feature)) = (&options.cfg_features, inst.feature) {
+            if !enabled.iter().any(|f| f == feature) {
+                continue;
+            }
+            fmt.comment(format!("cfg(feature = \"{feature}\")"));
+        }
+
         let results_len = inst.value_results.len();
         let is_var_args = inst.format.has_value_list;
         let has_side_effects = inst.can_trap || inst.other_side_effects;
@@ -484,22 +590,212 @@ fn gen_common_isle(
 
         fmt.empty_line();
     }
+
+    if isle_target == IsleTarget::Lower && options.inst_ctrl_type {
+        sections.push(("`inst_ctrl_type`", fmt.num_lines()));
+        gen_inst_ctrl_type(instructions, fmt, options);
+    }
+
+    if isle_target == IsleTarget::Opt && options.commutative_extractors {
+        sections.push(("Commutative extractors", fmt.num_lines()));
+        gen_commutative_extractors(instructions, fmt, options);
+    }
+
+    if options.table_of_contents {
+        gen_table_of_contents(fmt, toc_index, &sections);
+    }
+}
+
+/// Generate the `inst_ctrl_type` term, with a rule for each instruction whose
+/// controlling type variable is inferred from its typevar operand.
+fn gen_inst_ctrl_type(instructions: &AllInstructions, fmt: &mut Formatter, options: &IsleOptions) {
+    fmtln!(
+        fmt,
+        ";;;; `inst_ctrl_type` ;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;",
+    );
+    fmt.empty_line();
+    fmt.line("(decl partial inst_ctrl_type (Inst) Type)");
+    for inst in instructions {
+        if let (Some(enabled), Some(feature)) = (&options.cfg_features, inst.feature) {
+            if !enabled.iter().any(|f| f == feature) {
+                continue;
+            }
+        }
+        let Some(poly) = &inst.polymorphic_info else {
+            continue;
+        };
+        if !poly.use_typevar_operand {
+            continue;
+        }
+        let typevar_operand = inst.value_opnums[inst.format.typevar_operand.unwrap()];
+        let args = inst
+            .operands_in
+            .iter()
+            .enumerate()
+            .map(|(i, _)| {
+                if i == typevar_operand {
+                    "(value_type ty)"
+                } else {
+                    "_"
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        fmtln!(fmt, "(rule (inst_ctrl_type ({} {})) ty)", inst.name, args);
+    }
+    fmt.empty_line();
+}
+
+/// Generate a `{name}_commuted` extractor for each commutative instruction,
+/// which matches through the `value_array_2_commuted` multi-extractor so that
+/// rules see both orders of the instruction's operands.
+fn gen_commutative_extractors(
+    instructions: &AllInstructions,
+    fmt: &mut Formatter,
+    options: &IsleOptions,
+) {
+    fmtln!(
+        fmt,
+        ";;;; Extracting commutative instructions in either operand order ;;;;;;;;;;;;;;;",
+    );
+    fmt.empty_line();
+    for inst in instructions {
+        if !inst.is_commutative {
+            continue;
+        }
+        if let (Some(enabled), Some(feature)) = (&options.cfg_features, inst.feature) {
+            if !enabled.iter().any(|f| f == feature) {
+                continue;
+            }
+            fmt.comment(format!("cfg(feature = \"{feature}\")"));
+        }
+        assert_eq!(inst.value_results.len(), 1);
+        assert!(!inst.can_trap && !inst.other_side_effects);
+        let [x, y] = [0, 1].map(|i| inst.operands_in[inst.value_opnums[i]].name);
+        fmtln!(
+            fmt,
+            "(decl {}_commuted (Type Value Value) Value)",
+            inst.name
+        );
+        fmtln!(fmt, "(extractor");
+        fmt.indent(|fmt| {
+            fmtln!(fmt, "({}_commuted ty {x} {y})", inst.name);
+            fmtln!(
+                fmt,
+                "(inst_data_value ty (InstructionData.{} (Opcode.{}) (value_array_2_commuted {x} {y})))",
+                inst.format.name,
+                inst.camel_name
+            );
+        });
+        fmt.line(")");
+        fmt.empty_line();
+    }
+}
+
+/// Insert a table of contents comment at `index` listing each section, given
+/// as its name and the index of its first line, along with its line range.
+fn gen_table_of_contents(fmt: &mut Formatter, index: usize, sections: &[(&str, usize)]) {
+    // The table itself shifts everything after it, so account for its own
+    // length: a header, one line per section, and a trailing empty line.
+    let toc_len = sections.len() + 3;
+    let mut toc = Formatter::new(Language::Isle);
+    toc.comment("Table of contents:");
+    toc.line(";;");
+    let ends = sections
+        .iter()
+        .skip(1)
+        .map(|(_, start)| *start)
+        .chain([fmt.num_lines()]);
+    for ((name, start), end) in sections.iter().zip(ends) {
+        // Convert from zero-based indices to one-based line numbers.
+        let first = start + toc_len + 1;
+        let last = end + toc_len;
+        toc.comment(format!("  {name:<24} lines {first}-{last}"));
+    }
+    toc.empty_line();
+    assert_eq!(toc.num_lines(), toc_len);
+    fmt.insert(index, toc);
+}
+
+/// Generate the `InstFormatKind` enum and the `opcode_format` mapping from
+/// each opcode to the format of its `InstructionData`.
+fn gen_opcode_format(
+    formats: &[Rc<InstructionFormat>],
+    instructions: &AllInstructions,
+    fmt: &mut Formatter,
+) {
+    fmtln!(
+        fmt,
+        ";;;; `InstFormatKind` ;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;;",
+    );
+    fmt.empty_line();
+    fmt.line("(type InstFormatKind");
+    fmt.indent(|fmt| {
+        fmt.line("(enum");
+        fmt.indent(|fmt| {
+            for format in formats {
+                fmtln!(fmt, "{}", format.name);
+            }
+        });
+        fmt.line(")");
+    });
+    fmt.line(")");
+    fmt.empty_line();
+
+    fmt.line("(decl pure opcode_format (Opcode) InstFormatKind)");
+    for inst in instructions {
+        fmtln!(
+            fmt,
+            "(rule (opcode_format (Opcode.{})) (InstFormatKind.{}))",
+            inst.camel_name,
+            inst.format.name
+        );
+    }
+    fmt.empty_line();
 }
 
 fn gen_opt_isle(
     formats: &[Rc<InstructionFormat>],
     instructions: &AllInstructions,
     fmt: &mut Formatter,
+    options: &IsleOptions,
 ) {
-    gen_common_isle(formats, instructions, fmt, IsleTarget::Opt);
+    gen_common_isle(formats, instructions, fmt, IsleTarget::Opt, options);
 }
 
 fn gen_lower_isle(
     formats: &[Rc<InstructionFormat>],
     instructions: &AllInstructions,
     fmt: &mut Formatter,
+    options: &IsleOptions,
 ) {
-    gen_common_isle(formats, instructions, fmt, IsleTarget::Lower);
+    gen_common_isle(formats, instructions, fmt, IsleTarget::Lower, options);
+}
+
+/// Generate a comment describing the root matchers which every per-instruction
+/// extractor below first matches against, to help tell apart a failure to
+/// match the instruction itself from a failure of one of its sub-extractors.
+fn gen_root_matcher_doc(isle_target: IsleTarget, fmt: &mut Formatter) {
+    let roots: &[&str] = match isle_target {
+        IsleTarget::Opt => &[
+            "`(inst_data_value Type InstructionData) -> Value` for instructions with a",
+            "single result and no side effects, and `(inst_data InstructionData) -> Inst`",
+            "for all other instructions.",
+        ],
+        IsleTarget::Lower => &["`(inst_data_value InstructionData) -> Inst` for all instructions."],
+    };
+    fmt.line(";; Each extractor below is rooted at an external extractor which matches the");
+    fmt.line(";; `InstructionData` of an instruction and its `Opcode`, declared in the");
+    fmt.line(";; prelude:");
+    fmt.line(";;");
+    for line in roots {
+        fmt.comment(format!("  {line}"));
+    }
+    fmt.line(";;");
+    fmt.line(";; The operands and immediates of the instruction are then matched by the");
+    fmt.line(";; nested sub-extractors, so if a rule fails to match while the root matched,");
+    fmt.line(";; then it's one of those sub-extractors which rejected the instruction.");
+    fmt.empty_line();
 }
 
 /// Generate an `enum` immediate in ISLE.
@@ -1196,6 +1492,18 @@ fn gen_numerics_isle(isle: &mut Formatter, rust: &mut Formatter) {
     fmtln!(rust, "}}");
 }
 
+/// Append a comment with a hash of everything generated into `fmt` so far, if
+/// enabled.
+fn gen_hash_trailer(fmt: &mut Formatter, options: &IsleOptions) {
+    if !options.hash_trailer {
+        return;
+    }
+    let hash = fmt.content_hash();
+    fmt.comment(format!(
+        "content-hash: fnv1a64:{hash:016x} (of all preceding lines of this file)"
+    ));
+}
+
 pub(crate) fn generate(
     formats: &[Rc<InstructionFormat>],
     all_inst: &AllInstructions,
@@ -1204,22 +1512,27 @@ pub(crate) fn generate(
     isle_opt_filename: &str,
     isle_lower_filename: &str,
     isle_dir: &std::path::Path,
+    options: &IsleOptions,
 ) -> Result<(), error::Error> {
     // Numerics
     let mut isle_fmt = Formatter::new(Language::Isle);
     let mut rust_fmt = Formatter::new(Language::Rust);
     gen_numerics_isle(&mut isle_fmt, &mut rust_fmt);
+    gen_hash_trailer(&mut isle_fmt, options);
+    gen_hash_trailer(&mut rust_fmt, options);
     isle_fmt.write(isle_numerics_filename, isle_dir)?;
     rust_fmt.write(rust_numerics_filename, isle_dir)?;
 
     // ISLE DSL: mid-end ("opt") generated bindings.
     let mut fmt = Formatter::new(Language::Isle);
-    gen_opt_isle(&formats, all_inst, &mut fmt);
+    gen_opt_isle(&formats, all_inst, &mut fmt, options);
+    gen_hash_trailer(&mut fmt, options);
     fmt.write(isle_opt_filename, isle_dir)?;
 
     // ISLE DSL: lowering generated bindings.
     let mut fmt = Formatter::new(Language::Isle);
-    gen_lower_isle(&formats, all_inst, &mut fmt);
+    gen_lower_isle(&formats, all_inst, &mut fmt, options);
+    gen_hash_trailer(&mut fmt, options);
     fmt.write(isle_lower_filename, isle_dir)?;
 
     Ok(())
@@ -4,6 +4,8 @@
 use cranelift_srcgen::{Formatter, Language, error};
 use shared::Definitions;
 
+pub use gen_isle::{ISLE_SCHEMA_VERSION, IsleOptions};
+
 #[macro_use]
 mod cdsl;
 
@@ -77,12 +79,13 @@ fn generate_rust_for_shared_defs(
 /// Generates all the ISLE source files used in Cranelift from the meta-language.
 pub fn generate_isle(isle_dir: &std::path::Path) -> Result<(), error::Error> {
     let shared_defs = shared::define();
-    generate_isle_for_shared_defs(&shared_defs, isle_dir)
+    generate_isle_for_shared_defs(&shared_defs, isle_dir, &IsleOptions::default())
 }
 
 fn generate_isle_for_shared_defs(
     shared_defs: &Definitions,
     isle_dir: &std::path::Path,
+    isle_options: &IsleOptions,
 ) -> Result<(), error::Error> {
     gen_isle::generate(
         &shared_defs.all_formats,
@@ -92,6 +95,7 @@ fn generate_isle_for_shared_defs(
         "clif_opt.isle",
         "clif_lower.isle",
         isle_dir,
+        isle_options,
     )?;
 
     #[cfg(feature = "pulley")]
@@ -128,10 +132,21 @@ pub fn generate(
     isas: &[isa::Isa],
     out_dir: &std::path::Path,
     isle_dir: &std::path::Path,
+) -> Result<(), error::Error> {
+    generate_with_isle_options(isas, out_dir, isle_dir, &IsleOptions::default())
+}
+
+/// Same as [`generate`], but with control over optional extras in the
+/// generated ISLE.
+pub fn generate_with_isle_options(
+    isas: &[isa::Isa],
+    out_dir: &std::path::Path,
+    isle_dir: &std::path::Path,
+    isle_options: &IsleOptions,
 ) -> Result<(), error::Error> {
     let shared_defs = shared::define();
     generate_rust_for_shared_defs(&shared_defs, isas, out_dir)?;
-    generate_isle_for_shared_defs(&shared_defs, isle_dir)?;
+    generate_isle_for_shared_defs(&shared_defs, isle_dir, isle_options)?;
 
     let insts = cranelift_assembler_x64_meta::instructions::list();
     generate_isle_for_assembler(&insts, isle_dir)?;
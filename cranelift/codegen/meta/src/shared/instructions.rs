@@ -536,7 +536,8 @@ fn define_simd_arithmetic(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", Int), Operand::new("y", Int)])
-        .operands_out(vec![Operand::new("a", Int)]),
+        .operands_out(vec![Operand::new("a", Int)])
+        .commutative(),
     );
 
     ig.push(
@@ -548,7 +549,8 @@ fn define_simd_arithmetic(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", Int), Operand::new("y", Int)])
-        .operands_out(vec![Operand::new("a", Int)]),
+        .operands_out(vec![Operand::new("a", Int)])
+        .commutative(),
     );
 
     ig.push(
@@ -560,7 +562,8 @@ fn define_simd_arithmetic(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", Int), Operand::new("y", Int)])
-        .operands_out(vec![Operand::new("a", Int)]),
+        .operands_out(vec![Operand::new("a", Int)])
+        .commutative(),
     );
 
     ig.push(
@@ -572,7 +575,8 @@ fn define_simd_arithmetic(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", Int), Operand::new("y", Int)])
-        .operands_out(vec![Operand::new("a", Int)]),
+        .operands_out(vec![Operand::new("a", Int)])
+        .commutative(),
     );
 
     let IxN = &TypeVar::new(
@@ -596,7 +600,8 @@ fn define_simd_arithmetic(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", IxN), Operand::new("y", IxN)])
-        .operands_out(vec![Operand::new("a", IxN)]),
+        .operands_out(vec![Operand::new("a", IxN)])
+        .commutative(),
     );
 
     ig.push(
@@ -612,7 +617,8 @@ fn define_simd_arithmetic(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", IxN), Operand::new("y", IxN)])
-        .operands_out(vec![Operand::new("a", IxN)]),
+        .operands_out(vec![Operand::new("a", IxN)])
+        .commutative(),
     );
 
     ig.push(
@@ -630,7 +636,8 @@ fn define_simd_arithmetic(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", IxN), Operand::new("y", IxN)])
-        .operands_out(vec![Operand::new("a", IxN)]),
+        .operands_out(vec![Operand::new("a", IxN)])
+        .commutative(),
     );
 
     ig.push(
@@ -1803,7 +1810,8 @@ pub(crate) fn define(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", Int), Operand::new("y", Int)])
-        .operands_out(vec![Operand::new("a", Int)]),
+        .operands_out(vec![Operand::new("a", Int)])
+        .commutative(),
     );
 
     ig.push(
@@ -1859,7 +1867,8 @@ pub(crate) fn define(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", Int), Operand::new("y", Int)])
-        .operands_out(vec![Operand::new("a", Int)]),
+        .operands_out(vec![Operand::new("a", Int)])
+        .commutative(),
     );
 
     ig.push(
@@ -1874,7 +1883,8 @@ pub(crate) fn define(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", Int), Operand::new("y", Int)])
-        .operands_out(vec![Operand::new("a", Int)]),
+        .operands_out(vec![Operand::new("a", Int)])
+        .commutative(),
     );
 
     ig.push(
@@ -1889,7 +1899,8 @@ pub(crate) fn define(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", Int), Operand::new("y", Int)])
-        .operands_out(vec![Operand::new("a", Int)]),
+        .operands_out(vec![Operand::new("a", Int)])
+        .commutative(),
     );
 
     let I16or32 = &TypeVar::new(
@@ -2376,7 +2387,8 @@ pub(crate) fn define(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", bits), Operand::new("y", bits)])
-        .operands_out(vec![Operand::new("a", bits)]),
+        .operands_out(vec![Operand::new("a", bits)])
+        .commutative(),
     );
 
     ig.push(
@@ -2388,7 +2400,8 @@ pub(crate) fn define(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", bits), Operand::new("y", bits)])
-        .operands_out(vec![Operand::new("a", bits)]),
+        .operands_out(vec![Operand::new("a", bits)])
+        .commutative(),
     );
 
     ig.push(
@@ -2400,7 +2413,8 @@ pub(crate) fn define(
             &formats.binary,
         )
         .operands_in(vec![Operand::new("x", bits), Operand::new("y", bits)])
-        .operands_out(vec![Operand::new("a", bits)]),
+        .operands_out(vec![Operand::new("a", bits)])
+        .commutative(),
     );
 
     ig.push(
@@ -231,6 +231,18 @@ impl<'a, 'b, 'c> generated_code::Context for IsleContext<'a, 'b, 'c> {
         [arg0, arg1]
     }
 
+    type value_array_2_commuted_etor_returns =
+        generated_code::ContextIterWrapper<ConstructorVec<(Value, Value)>, Self>;
+
+    fn value_array_2_commuted_etor(
+        &mut self,
+        arr: &ValueArray2,
+        returns: &mut Self::value_array_2_commuted_etor_returns,
+    ) {
+        let [a, b] = *arr;
+        returns.extend([(a, b), (b, a)]);
+    }
+
     fn value_array_3_ctor(&mut self, arg0: Value, arg1: Value, arg2: Value) -> ValueArray3 {
         [arg0, arg1, arg2]
     }
@@ -150,6 +150,29 @@ impl Formatter {
         self.lines.push(indented_line);
     }
 
+    /// Get the number of lines added so far.
+    pub fn num_lines(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Insert all the lines of `other` before the line at `index`.
+    pub fn insert(&mut self, index: usize, other: Formatter) {
+        self.lines.splice(index..index, other.lines);
+    }
+
+    /// Compute a hash of all the lines added so far.
+    ///
+    /// This is a 64-bit FNV-1a hash of the exact bytes that [`Formatter::write`]
+    /// would emit, so it is stable across platforms and Rust versions.
+    pub fn content_hash(&self) -> u64 {
+        self.lines
+            .iter()
+            .flat_map(|l| l.bytes())
+            .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+            })
+    }
+
     /// Pushes an empty line.
     pub fn empty_line(&mut self) {
         self.lines.push("\n".to_string());
@@ -543,4 +566,19 @@ match x {
         );
         assert_eq!(fmt.lines, expected_lines);
     }
+
+    #[test]
+    fn content_hash_is_stable() {
+        let fmt = Formatter::new(Language::Isle);
+        assert_eq!(fmt.content_hash(), 0xcbf2_9ce4_8422_2325);
+
+        let mut fmt = Formatter::new(Language::Isle);
+        fmt.line("a");
+        // The FNV-1a hash of `"a\n"`.
+        assert_eq!(fmt.content_hash(), 0x089b_dc07_b544_e7b2);
+
+        let mut other = Formatter::new(Language::Isle);
+        other.line("b");
+        assert_ne!(fmt.content_hash(), other.content_hash());
+    }
 }
@@ -334,6 +334,7 @@ impl wasmtime_environ::Compiler for Compiler {
         }
 
         let needs_gc_heap = func_env.needs_gc_heap();
+        let instructions = func_env.instructions();
 
         if let Some((_, slot_builder)) = func_env.state_slot {
             compiler.cx.debug_slot_descriptor = Some(slot_builder);
@@ -346,6 +347,7 @@ impl wasmtime_environ::Compiler for Compiler {
         Ok(CompiledFunctionBody {
             code: box_dyn_any_compiler_context(Some(compiler.cx)),
             needs_gc_heap,
+            instructions,
         })
     }
 
@@ -472,6 +474,7 @@ impl wasmtime_environ::Compiler for Compiler {
         Ok(CompiledFunctionBody {
             code: box_dyn_any_compiler_context(Some(compiler.cx)),
             needs_gc_heap: false,
+            instructions: Default::default(),
         })
     }
 
@@ -828,6 +831,7 @@ impl wasmtime_environ::Compiler for Compiler {
         Ok(CompiledFunctionBody {
             code: box_dyn_any_compiler_context(Some(compiler.cx)),
             needs_gc_heap: false,
+            instructions: Default::default(),
         })
     }
 
@@ -1439,6 +1443,7 @@ impl Compiler {
         Ok(CompiledFunctionBody {
             code: box_dyn_any_compiler_context(Some(compiler.cx)),
             needs_gc_heap: false,
+            instructions: Default::default(),
         })
     }
 }
@@ -1612,6 +1612,7 @@ impl ComponentCompiler for Compiler {
         Ok(CompiledFunctionBody {
             code: super::box_dyn_any_compiler_context(Some(compiler.cx)),
             needs_gc_heap: false,
+            instructions: Default::default(),
         })
     }
 
@@ -1718,6 +1719,7 @@ impl ComponentCompiler for Compiler {
         Ok(CompiledFunctionBody {
             code: super::box_dyn_any_compiler_context(Some(compiler.cx)),
             needs_gc_heap: false,
+            instructions: Default::default(),
         })
     }
 }
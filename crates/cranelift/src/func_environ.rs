@@ -28,10 +28,10 @@ use wasmtime_core::math::f64_cvt_to_int_bounds;
 use wasmtime_environ::{
     BuiltinFunctionIndex, DataIndex, DefinedFuncIndex, ElemIndex, EngineOrModuleTypeIndex,
     FrameStateSlotBuilder, FrameValType, FuncIndex, FuncKey, GlobalConstValue, GlobalIndex,
-    IndexType, Memory, MemoryIndex, Module, ModuleInternedTypeIndex, ModuleTranslation,
-    ModuleTypesBuilder, PtrSize, Table, TableIndex, TagIndex, TripleExt, Tunables, TypeConvert,
-    TypeIndex, VMOffsets, WasmCompositeInnerType, WasmFuncType, WasmHeapTopType, WasmHeapType,
-    WasmRefType, WasmResult, WasmValType,
+    IndexType, InstructionUsage, Memory, MemoryIndex, Module, ModuleInternedTypeIndex,
+    ModuleTranslation, ModuleTypesBuilder, PtrSize, Table, TableIndex, TagIndex, TripleExt,
+    Tunables, TypeConvert, TypeIndex, VMOffsets, WasmCompositeInnerType, WasmFuncType,
+    WasmHeapTopType, WasmHeapType, WasmRefType, WasmResult, WasmValType,
 };
 use wasmtime_environ::{FUNCREF_INIT_BIT, FUNCREF_MASK};
 
@@ -130,6 +130,7 @@ pub struct FuncEnvironment<'module_environment> {
     wasm_func_ty: &'module_environment WasmFuncType,
     sig_ref_to_ty: SecondaryMap<ir::SigRef, Option<&'module_environment WasmFuncType>>,
     needs_gc_heap: bool,
+    instructions: InstructionUsage,
     entities: WasmEntities,
 
     /// Translation state at the given point.
@@ -244,6 +245,7 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
             wasm_func_ty,
             sig_ref_to_ty: SecondaryMap::default(),
             needs_gc_heap: false,
+            instructions: InstructionUsage::default(),
             entities: WasmEntities::default(),
             stacks: FuncTranslationStacks::new(),
 
@@ -1205,6 +1207,16 @@ impl<'module_environment> FuncEnvironment<'module_environment> {
         self.needs_gc_heap
     }
 
+    /// Which classes of instructions has this function used?
+    pub fn instructions(&self) -> InstructionUsage {
+        self.instructions
+    }
+
+    /// Record the declaration of a local of type `ty` in this function.
+    pub(crate) fn record_local(&mut self, ty: WasmValType) {
+        self.instructions.record_local(ty);
+    }
+
     /// Get the number of Wasm parameters for the given function.
     pub(crate) fn num_params_for_func(&self, function_index: FuncIndex) -> usize {
         let ty = self.module.functions[function_index]
@@ -3844,6 +3856,7 @@ impl FuncEnvironment<'_> {
         _operand_types: Option<&[WasmValType]>,
         builder: &mut FunctionBuilder,
     ) -> WasmResult<()> {
+        self.instructions.record(op);
         if self.tunables.consume_fuel {
             self.fuel_before_op(op, builder, self.is_reachable());
         }
@@ -155,6 +155,8 @@ fn parse_local_decls(
         let count = reader.read_var_u32()?;
         let ty = reader.read()?;
         validator.define_locals(pos, count, ty)?;
+        let wasm_ty = environ.convert_valtype(ty)?;
+        environ.record_local(wasm_ty);
         declare_locals(builder, count, ty, &mut next_local, environ)?;
     }
 
@@ -0,0 +1,197 @@
+use crate::WasmValType;
+use wasmparser::{Operator, WasmFeatures};
+
+/// Classes of instructions found in a function body.
+///
+/// The types of the function's locals are taken into account as well, since
+/// for example declaring a `v128` local requires SIMD even if no SIMD
+/// instruction is used.
+///
+/// This is recorded by a [`Compiler`](crate::Compiler) while it visits each
+/// operator of a function body during translation, so that it doesn't require
+/// a separate pass over the code. It's then accumulated into the flags of the
+/// [`Module`](crate::Module) that the function belongs to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InstructionUsage {
+    /// Whether any floating-point instruction which may produce a NaN is
+    /// used, see [`InstructionUsage::may_produce_nan`].
+    pub floating_point: bool,
+
+    /// The WebAssembly proposals that the instructions and locals belong to.
+    ///
+    /// Relaxed SIMD instructions also require SIMD, see
+    /// [`InstructionUsage::proposal`].
+    pub features: WasmFeatures,
+}
+
+impl Default for InstructionUsage {
+    fn default() -> Self {
+        InstructionUsage {
+            floating_point: false,
+            features: WasmFeatures::empty(),
+        }
+    }
+}
+
+impl InstructionUsage {
+    /// Records the use of `op`.
+    #[inline]
+    pub fn record(&mut self, op: &Operator<'_>) {
+        self.floating_point |= Self::may_produce_nan(op);
+        self.features |= Self::proposal(op);
+    }
+
+    /// Records the declaration of a local of type `ty`.
+    pub fn record_local(&mut self, ty: WasmValType) {
+        self.features |= ty.required_features();
+    }
+
+    /// Accumulates the usage of `other` into `self`.
+    pub fn merge(&mut self, other: InstructionUsage) {
+        self.floating_point |= other.floating_point;
+        self.features |= other.features;
+    }
+
+    /// Returns whether `op` is a floating-point instruction which may produce
+    /// a NaN from non-NaN inputs or propagate a NaN operand, in which case the
+    /// bit pattern of the resulting NaN is nondeterministic.
+    ///
+    /// Instructions which only move, compare, or reinterpret the bits of
+    /// floating-point values, such as loads, stores, constants, `abs`, `neg`,
+    /// `copysign`, and `pmin`/`pmax`, are deterministic and aren't included.
+    /// Nor are conversions from integers, which can't produce a NaN.
+    pub fn may_produce_nan(op: &Operator<'_>) -> bool {
+        use Operator::*;
+        matches!(
+            op,
+            F32Add
+                | F32Sub
+                | F32Mul
+                | F32Div
+                | F32Sqrt
+                | F32Min
+                | F32Max
+                | F32Ceil
+                | F32Floor
+                | F32Trunc
+                | F32Nearest
+                | F32DemoteF64
+                | F64Add
+                | F64Sub
+                | F64Mul
+                | F64Div
+                | F64Sqrt
+                | F64Min
+                | F64Max
+                | F64Ceil
+                | F64Floor
+                | F64Trunc
+                | F64Nearest
+                | F64PromoteF32
+                | F32x4Add
+                | F32x4Sub
+                | F32x4Mul
+                | F32x4Div
+                | F32x4Sqrt
+                | F32x4Min
+                | F32x4Max
+                | F32x4Ceil
+                | F32x4Floor
+                | F32x4Trunc
+                | F32x4Nearest
+                | F32x4DemoteF64x2Zero
+                | F64x2Add
+                | F64x2Sub
+                | F64x2Mul
+                | F64x2Div
+                | F64x2Sqrt
+                | F64x2Min
+                | F64x2Max
+                | F64x2Ceil
+                | F64x2Floor
+                | F64x2Trunc
+                | F64x2Nearest
+                | F64x2PromoteLowF32x4
+                | F32x4RelaxedMadd
+                | F32x4RelaxedNmadd
+                | F32x4RelaxedMin
+                | F32x4RelaxedMax
+                | F64x2RelaxedMadd
+                | F64x2RelaxedNmadd
+                | F64x2RelaxedMin
+                | F64x2RelaxedMax
+        )
+    }
+
+    /// Returns the features required by the proposal that `op` belongs to.
+    ///
+    /// Instructions from the MVP require no features, and relaxed SIMD
+    /// instructions are considered to be SIMD instructions as well.
+    pub fn proposal(op: &Operator<'_>) -> WasmFeatures {
+        const fn eq(a: &str, b: &str) -> bool {
+            let (a, b) = (a.as_bytes(), b.as_bytes());
+            if a.len() != b.len() {
+                return false;
+            }
+            let mut i = 0;
+            while i < a.len() {
+                if a[i] != b[i] {
+                    return false;
+                }
+                i += 1;
+            }
+            true
+        }
+
+        const fn features(proposal: &str) -> WasmFeatures {
+            const PROPOSALS: &[(&str, WasmFeatures)] = &[
+                ("sign_extension", WasmFeatures::SIGN_EXTENSION),
+                (
+                    "saturating_float_to_int",
+                    WasmFeatures::SATURATING_FLOAT_TO_INT,
+                ),
+                ("bulk_memory", WasmFeatures::BULK_MEMORY),
+                ("reference_types", WasmFeatures::REFERENCE_TYPES),
+                ("simd", WasmFeatures::SIMD),
+                (
+                    "relaxed_simd",
+                    WasmFeatures::SIMD.union(WasmFeatures::RELAXED_SIMD),
+                ),
+                ("threads", WasmFeatures::THREADS),
+                ("tail_call", WasmFeatures::TAIL_CALL),
+                ("exceptions", WasmFeatures::EXCEPTIONS),
+                ("legacy_exceptions", WasmFeatures::LEGACY_EXCEPTIONS),
+                ("function_references", WasmFeatures::FUNCTION_REFERENCES),
+                ("gc", WasmFeatures::GC),
+                ("memory_control", WasmFeatures::MEMORY_CONTROL),
+                ("stack_switching", WasmFeatures::STACK_SWITCHING),
+                ("wide_arithmetic", WasmFeatures::WIDE_ARITHMETIC),
+                (
+                    "shared_everything_threads",
+                    WasmFeatures::SHARED_EVERYTHING_THREADS,
+                ),
+                ("custom_descriptors", WasmFeatures::CUSTOM_DESCRIPTORS),
+            ];
+            let mut i = 0;
+            while i < PROPOSALS.len() {
+                if eq(PROPOSALS[i].0, proposal) {
+                    return PROPOSALS[i].1;
+                }
+                i += 1;
+            }
+            WasmFeatures::empty()
+        }
+
+        macro_rules! proposal {
+            ($( @$proposal:ident $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $ann:tt)*) => {
+                match op {
+                    $(
+                        Operator::$op { .. } => const { features(stringify!($proposal)) },
+                    )*
+                    _ => WasmFeatures::empty(),
+                }
+            };
+        }
+        wasmparser::for_each_operator!(proposal)
+    }
+}
@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 mod address_map;
 mod frame_table;
+mod instruction_usage;
 mod module_artifacts;
 mod module_environ;
 mod module_types;
@@ -25,6 +26,7 @@ mod trap_encoding;
 
 pub use self::address_map::*;
 pub use self::frame_table::*;
+pub use self::instruction_usage::*;
 pub use self::module_artifacts::*;
 pub use self::module_environ::*;
 pub use self::module_types::*;
@@ -175,6 +177,9 @@ pub struct CompiledFunctionBody {
     /// Whether the compiled function needs a GC heap to run; that is, whether
     /// it reads a struct field, allocates, an array, or etc...
     pub needs_gc_heap: bool,
+    /// The classes of instructions used by this function's body, for
+    /// functions compiled from wasm.
+    pub instructions: InstructionUsage,
 }
 
 /// An implementation of a compiler which can compile WebAssembly functions to
@@ -39,6 +39,12 @@ pub struct ObjectBuilder<'a> {
     ///
     /// This is optional and lazily created on demand.
     dwarf: Option<SectionId>,
+
+    /// The section identifier for the contents of custom sections retained
+    /// from the original wasm files.
+    ///
+    /// This is optional and lazily created on demand.
+    custom: Option<SectionId>,
 }
 
 impl<'a> ObjectBuilder<'a> {
@@ -55,6 +61,7 @@ impl<'a> ObjectBuilder<'a> {
             data,
             names: None,
             dwarf: None,
+            custom: None,
         }
     }
 
@@ -121,6 +128,7 @@ impl<'a> ObjectBuilder<'a> {
             data,
             data_align,
             passive_data,
+            custom_sections,
             wasm,
             ..
         } = translation;
@@ -213,6 +221,23 @@ impl<'a> ObjectBuilder<'a> {
             self.push_debuginfo(&mut dwarf, &debuginfo);
         }
 
+        // Copy the contents of any retained custom sections into their own
+        // section, recording where each one lives.
+        let custom_sections = custom_sections
+            .iter()
+            .map(|(name, data)| {
+                let section_id = *self.custom.get_or_insert_with(|| {
+                    self.obj.add_section(
+                        self.obj.segment_name(StandardSegment::Data).to_vec(),
+                        obj::ELF_WASMTIME_CUSTOM.as_bytes().to_vec(),
+                        SectionKind::ReadOnlyData,
+                    )
+                });
+                let offset = self.obj.append_section_data(section_id, data, 1);
+                (name.to_string(), offset..offset + data.len() as u64)
+            })
+            .collect();
+
         Ok(CompiledModuleInfo {
             module,
             func_names,
@@ -221,6 +246,7 @@ impl<'a> ObjectBuilder<'a> {
                 code_section_offset: debuginfo.wasm_file.code_section_offset,
                 has_wasm_debuginfo: self.tunables.parse_wasm_debuginfo,
                 dwarf,
+                custom_sections,
             },
             checksum: WasmChecksum::from_binary(wasm, self.tunables.recording),
         })
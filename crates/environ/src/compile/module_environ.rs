@@ -103,6 +103,10 @@ pub struct ModuleTranslation<'data> {
     /// Total size of all passive data pushed into `passive_data` so far.
     total_passive_data: u32,
 
+    /// Names and contents of all custom sections found in this module, in
+    /// order, if `Tunables::retain_custom_sections` is enabled.
+    pub custom_sections: Vec<(&'data str, &'data [u8])>,
+
     /// When we're parsing the code section this will be incremented so we know
     /// which function is currently being defined.
     code_index: u32,
@@ -128,6 +132,7 @@ impl<'data> ModuleTranslation<'data> {
             total_data: 0,
             passive_data: Vec::default(),
             total_passive_data: 0,
+            custom_sections: Vec::default(),
             code_index: 0,
             types: None,
         }
@@ -729,6 +734,11 @@ and for re-adding support for interface types you can see this issue:
     }
 
     fn register_custom_section(&mut self, section: &CustomSectionReader<'data>) {
+        if self.tunables.retain_custom_sections {
+            self.result
+                .custom_sections
+                .push((section.name(), section.data()));
+        }
         match section.as_known() {
             KnownCustom::Name(name) => {
                 let result = self.name_section(name);
@@ -1,9 +1,10 @@
 //! Definitions of compilation artifacts of the component compilation process
 //! which are serialized with `bincode` into output ELF files.
 
+use crate::prelude::*;
 use crate::{
     CompiledFunctionsTable, CompiledModuleInfo, PrimaryMap, StaticModuleIndex, WasmChecksum,
-    component::{Component, ComponentTypes, TypeComponentIndex},
+    component::{Component, ComponentTypes, TrampolineIndex, TrampolineKind, TypeComponentIndex},
 };
 use serde_derive::{Deserialize, Serialize};
 
@@ -22,6 +23,8 @@ pub struct ComponentArtifacts {
     pub static_modules: PrimaryMap<StaticModuleIndex, CompiledModuleInfo>,
     /// A checksum of the source Wasm binary from which the component was compiled.
     pub checksum: WasmChecksum,
+    /// The source Wasm binary itself, if it was requested to be preserved.
+    pub original_wasm: Option<Vec<u8>>,
 }
 
 /// Runtime state that a component retains to support its operation.
@@ -29,4 +32,6 @@ pub struct ComponentArtifacts {
 pub struct CompiledComponentInfo {
     /// Type information calculated during translation about this component.
     pub component: Component,
+    /// The classification of each trampoline of this component.
+    pub trampoline_kinds: PrimaryMap<TrampolineIndex, TrampolineKind>,
 }
@@ -42,6 +42,9 @@ use wasmparser::component_types::ComponentCoreModuleTypeId;
 /// High-level representation of a component as a "data-flow graph".
 #[derive(Default)]
 pub struct ComponentDfg {
+    /// Same as `Component::name`
+    pub name: Option<String>,
+
     /// Same as `Component::import_types`
     pub import_types: PrimaryMap<ImportIndex, (String, TypeDef)>,
 
@@ -674,6 +677,7 @@ impl ComponentDfg {
                 num_runtime_reallocs: linearize.runtime_reallocs.len() as u32,
                 num_runtime_callbacks: linearize.runtime_callbacks.len() as u32,
                 num_runtime_instances: linearize.runtime_instances.len() as u32,
+                name: self.name,
                 imports: self.imports,
                 import_types: self.import_types,
                 num_runtime_component_instances: self.num_runtime_component_instances,
@@ -932,13 +936,15 @@ impl LinearizeDfg<'_> {
             } => {
                 let index = LoweredIndex::from_u32(self.num_lowerings);
                 self.num_lowerings += 1;
+                let options = self.options(*options);
                 self.initializers.push(GlobalInitializer::LowerImport {
                     index,
                     import: *import,
+                    options,
                 });
                 info::Trampoline::LowerImport {
                     index,
-                    options: self.options(*options),
+                    options,
                     lower_ty: *lower_ty,
                 }
             }
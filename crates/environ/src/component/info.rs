@@ -72,6 +72,10 @@ pub struct ComponentTranslation {
 /// this is going to undergo a lot of churn.
 #[derive(Default, Debug, Serialize, Deserialize)]
 pub struct Component {
+    /// The name of this component as recorded in its `component-name` custom
+    /// section, if any.
+    pub name: Option<String>,
+
     /// A list of typed values that this component imports.
     ///
     /// Note that each name is given an `ImportIndex` here for the next map to
@@ -265,6 +269,10 @@ pub enum GlobalInitializer {
         ///
         /// It's guaranteed that this `RuntimeImportIndex` points to a function.
         import: RuntimeImportIndex,
+
+        /// The canonical ABI options used when lowering this function, the
+        /// same as those of the corresponding `Trampoline::LowerImport`.
+        options: OptionsIndex,
     },
 
     /// A core wasm linear memory is going to be saved into the
@@ -1258,4 +1266,29 @@ impl Trampoline {
             ThreadYieldToSuspended { .. } => format!("thread-yield-to-suspended"),
         }
     }
+
+    /// Returns the coarse classification of this trampoline which is retained
+    /// at runtime.
+    pub fn kind(&self) -> TrampolineKind {
+        match self {
+            Trampoline::LowerImport { .. } => TrampolineKind::LowerImport,
+            Trampoline::ResourceDrop { .. } => TrampolineKind::ResourceDrop,
+            Trampoline::Trap => TrampolineKind::Trap,
+            _ => TrampolineKind::Other,
+        }
+    }
+}
+
+/// A coarse classification of a `Trampoline`, retained at runtime in
+/// `CompiledComponentInfo` for introspection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrampolineKind {
+    /// A `Trampoline::LowerImport`.
+    LowerImport,
+    /// A `Trampoline::ResourceDrop`.
+    ResourceDrop,
+    /// A `Trampoline::Trap`.
+    Trap,
+    /// Any other trampoline.
+    Other,
 }
@@ -18,7 +18,10 @@ use wasmparser::component_types::{
     ComponentFuncTypeId, ComponentInstanceTypeId, ComponentValType,
 };
 use wasmparser::types::Types;
-use wasmparser::{Chunk, ComponentImportName, Encoding, Parser, Payload, Validator};
+use wasmparser::{
+    Chunk, ComponentImportName, ComponentName, ComponentNameSectionReader, Encoding, KnownCustom,
+    Parser, Payload, Validator,
+};
 
 mod adapt;
 pub use self::adapt::*;
@@ -167,6 +170,10 @@ struct Translation<'data> {
     /// component has finished, e.g. for the `inline` pass, but beforehand this
     /// is set to `None`.
     types: Option<Types>,
+
+    /// The name of this component from its `component-name` custom section,
+    /// if present.
+    name: Option<&'data str>,
 }
 
 // NB: the type information contained in `LocalInitializer` should always point
@@ -1346,11 +1353,16 @@ impl<'a, 'data> Translator<'a, 'data> {
                 }
             }
 
-            // All custom sections are ignored by Wasmtime at this time.
-            //
-            // FIXME(WebAssembly/component-model#14): probably want to specify
-            // and parse a `name` section here.
-            Payload::CustomSection { .. } => {}
+            // The only custom section Wasmtime looks at is the
+            // `component-name` section, and only for the component-level name.
+            // Everything else is ignored at this time.
+            Payload::CustomSection(section) => {
+                if let KnownCustom::ComponentName(names) = section.as_known() {
+                    if let Err(e) = self.component_name_section(names) {
+                        log::warn!("failed to parse component name section {e:?}");
+                    }
+                }
+            }
 
             // Anything else is either not reachable since we never enable the
             // feature in Wasmtime or we do enable it and it's a bug we don't
@@ -1366,6 +1378,18 @@ impl<'a, 'data> Translator<'a, 'data> {
         Ok(Action::KeepGoing)
     }
 
+    fn component_name_section(
+        &mut self,
+        names: ComponentNameSectionReader<'data>,
+    ) -> wasmparser::Result<()> {
+        for subsection in names {
+            if let ComponentName::Component { name, .. } = subsection? {
+                self.result.name = Some(name);
+            }
+        }
+        Ok(())
+    }
+
     fn instantiate_module(
         &mut self,
         module: ModuleIndex,
@@ -138,6 +138,7 @@ pub(super) fn run(
     inliner.result.num_future_tables = types.num_future_tables();
     inliner.result.num_stream_tables = types.num_stream_tables();
     inliner.result.num_error_context_tables = types.num_error_context_tables();
+    inliner.result.name = result.name.map(|s| s.to_string());
 
     Ok(inliner.result)
 }
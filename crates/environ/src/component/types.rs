@@ -1,5 +1,5 @@
 use crate::component::{MAX_FLAT_PARAMS, MAX_FLAT_RESULTS};
-use crate::{EntityType, ModuleInternedTypeIndex, ModuleTypes, PrimaryMap};
+use crate::{EntityType, ModuleInternedTypeIndex, ModuleTypes, PrimaryMap, WasmValType};
 use crate::{TypeTrace, prelude::*};
 use core::hash::{Hash, Hasher};
 use core::ops::Index;
@@ -299,6 +299,7 @@ pub struct ComponentTypes {
     pub(super) error_context_tables:
         PrimaryMap<TypeComponentLocalErrorContextTableIndex, TypeErrorContextTable>,
     pub(super) fixed_length_lists: PrimaryMap<TypeFixedLengthListIndex, TypeFixedLengthList>,
+    pub(super) resource_reps: PrimaryMap<ResourceIndex, Option<WasmValType>>,
 }
 
 impl TypeTrace for ComponentTypes {
@@ -380,6 +381,50 @@ impl ComponentTypes {
     pub fn push_resource_table(&mut self, table: TypeResourceTable) -> TypeResourceTableIndex {
         self.resource_tables.push(table)
     }
+
+    /// Returns the core wasm representation declared with `(rep ...)` for the
+    /// resource `index`.
+    ///
+    /// Returns `None` for resources imported into the top-level component
+    /// since their representation isn't known.
+    pub fn resource_rep(&self, index: ResourceIndex) -> Option<WasmValType> {
+        self.resource_reps.get(index).copied().flatten()
+    }
+
+    /// Returns an estimate, in bytes, of the memory occupied by the type
+    /// tables of this component.
+    ///
+    /// This counts the inline size of each entry of each table but not any
+    /// heap allocations owned by those entries, such as the names of record
+    /// fields, so the true footprint is somewhat larger.
+    pub fn estimated_size(&self) -> usize {
+        fn size<K: crate::EntityRef, V>(map: &PrimaryMap<K, V>) -> usize {
+            map.len() * core::mem::size_of::<V>()
+        }
+        size(&self.modules)
+            + size(&self.components)
+            + size(&self.component_instances)
+            + size(&self.functions)
+            + size(&self.lists)
+            + size(&self.records)
+            + size(&self.variants)
+            + size(&self.tuples)
+            + size(&self.enums)
+            + size(&self.flags)
+            + size(&self.options)
+            + size(&self.results)
+            + size(&self.resource_tables)
+            + size(&self.futures)
+            + size(&self.future_tables)
+            + size(&self.streams)
+            + size(&self.stream_tables)
+            + size(&self.error_context_tables)
+            + size(&self.fixed_length_lists)
+            + size(&self.resource_reps)
+            + self.module_types.as_ref().map_or(0, |types| {
+                types.wasm_types().len() * core::mem::size_of::<crate::WasmSubType>()
+            })
+    }
 }
 
 macro_rules! impl_index {
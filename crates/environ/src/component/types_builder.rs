@@ -154,6 +154,14 @@ impl ComponentTypesBuilder {
         }
         let ty = self.component_types.components.push(component_ty);
 
+        let mut reps = vec![None; component.num_resources as usize];
+        for init in component.initializers.iter() {
+            if let GlobalInitializer::Resource(resource) = init {
+                reps[component.resource_index(resource.index).index()] = Some(resource.rep);
+            }
+        }
+        self.component_types.resource_reps = reps.into_iter().collect();
+
         self.component_types.module_types = Some(self.module_types.finish());
         (self.component_types, ty)
     }
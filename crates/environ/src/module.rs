@@ -345,6 +345,17 @@ pub struct Module {
     /// Does this module need a GC heap to run?
     pub needs_gc_heap: bool,
 
+    /// Does any function body in this module contain a floating-point
+    /// instruction which may produce a NaN? See
+    /// [`InstructionUsage::may_produce_nan`](crate::InstructionUsage::may_produce_nan).
+    pub uses_floating_point: bool,
+
+    /// The WebAssembly features required by the instructions and locals of
+    /// the function bodies in this module, as the bits of a
+    /// [`wasmparser::WasmFeatures`]. See
+    /// [`InstructionUsage::features`](crate::InstructionUsage::features).
+    pub function_features: u64,
+
     /// Number of functions that "escape" from this module may need to have a
     /// `VMFuncRef` constructed for them.
     ///
@@ -409,6 +420,8 @@ impl Module {
             num_imported_globals: Default::default(),
             num_imported_tags: Default::default(),
             needs_gc_heap: Default::default(),
+            uses_floating_point: Default::default(),
+            function_features: Default::default(),
             num_escaped_funcs: Default::default(),
             functions: Default::default(),
             tables: Default::default(),
@@ -701,6 +714,8 @@ impl TypeTrace for Module {
             num_imported_tags: _,
             num_escaped_funcs: _,
             needs_gc_heap: _,
+            uses_floating_point: _,
+            function_features: _,
             functions,
             tables,
             memories: _,
@@ -753,6 +768,8 @@ impl TypeTrace for Module {
             num_imported_tags: _,
             num_escaped_funcs: _,
             needs_gc_heap: _,
+            uses_floating_point: _,
+            function_features: _,
             functions,
             tables,
             memories: _,
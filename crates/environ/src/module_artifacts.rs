@@ -624,6 +624,11 @@ pub struct Metadata {
     /// Dwarf sections and the offsets at which they're stored in the
     /// ELF_WASMTIME_DWARF
     pub dwarf: Vec<(u8, Range<u64>)>,
+
+    /// Names of retained custom sections, in their original order, and the
+    /// offsets at which their contents are stored in the ELF_WASMTIME_CUSTOM
+    /// section.
+    pub custom_sections: Vec<(String, Range<u64>)>,
 }
 
 /// Value of a configured setting for a [`Compiler`](crate::Compiler)
@@ -177,6 +177,15 @@ pub const ELF_NAME_DATA: &'static str = ".name.wasm";
 /// metadata.
 pub const ELF_WASMTIME_DWARF: &str = ".wasmtime.dwarf";
 
+/// This is the name of the section in the final ELF image that contains the
+/// concatenation of the contents of all custom sections retained from the
+/// original wasm files.
+///
+/// This section is only present if custom sections are configured to be
+/// retained. Like `ELF_WASMTIME_DWARF` it's indexed by ranges stored in
+/// compilation metadata.
+pub const ELF_WASMTIME_CUSTOM: &str = ".wasmtime.custom";
+
 /// Workaround to implement `core::error::Error` until
 /// gimli-rs/object#747 is settled.
 pub struct ObjectCrateErrorWrapper(pub object::Error);
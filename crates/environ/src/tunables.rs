@@ -108,6 +108,10 @@ define_tunables! {
         /// offsets in the original file is generated.
         pub generate_address_map: bool,
 
+        /// Whether or not the contents of custom sections in core wasm modules
+        /// are retained in compiled artifacts.
+        pub retain_custom_sections: bool,
+
         /// Flag for the component module whether adapter modules have debug
         /// assertions baked into them.
         pub debug_adapter_modules: bool,
@@ -213,6 +217,7 @@ impl Tunables {
             guard_before_linear_memory: true,
             table_lazy_init: true,
             generate_address_map: true,
+            retain_custom_sections: false,
             debug_adapter_modules: false,
             relaxed_simd_deterministic: false,
             winch_callable: false,
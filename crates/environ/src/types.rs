@@ -4,6 +4,7 @@ use alloc::boxed::Box;
 use core::{fmt, ops::Range};
 use serde_derive::{Deserialize, Serialize};
 use smallvec::SmallVec;
+use wasmparser::WasmFeatures;
 use wasmtime_core::alloc::{TryClone, TryCollect as _};
 
 /// A trait for things that can trace all type-to-type edges, aka all type
@@ -214,6 +215,18 @@ impl WasmValType {
         }
     }
 
+    /// Returns the WebAssembly features that must be enabled to use this
+    /// type.
+    pub fn required_features(&self) -> WasmFeatures {
+        match self {
+            WasmValType::I32 | WasmValType::I64 | WasmValType::F32 | WasmValType::F64 => {
+                WasmFeatures::empty()
+            }
+            WasmValType::V128 => WasmFeatures::SIMD,
+            WasmValType::Ref(r) => r.required_features(),
+        }
+    }
+
     fn trampoline_type(&self) -> Self {
         match self {
             WasmValType::Ref(r) => WasmValType::Ref(WasmRefType {
@@ -302,6 +315,16 @@ impl WasmRefType {
     pub fn is_vmgcref_type_and_not_i31(&self) -> bool {
         self.heap_type.is_vmgcref_type_and_not_i31()
     }
+
+    /// Returns the WebAssembly features that must be enabled to use this
+    /// type.
+    pub fn required_features(&self) -> WasmFeatures {
+        let mut features = self.heap_type.required_features();
+        if !self.nullable {
+            features |= WasmFeatures::FUNCTION_REFERENCES;
+        }
+        features
+    }
 }
 
 impl fmt::Display for WasmRefType {
@@ -581,6 +604,31 @@ impl WasmHeapType {
         self.is_vmgcref_type() && *self != Self::I31
     }
 
+    /// Returns the WebAssembly features that must be enabled to use this
+    /// heap type.
+    pub fn required_features(&self) -> WasmFeatures {
+        match self {
+            WasmHeapType::Extern | WasmHeapType::Func => WasmFeatures::REFERENCE_TYPES,
+            WasmHeapType::ConcreteFunc(_) => WasmFeatures::FUNCTION_REFERENCES,
+            WasmHeapType::NoExtern
+            | WasmHeapType::NoFunc
+            | WasmHeapType::Any
+            | WasmHeapType::Eq
+            | WasmHeapType::I31
+            | WasmHeapType::Array
+            | WasmHeapType::ConcreteArray(_)
+            | WasmHeapType::Struct
+            | WasmHeapType::ConcreteStruct(_)
+            | WasmHeapType::None => WasmFeatures::GC,
+            WasmHeapType::Exn | WasmHeapType::ConcreteExn(_) | WasmHeapType::NoExn => {
+                WasmFeatures::EXCEPTIONS
+            }
+            WasmHeapType::Cont | WasmHeapType::ConcreteCont(_) | WasmHeapType::NoCont => {
+                WasmFeatures::STACK_SWITCHING
+            }
+        }
+    }
+
     /// Is this heap type the top of its type hierarchy?
     #[inline]
     pub fn is_top(&self) -> bool {
@@ -1369,6 +1417,40 @@ impl fmt::Display for WasmSubType {
     }
 }
 
+impl WasmSubType {
+    /// Returns the WebAssembly features that must be enabled to define this
+    /// type.
+    pub fn required_features(&self) -> WasmFeatures {
+        let storage = |ty: &WasmStorageType| match ty {
+            WasmStorageType::I8 | WasmStorageType::I16 => WasmFeatures::empty(),
+            WasmStorageType::Val(ty) => ty.required_features(),
+        };
+        let mut features = match &self.composite_type.inner {
+            WasmCompositeInnerType::Func(f) => f
+                .params()
+                .iter()
+                .chain(f.returns())
+                .fold(WasmFeatures::empty(), |acc, ty| {
+                    acc | ty.required_features()
+                }),
+            WasmCompositeInnerType::Array(a) => WasmFeatures::GC | storage(&a.0.element_type),
+            WasmCompositeInnerType::Struct(s) => s
+                .fields
+                .iter()
+                .fold(WasmFeatures::GC, |acc, f| acc | storage(&f.element_type)),
+            WasmCompositeInnerType::Cont(_) => WasmFeatures::STACK_SWITCHING,
+            WasmCompositeInnerType::Exn(_) => WasmFeatures::EXCEPTIONS,
+        };
+        if !self.is_final || self.supertype.is_some() {
+            features |= WasmFeatures::GC;
+        }
+        if self.composite_type.shared {
+            features |= WasmFeatures::SHARED_EVERYTHING_THREADS;
+        }
+        features
+    }
+}
+
 /// Implicitly define all of these helper functions to handle only unshared
 /// types; essentially, these act like `is_unshared_*` functions until shared
 /// support is implemented.
@@ -2014,6 +2096,34 @@ pub enum ConstOp {
 }
 
 impl ConstOp {
+    /// Returns the WebAssembly features that must be enabled to use this
+    /// operator in a constant expression.
+    pub fn required_features(&self) -> WasmFeatures {
+        match self {
+            ConstOp::I32Const(_)
+            | ConstOp::I64Const(_)
+            | ConstOp::F32Const(_)
+            | ConstOp::F64Const(_)
+            | ConstOp::GlobalGet(_) => WasmFeatures::empty(),
+            ConstOp::V128Const(_) => WasmFeatures::SIMD,
+            ConstOp::RefNull(_) | ConstOp::RefFunc(_) => WasmFeatures::REFERENCE_TYPES,
+            ConstOp::I32Add
+            | ConstOp::I32Sub
+            | ConstOp::I32Mul
+            | ConstOp::I64Add
+            | ConstOp::I64Sub
+            | ConstOp::I64Mul => WasmFeatures::EXTENDED_CONST,
+            ConstOp::RefI31
+            | ConstOp::StructNew { .. }
+            | ConstOp::StructNewDefault { .. }
+            | ConstOp::ArrayNew { .. }
+            | ConstOp::ArrayNewDefault { .. }
+            | ConstOp::ArrayNewFixed { .. }
+            | ConstOp::ExternConvertAny
+            | ConstOp::AnyConvertExtern => WasmFeatures::GC,
+        }
+    }
+
     /// Convert a `wasmparser::Operator` to a `ConstOp`.
     pub fn from_wasmparser(
         env: &dyn TypeConvert,
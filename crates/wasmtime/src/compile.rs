@@ -30,8 +30,9 @@ use std::{any::Any, borrow::Cow, collections::BTreeMap, mem, ops::Range};
 use wasmtime_environ::{
     Abi, CompiledFunctionBody, CompiledFunctionsTable, CompiledFunctionsTableBuilder,
     CompiledModuleInfo, Compiler, DefinedFuncIndex, FilePos, FinishedObject, FuncKey,
-    FunctionBodyData, InliningCompiler, IntraModuleInlining, ModuleEnvironment, ModuleTranslation,
-    ModuleTypes, ModuleTypesBuilder, ObjectKind, PrimaryMap, StaticModuleIndex, Tunables,
+    FunctionBodyData, InliningCompiler, InstructionUsage, IntraModuleInlining, ModuleEnvironment,
+    ModuleTranslation, ModuleTypes, ModuleTypesBuilder, ObjectKind, PrimaryMap, StaticModuleIndex,
+    Tunables,
     graphs::{EntityGraph, Graph as _},
 };
 #[cfg(feature = "component-model")]
@@ -91,10 +92,14 @@ pub(crate) fn build_module_artifacts<T: FinishedObject>(
     let unlinked_compile_outputs = compile_inputs.compile(engine)?;
     let PreLinkOutput {
         needs_gc_heap,
+        instructions,
         compiled_funcs,
         indices,
     } = unlinked_compile_outputs.pre_link();
     translation.module.needs_gc_heap |= needs_gc_heap;
+    if let Some(usage) = instructions.get(&translation.module_index()) {
+        set_instruction_usage(&mut translation.module, usage);
+    }
 
     // Emplace all compiled functions into the object file with any other
     // sections associated with code as well.
@@ -174,11 +179,15 @@ pub(crate) fn build_component_artifacts<T: FinishedObject>(
 
     let PreLinkOutput {
         needs_gc_heap,
+        instructions,
         compiled_funcs,
         indices,
     } = unlinked_compile_outputs.pre_link();
-    for (_, t) in &mut module_translations {
-        t.module.needs_gc_heap |= needs_gc_heap
+    for (i, t) in &mut module_translations {
+        t.module.needs_gc_heap |= needs_gc_heap;
+        if let Some(usage) = instructions.get(&i) {
+            set_instruction_usage(&mut t.module, usage);
+        }
     }
 
     let mut object = compiler.object(ObjectKind::Component)?;
@@ -196,6 +205,7 @@ pub(crate) fn build_component_artifacts<T: FinishedObject>(
 
     let info = CompiledComponentInfo {
         component: component.component,
+        trampoline_kinds: component.trampolines.values().map(|t| t.kind()).collect(),
     };
     let artifacts = ComponentArtifacts {
         info,
@@ -204,6 +214,10 @@ pub(crate) fn build_component_artifacts<T: FinishedObject>(
         types,
         static_modules: compilation_artifacts.modules,
         checksum: WasmChecksum::from_binary(binary, tunables.recording),
+        original_wasm: engine
+            .config()
+            .preserve_wasm_source
+            .then(|| binary.to_vec()),
     };
     object.serialize_info(&artifacts);
 
@@ -211,6 +225,46 @@ pub(crate) fn build_component_artifacts<T: FinishedObject>(
     Ok((result, Some(artifacts)))
 }
 
+/// Performs the same translation and validation of `binary` as
+/// `build_component_artifacts` without compiling anything.
+///
+/// Errors are reported with the same context as they would be during
+/// compilation so the two produce the same diagnostics.
+#[cfg(feature = "component-model")]
+pub(crate) fn validate_component(engine: &Engine, binary: &[u8]) -> Result<()> {
+    use wasmtime_environ::ScopeVec;
+    use wasmtime_environ::component::ComponentTypesBuilder;
+
+    let tunables = engine.tunables();
+    let scope = ScopeVec::new();
+    let mut validator = wasmparser::Validator::new_with_features(engine.features());
+    let mut types = ComponentTypesBuilder::new(&validator);
+    let (_component, mut module_translations) =
+        Translator::new(tunables, &mut validator, &mut types, &scope)
+            .translate(binary)
+            .context("failed to parse WebAssembly module")?;
+
+    let mut functions = Vec::new();
+    for (module, translation) in module_translations.iter_mut() {
+        for (def_func_index, func_body_data) in mem::take(&mut translation.function_body_inputs) {
+            let symbol = CompileInputs::function_symbol(translation, module, def_func_index);
+            functions.push((symbol, func_body_data));
+        }
+    }
+
+    engine.run_maybe_parallel(
+        functions,
+        |(symbol, FunctionBodyData { validator, body })| {
+            validator
+                .into_validator(Default::default())
+                .validate(&body)
+                .map_err(|e| wasmtime_environ::CompileError::Wasm(e.into()))
+                .with_context(|| format!("failed to compile: {symbol}"))
+        },
+    )?;
+    Ok(())
+}
+
 type CompileInput<'a> = Box<dyn FnOnce(&dyn Compiler) -> Result<CompileOutput<'a>> + Send + 'a>;
 
 struct CompileOutput<'a> {
@@ -381,6 +435,34 @@ impl<'a> CompileInputs<'a> {
         ret
     }
 
+    /// Returns the symbol name used for the defined function `def_func_index`
+    /// of `module`, both in the compiled object and in compilation errors.
+    fn function_symbol(
+        translation: &ModuleTranslation<'_>,
+        module: StaticModuleIndex,
+        def_func_index: DefinedFuncIndex,
+    ) -> String {
+        let func_index = translation.module.func_index(def_func_index);
+        match translation
+            .debuginfo
+            .name_section
+            .func_names
+            .get(&func_index)
+        {
+            Some(name) => format!(
+                "wasm[{}]::function[{}]::{}",
+                module.as_u32(),
+                func_index.as_u32(),
+                Self::clean_symbol(&name)
+            ),
+            None => format!(
+                "wasm[{}]::function[{}]",
+                module.as_u32(),
+                func_index.as_u32()
+            ),
+        }
+    }
+
     fn clean_symbol(name: &str) -> Cow<'_, str> {
         /// Maximum length of symbols generated in objects.
         const MAX_SYMBOL_LEN: usize = 96;
@@ -426,25 +508,7 @@ impl<'a> CompileInputs<'a> {
             for (def_func_index, func_body_data) in functions {
                 self.push_input(move |compiler| {
                     let key = FuncKey::DefinedWasmFunction(module, def_func_index);
-                    let func_index = translation.module.func_index(def_func_index);
-                    let symbol = match translation
-                        .debuginfo
-                        .name_section
-                        .func_names
-                        .get(&func_index)
-                    {
-                        Some(name) => format!(
-                            "wasm[{}]::function[{}]::{}",
-                            module.as_u32(),
-                            func_index.as_u32(),
-                            Self::clean_symbol(&name)
-                        ),
-                        None => format!(
-                            "wasm[{}]::function[{}]",
-                            module.as_u32(),
-                            func_index.as_u32()
-                        ),
-                    };
+                    let symbol = Self::function_symbol(translation, module, def_func_index);
                     let func_body = func_body_data.body.clone();
                     let data = func_body.get_binary_reader();
                     let offset = data.original_position();
@@ -990,11 +1054,18 @@ impl UnlinkedCompileOutputs<'_> {
 
         let mut indices = FunctionIndices::default();
         let mut needs_gc_heap = false;
+        let mut instructions = BTreeMap::<StaticModuleIndex, InstructionUsage>::new();
 
         // NB: Iteration over this `BTreeMap` ensures that we uphold
         // `compiled_func`'s sorted property.
         for output in self.outputs.into_values() {
             needs_gc_heap |= output.function.needs_gc_heap;
+            if let FuncKey::DefinedWasmFunction(module, _) = output.key {
+                instructions
+                    .entry(module)
+                    .or_default()
+                    .merge(output.function.instructions);
+            }
 
             let index = compiled_funcs.len();
             compiled_funcs.push((output.symbol, output.key, output.function.code));
@@ -1010,6 +1081,7 @@ impl UnlinkedCompileOutputs<'_> {
 
         PreLinkOutput {
             needs_gc_heap,
+            instructions,
             compiled_funcs,
             indices,
         }
@@ -1020,6 +1092,8 @@ impl UnlinkedCompileOutputs<'_> {
 struct PreLinkOutput {
     /// Whether or not any of these functions require a GC heap
     needs_gc_heap: bool,
+    /// The classes of instructions used by each module's defined functions.
+    instructions: BTreeMap<StaticModuleIndex, InstructionUsage>,
     /// The flattened list of (symbol name, FuncKey, compiled
     /// function) triples, as they will be laid out in the object
     /// file.
@@ -1145,6 +1219,12 @@ impl Artifacts {
     }
 }
 
+/// Records the classes of instructions used by `module`'s function bodies.
+fn set_instruction_usage(module: &mut wasmtime_environ::Module, usage: &InstructionUsage) {
+    module.uses_floating_point |= usage.floating_point;
+    module.function_features |= usage.features.bits();
+}
+
 /// Extend `dest` with `items` and return the range of indices in `dest` where
 /// they ended up.
 fn extend_with_range<T>(dest: &mut Vec<T>, items: impl IntoIterator<Item = T>) -> Range<u32> {
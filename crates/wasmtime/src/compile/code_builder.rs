@@ -819,6 +819,7 @@ impl std::hash::Hash for HashedEngineCompileEnv<'_> {
         self.0.tunables().hash(hasher);
         self.0.features().hash(hasher);
         config.wmemcheck.hash(hasher);
+        config.preserve_wasm_source.hash(hasher);
 
         // Catch accidental bugs of reusing across crate versions.
         config.module_version.hash(hasher);
@@ -186,6 +186,12 @@ pub struct Config {
     pub(crate) memory_guaranteed_dense_image_size: u64,
     pub(crate) force_memory_init_memfd: bool,
     pub(crate) wmemcheck: bool,
+    #[cfg(any(feature = "cranelift", feature = "winch"))]
+    pub(crate) preserve_wasm_source: bool,
+    #[cfg(all(feature = "runtime", feature = "component-model"))]
+    pub(crate) component_instantiate_observer: Option<Arc<ComponentInstantiateObserver>>,
+    #[cfg(feature = "component-model")]
+    pub(crate) shared_component_types: bool,
     #[cfg(feature = "coredump")]
     pub(crate) coredump_on_trap: bool,
     pub(crate) macos_use_mach_ports: bool,
@@ -195,6 +201,12 @@ pub struct Config {
     pub(crate) rr_config: RRConfig,
 }
 
+/// The type of callback configured with
+/// [`Config::component_instantiate_observer`].
+#[cfg(all(feature = "runtime", feature = "component-model"))]
+pub(crate) type ComponentInstantiateObserver =
+    dyn Fn(&crate::component::Component, &crate::component::Instance) + Send + Sync;
+
 /// User-provided configuration for the compiler.
 #[cfg(any(feature = "cranelift", feature = "winch"))]
 #[derive(Debug, Clone)]
@@ -293,6 +305,12 @@ impl Config {
             memory_guaranteed_dense_image_size: 16 << 20,
             force_memory_init_memfd: false,
             wmemcheck: false,
+            #[cfg(any(feature = "cranelift", feature = "winch"))]
+            preserve_wasm_source: false,
+            #[cfg(all(feature = "runtime", feature = "component-model"))]
+            component_instantiate_observer: None,
+            #[cfg(feature = "component-model")]
+            shared_component_types: false,
             #[cfg(feature = "coredump")]
             coredump_on_trap: false,
             macos_use_mach_ports: !cfg!(miri),
@@ -2022,6 +2040,24 @@ impl Config {
         self
     }
 
+    /// Configures whether compiled artifacts will retain the contents of the
+    /// custom sections found in core wasm modules.
+    ///
+    /// When enabled every custom section of a core wasm module, including
+    /// those nested within a component, is copied verbatim into the compiled
+    /// artifact. They can then be read back with
+    /// [`Component::module_custom_sections`] for example to retrieve
+    /// toolchain metadata such as source maps or build provenance.
+    ///
+    /// This configuration option is `false` by default since custom sections
+    /// are otherwise not needed at runtime and may be quite large.
+    ///
+    /// [`Component::module_custom_sections`]: crate::component::Component::module_custom_sections
+    pub fn retain_custom_sections(&mut self, retain: bool) -> &mut Self {
+        self.tunables.retain_custom_sections = Some(retain);
+        self
+    }
+
     /// Configures whether copy-on-write memory-mapped data is used to
     /// initialize a linear memory.
     ///
@@ -2125,6 +2161,78 @@ impl Config {
         self
     }
 
+    /// Configures whether the original WebAssembly binary is embedded in the
+    /// compiled artifact of a component.
+    ///
+    /// When enabled the bytes passed to compilation are kept alongside the
+    /// compiled code, including in the output of
+    /// [`Component::serialize`](crate::component::Component::serialize), and
+    /// can be retrieved with
+    /// [`Component::original_wasm`](crate::component::Component::original_wasm).
+    /// This is intended for debugging, to correlate a compiled component with
+    /// its source, and increases the size of compiled artifacts accordingly.
+    ///
+    /// This option is disabled by default.
+    #[cfg(all(
+        feature = "component-model",
+        any(feature = "cranelift", feature = "winch")
+    ))]
+    pub fn preserve_wasm_source(&mut self, enable: bool) -> &mut Self {
+        self.preserve_wasm_source = enable;
+        self
+    }
+
+    /// Configures a callback which is invoked after each successful
+    /// instantiation of a component.
+    ///
+    /// The callback receives the component which was instantiated and the
+    /// resulting instance. This applies to all instantiations within engines
+    /// created from this configuration, for example through
+    /// [`Linker::instantiate`](crate::component::Linker::instantiate) or
+    /// [`InstancePre::instantiate_async`](crate::component::InstancePre::instantiate_async),
+    /// which makes it possible to count or label instantiations centrally
+    /// without wrapping each call site. The callback is invoked before the
+    /// instance is returned to the caller, so it should be quick. Throwaway
+    /// instantiations performed by
+    /// [`Component::dry_run`](crate::component::Component::dry_run) and
+    /// [`Component::warmup`](crate::component::Component::warmup) are not
+    /// observed.
+    ///
+    /// By default no observer is configured.
+    #[cfg(all(feature = "runtime", feature = "component-model"))]
+    pub fn component_instantiate_observer(
+        &mut self,
+        observer: impl Fn(&crate::component::Component, &crate::component::Instance)
+        + Send
+        + Sync
+        + 'static,
+    ) -> &mut Self {
+        self.component_instantiate_observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Configures whether the type information of components is shared
+    /// between components with identical types.
+    ///
+    /// Each [`Component`](crate::component::Component) carries a table of all
+    /// of the types it defines. When this option is enabled the engine keeps
+    /// a registry of these tables and components whose type information is
+    /// identical, for example multiple copies of the same component or
+    /// components produced by the same toolchain for the same world, share a
+    /// single table instead. This reduces memory usage for hosts that load
+    /// many such components and additionally makes type checks between their
+    /// types cheaper, as shared types can be compared by identity.
+    ///
+    /// The cost of this is that type information must be compared against
+    /// the registry each time a component is compiled or deserialized.
+    ///
+    /// This option is `false` by default.
+    #[cfg(feature = "component-model")]
+    pub fn shared_component_types(&mut self, enable: bool) -> &mut Self {
+        self.shared_component_types = enable;
+        self
+    }
+
     /// Configures the "guaranteed dense image size" for copy-on-write
     /// initialized memories.
     ///
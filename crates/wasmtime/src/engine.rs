@@ -64,6 +64,17 @@ struct EngineInner {
     #[cfg(all(feature = "runtime", target_has_atomic = "64"))]
     epoch: AtomicU64,
 
+    /// Type information of components shared through
+    /// `Config::shared_component_types`, keyed by a SHA-256 digest of its
+    /// serialized form.
+    #[cfg(all(feature = "runtime", feature = "component-model"))]
+    component_types: crate::sync::RwLock<
+        crate::hash_map::HashMap<
+            [u8; 32],
+            alloc::sync::Weak<wasmtime_environ::component::ComponentTypes>,
+        >,
+    >,
+
     /// One-time check of whether the compiler's settings, if present, are
     /// compatible with the native host.
     compatible_with_native_host: crate::sync::OnceLock<Result<(), String>>,
@@ -158,6 +169,8 @@ impl Engine {
                 signatures: TypeRegistry::new(),
                 #[cfg(all(feature = "runtime", target_has_atomic = "64"))]
                 epoch: AtomicU64::new(0),
+                #[cfg(all(feature = "runtime", feature = "component-model"))]
+                component_types: Default::default(),
                 compatible_with_native_host: Default::default(),
                 config,
                 tunables,
@@ -806,6 +819,69 @@ impl Engine {
         &self.inner.signatures
     }
 
+    /// Returns `types` wrapped in an `Arc`, or if
+    /// `Config::shared_component_types` is enabled a previously returned
+    /// `Arc` of identical types.
+    #[cfg(all(feature = "runtime", feature = "component-model"))]
+    pub(crate) fn intern_component_types(
+        &self,
+        types: wasmtime_environ::component::ComponentTypes,
+    ) -> Result<Arc<wasmtime_environ::component::ComponentTypes>> {
+        use sha2::{Digest, Sha256};
+
+        /// A postcard flavor which feeds the serialized form of a value into
+        /// a hasher instead of buffering it.
+        struct Hashing(Sha256);
+
+        impl postcard::ser_flavors::Flavor for Hashing {
+            type Output = [u8; 32];
+
+            fn try_extend(&mut self, data: &[u8]) -> postcard::Result<()> {
+                self.0.update(data);
+                Ok(())
+            }
+
+            fn try_push(&mut self, data: u8) -> postcard::Result<()> {
+                self.0.update([data]);
+                Ok(())
+            }
+
+            fn finalize(self) -> postcard::Result<[u8; 32]> {
+                Ok(self.0.finalize().into())
+            }
+        }
+
+        if !self.config().shared_component_types {
+            return Ok(Arc::new(types));
+        }
+
+        // Types are compared by a digest of their serialized form, which after
+        // canonicalization for runtime usage refers to engine-wide type
+        // indices. These indices are kept alive by the registrations of each
+        // component using the types, so identical digests mean identical
+        // types. The digest is computed before taking any lock.
+        let key = postcard::serialize_with_flavor(&types, Hashing(Sha256::new()))?;
+        if let Some(shared) = self.inner.component_types.read().get(&key) {
+            if let Some(shared) = shared.upgrade() {
+                return Ok(shared);
+            }
+        }
+
+        let mut shared = self.inner.component_types.write();
+        if let Some(existing) = shared.get(&key).and_then(|types| types.upgrade()) {
+            return Ok(existing);
+        }
+        shared.retain(|_, types| types.strong_count() > 0);
+        let types = Arc::new(types);
+        shared.insert(key, Arc::downgrade(&types));
+        Ok(types)
+    }
+
+    #[cfg(all(test, feature = "component-model"))]
+    pub(crate) fn num_shared_component_types(&self) -> usize {
+        self.inner.component_types.read().len()
+    }
+
     #[cfg(feature = "runtime")]
     pub(crate) fn custom_code_memory(&self) -> Option<&Arc<dyn CustomCodeMemory>> {
         self.config().custom_code_memory.as_ref()
@@ -943,6 +1019,90 @@ impl Engine {
         Ok(try_new(code)?)
     }
 
+    /// Checks that the precompiled artifact `image` could be loaded into this
+    /// engine, reporting all incompatible settings at once.
+    #[cfg(feature = "component-model")]
+    pub(crate) fn check_artifact_compatible(
+        &self,
+        image: &[u8],
+        expected: ObjectKind,
+    ) -> Result<()> {
+        serialization::check_all_compatible(self, image, expected)
+    }
+
+    /// Checks whether the precompiled artifact `image` could be loaded into
+    /// this engine, distinguishing incompatible artifacts in the returned
+    /// `Ok` value from data which isn't an artifact at all.
+    #[cfg(feature = "component-model")]
+    pub(crate) fn artifact_compatibility(
+        &self,
+        image: &[u8],
+        expected: ObjectKind,
+    ) -> Result<Result<()>> {
+        if let Err(e) = self.check_compatible_with_native_host() {
+            return Ok(Err(e.context(
+                "compilation settings are not compatible with the native host",
+            )));
+        }
+        serialization::compatibility(self, image, expected)
+    }
+
+    /// Returns the value of the shared compiler setting `name` that the
+    /// precompiled artifact `image` was compiled with, if it was recorded.
+    #[cfg(feature = "component-model")]
+    pub(crate) fn artifact_shared_flag<'a>(
+        &self,
+        image: &'a [u8],
+        expected: ObjectKind,
+        name: &str,
+    ) -> Result<Option<wasmtime_environ::FlagValue<'a>>> {
+        serialization::shared_flag(self, image, expected, name)
+    }
+
+    /// Returns the tunables that the precompiled artifact `image` was
+    /// compiled with.
+    #[cfg(feature = "component-model")]
+    pub(crate) fn artifact_tunables(
+        &self,
+        image: &[u8],
+        expected: ObjectKind,
+    ) -> Result<wasmtime_environ::Tunables> {
+        serialization::tunables(self, image, expected)
+    }
+
+    /// Returns the version string, target triple, and whether Winch was used
+    /// for the precompiled artifact `image`.
+    #[cfg(feature = "component-model")]
+    pub(crate) fn artifact_build_info<'a>(
+        &self,
+        image: &'a [u8],
+        expected: ObjectKind,
+    ) -> Result<(&'a str, String, bool)> {
+        serialization::build_info(self, image, expected)
+    }
+
+    /// Returns the raw version string and encoded compilation settings
+    /// recorded in the precompiled artifact `image`.
+    #[cfg(feature = "component-model")]
+    pub(crate) fn artifact_engine_section<'a>(
+        &self,
+        image: &'a [u8],
+        expected: ObjectKind,
+    ) -> Result<(&'a [u8], &'a [u8])> {
+        serialization::engine_section(self, image, expected)
+    }
+
+    /// Returns the serialized metadata section of the precompiled artifact
+    /// `image` without loading its code.
+    #[cfg(feature = "component-model")]
+    pub(crate) fn artifact_wasmtime_info<'a>(
+        &self,
+        image: &'a [u8],
+        expected: ObjectKind,
+    ) -> Result<&'a [u8]> {
+        serialization::wasmtime_info(self, image, expected)
+    }
+
     /// Unload process-related trap/signal handlers and destroy this engine.
     ///
     /// This method is not safe and is not widely applicable. It is not required
@@ -48,6 +48,96 @@ const VERSION: u8 = 0;
 /// compiler options, etc. If a mismatch is found and the compilation metadata
 /// specified is incompatible then an error is returned.
 pub fn check_compatible(engine: &Engine, mmap: &[u8], expected: ObjectKind) -> Result<()> {
+    metadata(engine, mmap, expected)?.check_compatible(engine)
+}
+
+/// Same as [`check_compatible`], except that all incompatible settings are
+/// reported in the returned error instead of only the first.
+#[cfg(feature = "component-model")]
+pub fn check_all_compatible(engine: &Engine, mmap: &[u8], expected: ObjectKind) -> Result<()> {
+    metadata(engine, mmap, expected)?.check_all_compatible(engine)
+}
+
+/// Returns the value of the shared compiler setting `name` that the artifact in
+/// `mmap` was compiled with, if it was recorded.
+#[cfg(feature = "component-model")]
+pub fn shared_flag<'a>(
+    engine: &Engine,
+    mmap: &'a [u8],
+    expected: ObjectKind,
+    name: &str,
+) -> Result<Option<FlagValue<'a>>> {
+    Ok(metadata(engine, mmap, expected)?
+        .shared_flags
+        .into_iter()
+        .find(|(flag, _)| *flag == name)
+        .map(|(_, value)| value))
+}
+
+/// Returns the tunables that the artifact in `mmap` was compiled with.
+#[cfg(feature = "component-model")]
+pub fn tunables(engine: &Engine, mmap: &[u8], expected: ObjectKind) -> Result<Tunables> {
+    Ok(metadata(engine, mmap, expected)?.tunables)
+}
+
+/// Returns the version string, target triple, and whether Winch was used for
+/// the artifact in `mmap`, as recorded when it was compiled.
+#[cfg(feature = "component-model")]
+pub fn build_info<'a>(
+    engine: &Engine,
+    mmap: &'a [u8],
+    expected: ObjectKind,
+) -> Result<(&'a str, String, bool)> {
+    let (version, metadata) = versioned_metadata(engine, mmap, expected)?;
+    let version = core::str::from_utf8(version)?;
+    let target = String::from(&*metadata.target);
+    Ok((version, target, metadata.tunables.winch_callable))
+}
+
+fn metadata<'a>(engine: &Engine, mmap: &'a [u8], expected: ObjectKind) -> Result<Metadata<'a>> {
+    Ok(versioned_metadata(engine, mmap, expected)?.1)
+}
+
+fn versioned_metadata<'a>(
+    engine: &Engine,
+    mmap: &'a [u8],
+    expected: ObjectKind,
+) -> Result<(&'a [u8], Metadata<'a>)> {
+    let (version, data) = engine_section(engine, mmap, expected)?;
+    Ok((version, postcard::from_bytes::<Metadata<'_>>(data)?))
+}
+
+/// Returns the raw version string and encoded compilation settings recorded in
+/// the artifact in `mmap`.
+pub fn engine_section<'a>(
+    engine: &Engine,
+    mmap: &'a [u8],
+    expected: ObjectKind,
+) -> Result<(&'a [u8], &'a [u8])> {
+    let (version, data) = raw_engine_section(mmap, expected)?;
+    check_version(engine, version)?;
+    Ok((version, data))
+}
+
+/// Checks whether the artifact in `mmap` could be loaded into `engine`.
+///
+/// An error is returned if `mmap` isn't a Wasmtime artifact of the `expected`
+/// kind at all. Otherwise the returned result describes whether the artifact
+/// was produced by this version of Wasmtime with compatible settings, where
+/// all incompatible settings are reported at once.
+#[cfg(feature = "component-model")]
+pub fn compatibility(engine: &Engine, mmap: &[u8], expected: ObjectKind) -> Result<Result<()>> {
+    let (version, data) = raw_engine_section(mmap, expected)?;
+    if let Err(e) = check_version(engine, version) {
+        return Ok(Err(e));
+    }
+    let metadata = postcard::from_bytes::<Metadata<'_>>(data)?;
+    Ok(metadata.check_all_compatible(engine))
+}
+
+/// Same as [`engine_section`], but without checking that the artifact was
+/// produced by a compatible version of Wasmtime.
+fn raw_engine_section<'a>(mmap: &'a [u8], expected: ObjectKind) -> Result<(&'a [u8], &'a [u8])> {
     // Parse the input `mmap` as an ELF file and see if the header matches the
     // Wasmtime-generated header. This includes a Wasmtime-specific `os_abi` and
     // the `e_flags` field should indicate whether `expected` matches or not.
@@ -115,7 +205,10 @@ pub fn check_compatible(engine: &Engine, mmap: &[u8], expected: ObjectKind) -> R
     } else {
         data.split_at(len)
     };
+    Ok((version, data))
+}
 
+fn check_version(engine: &Engine, version: &[u8]) -> Result<()> {
     match &engine.config().module_version {
         ModuleVersionStrategy::None => { /* ignore the version info, accept all */ }
         _ => {
@@ -125,7 +218,26 @@ pub fn check_compatible(engine: &Engine, mmap: &[u8], expected: ObjectKind) -> R
             }
         }
     }
-    postcard::from_bytes::<Metadata<'_>>(data)?.check_compatible(engine)
+    Ok(())
+}
+
+/// Returns the contents of the `ELF_WASMTIME_INFO` section of the artifact in
+/// `mmap`, after checking that it's of the `expected` kind and was produced by
+/// this version of Wasmtime.
+#[cfg(feature = "component-model")]
+pub fn wasmtime_info<'a>(
+    engine: &Engine,
+    mmap: &'a [u8],
+    expected: ObjectKind,
+) -> Result<&'a [u8]> {
+    use object::ObjectSection as _;
+
+    engine_section(engine, mmap, expected)?;
+    let obj = ElfFile64::<Endianness>::parse(mmap).map_err(obj::ObjectCrateErrorWrapper)?;
+    let Some(section) = obj.section_by_name(obj::ELF_WASMTIME_INFO) else {
+        bail!("failed to find section `{}`", obj::ELF_WASMTIME_INFO)
+    };
+    Ok(section.data().map_err(obj::ObjectCrateErrorWrapper)?)
 }
 
 #[cfg(any(feature = "cranelift", feature = "winch"))]
@@ -211,6 +323,29 @@ impl Metadata<'_> {
         Ok(())
     }
 
+    #[cfg(feature = "component-model")]
+    fn check_all_compatible(mut self, engine: &Engine) -> Result<()> {
+        let results = [
+            self.check_triple(engine),
+            self.check_shared_flags(engine),
+            self.check_isa_flags(engine),
+            self.check_tunables(&engine.tunables()),
+            self.check_features(&engine.features()),
+        ];
+        let mut errors = results.into_iter().filter_map(|r| r.err()).peekable();
+        let Some(first) = errors.next() else {
+            return Ok(());
+        };
+        if errors.peek().is_none() {
+            return Err(first);
+        }
+        let mut message = String::from("multiple incompatible settings:");
+        for error in core::iter::once(first).chain(errors) {
+            message.push_str(&format!("\n  - {error:#}"));
+        }
+        bail!("{message}")
+    }
+
     fn check_triple(&self, engine: &Engine) -> Result<()> {
         let engine_target = engine.target();
         let module_target =
@@ -309,6 +444,10 @@ impl Metadata<'_> {
             // whether it's present or not)
             generate_address_map: _,
 
+            // Similar to the address map, retained custom sections are only
+            // extra data in the compiled file which is read on demand.
+            retain_custom_sections: _,
+
             // Just a debugging aid, doesn't affect functionality at all.
             debug_adapter_modules: _,
         } = self.tunables;
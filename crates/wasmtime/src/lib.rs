@@ -365,6 +365,10 @@
 //!   with the same overhead as the `call-hook` feature where entries/exits into
 //!   WebAssembly will have more overhead than before.
 //!
+//! * `zstd` - Disabled by default, this enables support for zstd-compressed
+//!   component artifacts with [`component::Component::serialize_compressed`]
+//!   and [`component::Component::deserialize_compressed`].
+//!
 //! * `signals-based-traps` - Enabled by default, this enables support for using
 //!   host signal handlers to implement WebAssembly traps. For example virtual
 //!   memory is used to catch out-of-bounds accesses in WebAssembly that result
@@ -227,6 +227,20 @@ impl EngineCode {
         self.original_code.wasm_dwarf()
     }
 
+    /// Returns the contents of the `ELF_WASMTIME_CUSTOM` section.
+    #[cfg(feature = "component-model")]
+    #[inline]
+    pub fn wasm_custom_sections(&self) -> &[u8] {
+        self.original_code.wasm_custom_sections()
+    }
+
+    /// Returns the serialized metadata section of the raw image.
+    #[cfg(feature = "component-model")]
+    #[inline]
+    pub fn wasmtime_info(&self) -> &[u8] {
+        self.original_code.wasmtime_info()
+    }
+
     /// Returns the raw image as bytes (in our internal image format).
     pub fn image(&self) -> &[u8] {
         &self.original_code.mmap()[..]
@@ -236,6 +250,18 @@ impl EngineCode {
         &self.original_code.text()
     }
 
+    /// Returns whether the raw image is known to be immutable from here on.
+    #[cfg(feature = "component-model")]
+    pub fn is_frozen(&self) -> bool {
+        self.original_code.is_frozen()
+    }
+
+    /// Returns why pages of the raw image may be writable, if they may be.
+    #[cfg(feature = "component-model")]
+    pub fn writable_reason(&self) -> Option<&'static str> {
+        self.original_code.writable_reason()
+    }
+
     /// Returns the concatenated list of all data associated with this wasm
     /// module.
     ///
@@ -45,6 +45,7 @@ pub struct CodeMemory {
     func_name_data: Range<usize>,
     info_data: Range<usize>,
     wasm_dwarf: Range<usize>,
+    wasm_custom_sections: Range<usize>,
 }
 
 impl Drop for CodeMemory {
@@ -153,6 +154,7 @@ impl CodeMemory {
         let mut func_name_data = 0..0;
         let mut info_data = 0..0;
         let mut wasm_dwarf = 0..0;
+        let mut wasm_custom_sections = 0..0;
         for section_header in sections.iter() {
             let data = section_header
                 .data(endian, mmap_data)
@@ -212,6 +214,7 @@ impl CodeMemory {
                 obj::ELF_NAME_DATA => func_name_data = range,
                 obj::ELF_WASMTIME_INFO => info_data = range,
                 obj::ELF_WASMTIME_DWARF => wasm_dwarf = range,
+                obj::ELF_WASMTIME_CUSTOM => wasm_custom_sections = range,
 
                 #[cfg(feature = "debug-builtins")]
                 ".debug_info" => has_native_debug_info = true,
@@ -267,6 +270,7 @@ impl CodeMemory {
             frame_tables_data,
             func_name_data,
             wasm_dwarf,
+            wasm_custom_sections,
             info_data,
             wasm_data,
         })
@@ -278,6 +282,37 @@ impl CodeMemory {
         &self.mmap
     }
 
+    /// Returns whether this image has been published and can no longer be
+    /// modified.
+    ///
+    /// This is the case if the image is mapped read-only with virtual memory
+    /// or if it's externally-owned memory which the embedder guarantees is
+    /// never modified.
+    pub fn is_frozen(&self) -> bool {
+        self.published && (self.mmap.supports_virtual_memory() || self.mmap.is_always_readonly())
+    }
+
+    /// Returns why pages of this image may be writable, or `None` if none
+    /// of them are.
+    ///
+    /// Publishing makes the whole image read-only, and then its text section
+    /// read/execute if it needs to be executable, but only when that's
+    /// possible with virtual memory. Externally-owned memory is instead
+    /// guaranteed by the embedder to never be modified.
+    pub fn writable_reason(&self) -> Option<&'static str> {
+        if !self.published {
+            Some("the image has not been published")
+        } else if self.mmap.is_always_readonly() {
+            None
+        } else if !self.mmap.supports_virtual_memory() {
+            Some("virtual memory is not available to write-protect the image")
+        } else if self.text.is_empty() {
+            Some("the image contains no code so it was never write-protected")
+        } else {
+            None
+        }
+    }
+
     /// Returns the contents of the text section of the ELF executable this
     /// represents.
     #[inline]
@@ -291,6 +326,12 @@ impl CodeMemory {
         &self.mmap[self.wasm_dwarf.clone()]
     }
 
+    /// Returns the contents of the `ELF_WASMTIME_CUSTOM` section.
+    #[inline]
+    pub fn wasm_custom_sections(&self) -> &[u8] {
+        &self.mmap[self.wasm_custom_sections.clone()]
+    }
+
     /// Returns the data in the `ELF_NAME_DATA` section.
     #[inline]
     pub fn func_name_data(&self) -> &[u8] {
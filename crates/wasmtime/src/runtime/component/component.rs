@@ -1,27 +1,36 @@
-use crate::component::InstanceExportLookup;
+use crate::component::instance::Instantiation;
 use crate::component::matching::InstanceType;
 use crate::component::types;
+use crate::component::{InstanceExportLookup, Linker};
+use crate::hash_map::HashMap;
 use crate::prelude::*;
 #[cfg(feature = "std")]
 use crate::runtime::vm::open_file_for_mmap;
 use crate::runtime::vm::{CompiledModuleId, VMArrayCallFunction, VMFuncRef, VMWasmCallFunction};
 use crate::{
-    Engine, Module, ResourcesRequired, code::EngineCode, code_memory::CodeMemory,
+    Engine, Module, ResourcesRequired, Store, Strategy, code::EngineCode, code_memory::CodeMemory,
     type_registry::TypeCollection,
 };
-use crate::{FuncType, ValType};
+use crate::{FrameInfo, FuncType, MemoryType, Trap, ValType, WasmFeatures};
 use alloc::sync::Arc;
 use core::ops::Range;
+use core::pin::Pin;
 use core::ptr::NonNull;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use serde_derive::{Deserialize, Serialize};
 #[cfg(feature = "std")]
 use std::path::Path;
 use wasmtime_environ::component::{
-    CompiledComponentInfo, ComponentArtifacts, ComponentTypes, CoreDef, Export, ExportIndex,
-    GlobalInitializer, InstantiateModule, NameMapNoIntern, OptionsIndex, StaticModuleIndex,
-    TrampolineIndex, TypeComponentIndex, TypeFuncIndex, UnsafeIntrinsic, VMComponentOffsets,
+    CanonicalOptionsDataModel, CompiledComponentInfo, ComponentArtifacts, ComponentTypes, CoreDef,
+    Export, ExportIndex, ExportItem, ExtractPostReturn, GlobalInitializer, InstantiateModule,
+    NameMap, NameMapNoIntern, OptionsIndex, ResourceIndex, StaticModuleIndex, TrampolineIndex,
+    TypeComponentIndex, TypeDef, TypeFuncIndex, UnsafeIntrinsic, VMComponentOffsets,
 };
 use wasmtime_environ::{Abi, CompiledFunctionsTable, FuncKey, TypeTrace, WasmChecksum};
-use wasmtime_environ::{FunctionLoc, HostPtr, ObjectKind, PrimaryMap};
+use wasmtime_environ::{
+    DefinedMemoryIndex, DefinedTableIndex, EntityRef, EntityType, FuncIndex, FunctionLoc, HostPtr,
+    IndexType, ObjectKind, PrimaryMap,
+};
 
 /// A compiled WebAssembly Component.
 ///
@@ -97,8 +106,41 @@ struct ComponentInner {
 
     /// The checksum of the source binary from which the module was compiled.
     checksum: WasmChecksum,
+
+    /// The source binary from which the component was compiled, if
+    /// `Config::preserve_wasm_source` was enabled.
+    original_wasm: Option<Box<[u8]>>,
+
+    /// The number of instances of this component which are currently alive,
+    /// across all stores.
+    live_instances: AtomicUsize,
+
+    /// The fallback for function names missing from the name section,
+    /// configured with [`Component::set_name_resolver`].
+    name_resolver: crate::sync::RwLock<Option<Arc<NameResolver>>>,
+
+    /// The hash returned by [`Component::compilation_id`], computed on first
+    /// use.
+    compilation_id: crate::sync::OnceLock<[u8; 32]>,
+
+    /// An empty map of resource substitutions shared by all types created
+    /// for this component before it's instantiated.
+    uninstantiated_resources: Arc<PrimaryMap<ResourceIndex, crate::component::ResourceType>>,
+}
+
+/// The header of artifacts produced by [`Component::serialize_compressed`],
+/// followed by a zstd frame of what [`Component::serialize`] produces.
+const COMPRESSED_MAGIC: &[u8] = b"\0wasmtime-zstd\0";
+
+fn ensure_uncompressed(bytes: &[u8]) -> Result<()> {
+    if bytes.starts_with(COMPRESSED_MAGIC) {
+        bail!("artifact is compressed, use `Component::deserialize_compressed` to load it");
+    }
+    Ok(())
 }
 
+pub(crate) type NameResolver = dyn Fn(StaticModuleIndex, FuncIndex) -> Option<String> + Send + Sync;
+
 pub(crate) struct AllCallFuncPointers {
     pub wasm_call: NonNull<VMWasmCallFunction>,
     pub array_call: NonNull<VMArrayCallFunction>,
@@ -170,6 +212,35 @@ impl Component {
             .compile_component()
     }
 
+    /// Same as [`Component::new`], except that compilation happens on a
+    /// separate thread and the returned future resolves once it's finished.
+    ///
+    /// This is intended for async servers which receive components at runtime
+    /// and don't want to block their executor's worker threads for the
+    /// duration of a compilation. The input is copied and compiled with the
+    /// settings of `engine`, so both the binary and text formats are accepted
+    /// and validation errors are the same as those of [`Component::new`].
+    ///
+    /// This doesn't depend on any particular async runtime: a new thread is
+    /// spawned for each call and the future is woken when it completes. If the
+    /// future is dropped before then the compilation still runs to completion
+    /// but its result is discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a thread can't be spawned or if compilation fails
+    /// for any of the reasons listed in [`Component::new`].
+    #[cfg(all(
+        feature = "async",
+        feature = "std",
+        any(feature = "cranelift", feature = "winch")
+    ))]
+    pub async fn new_async(engine: &Engine, bytes: impl AsRef<[u8]>) -> Result<Component> {
+        let engine = engine.clone();
+        let bytes = bytes.as_ref().to_vec();
+        BackgroundCompile::spawn(move || Component::new(&engine, &bytes))?.await
+    }
+
     /// Compiles a new WebAssembly component from a wasm file on disk pointed
     /// to by `file`.
     ///
@@ -198,6 +269,278 @@ impl Component {
             .compile_component()
     }
 
+    /// Validates `binary` as a WebAssembly component given the configuration
+    /// in `engine`, without compiling it.
+    ///
+    /// This performs the same validation of the component and of all the core
+    /// wasm modules within it as [`Component::new`] does, and reports errors
+    /// the same way, but stops before any code is generated. This makes it
+    /// much cheaper than [`Component::new`] for checking many components, for
+    /// example in a lint step which discards them afterwards.
+    ///
+    /// Like [`Component::from_binary`] the text format is not accepted.
+    /// Checks which [`Component::new`] performs after compilation, such as
+    /// validating against the limits of a [pooling
+    /// allocator](crate::PoolingAllocationConfig), aren't performed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first validation issue found if
+    /// `binary` isn't a valid component, for example a type check error or
+    /// the usage of a feature which isn't enabled in `engine`.
+    #[cfg(any(feature = "cranelift", feature = "winch"))]
+    pub fn validate(engine: &Engine, binary: &[u8]) -> Result<()> {
+        crate::compile::validate_component(engine, binary)
+    }
+
+    /// Compiles a new WebAssembly component by reading its contents from
+    /// `reader`.
+    ///
+    /// This is a convenience function for embedders which receive components
+    /// from a stream, such as a socket or a decompressor, and would otherwise
+    /// have to buffer the entire input themselves before calling
+    /// [`Component::from_binary`]. The input is read in chunks until EOF and
+    /// then handed to the compiler. The internal buffer grows by at most a
+    /// fixed amount at a time so large inputs don't temporarily reserve much
+    /// more memory than they need.
+    ///
+    /// Like [`Component::from_binary`] the text format is not accepted, since
+    /// it can't be reliably detected partway through a stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from `reader` fails, with the underlying
+    /// [`std::io::Error`] as its source, or if compilation fails for any of
+    /// the reasons listed in [`Component::new`].
+    #[cfg(all(feature = "std", any(feature = "cranelift", feature = "winch")))]
+    pub fn from_reader(engine: &Engine, mut reader: impl std::io::Read) -> Result<Component> {
+        const CHUNK: usize = 64 << 10;
+        const MAX_GROWTH: usize = 16 << 20;
+
+        let mut bytes = Vec::new();
+        let mut chunk = vec![0; CHUNK];
+        loop {
+            let n = match reader.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e).context("failed to read component from reader"),
+            };
+            if bytes.capacity() - bytes.len() < n {
+                bytes.reserve_exact(bytes.len().clamp(n, MAX_GROWTH));
+            }
+            bytes.extend_from_slice(&chunk[..n]);
+        }
+        Component::from_binary(engine, &bytes)
+    }
+
+    /// Creates a component which embeds the core wasm module `binary`,
+    /// instantiating it and exporting each of its functions as a component
+    /// function.
+    ///
+    /// This avoids writing out a `(component ...)` wrapper by hand for inputs
+    /// which are plain core modules. Each exported function is lifted using
+    /// the component model's flat ABI, where `i32` and `i64` become `u32` and
+    /// `u64`, `f32` and `f64` are unchanged, and parameters are named `p0`,
+    /// `p1`, and so on. Memories, tables, globals, and other non-function
+    /// exports have no equivalent in the component model and aren't exported.
+    ///
+    /// Compiled [`Module`]s don't retain their original WebAssembly binary, so
+    /// the module is provided here in the binary format instead. It's embedded
+    /// within the returned component, which is self-contained and may be
+    /// serialized and instantiated like any other:
+    ///
+    /// ```
+    /// # use wasmtime::*;
+    /// # use wasmtime::component::{Component, Linker};
+    /// # fn main() -> Result<()> {
+    /// let engine = Engine::default();
+    /// let module = wat::parse_str(r#"(module (func (export "f") (result i32) i32.const 1))"#)?;
+    /// let component = Component::from_module(&engine, &module)?;
+    ///
+    /// let linker = Linker::<()>::new(&engine);
+    /// let mut store = Store::new(&engine, ());
+    /// let instance = linker.instantiate(&mut store, &component)?;
+    /// let f = instance.get_typed_func::<(), (u32,)>(&mut store, "f")?;
+    /// assert_eq!(f.call(&mut store, ())?, (1,));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `binary` isn't a valid core wasm module, or if it
+    /// has imports, since nothing within the component could satisfy them. An
+    /// error listing each offending export is returned if any exported
+    /// function can't be lifted, either because its name isn't a valid
+    /// component export name or because its type can't be expressed with the
+    /// flat ABI, for example if it uses reference types or returns multiple
+    /// values.
+    #[cfg(any(feature = "cranelift", feature = "winch"))]
+    pub fn from_module(engine: &Engine, binary: &[u8]) -> Result<Component> {
+        use wasm_encoder::{ComponentBuilder, ComponentExportKind, ExportKind, PrimitiveValType};
+        use wasmparser::{Encoding, Parser, Payload, ValidPayload, Validator, types::EntityType};
+        use wasmtime_environ::component::{MAX_FLAT_PARAMS, MAX_FLAT_RESULTS};
+
+        fn flat_type(ty: &wasmparser::ValType) -> Option<PrimitiveValType> {
+            match ty {
+                wasmparser::ValType::I32 => Some(PrimitiveValType::U32),
+                wasmparser::ValType::I64 => Some(PrimitiveValType::U64),
+                wasmparser::ValType::F32 => Some(PrimitiveValType::F32),
+                wasmparser::ValType::F64 => Some(PrimitiveValType::F64),
+                wasmparser::ValType::V128 | wasmparser::ValType::Ref(_) => None,
+            }
+        }
+
+        // Validate the structure of the module, but not function bodies which
+        // are validated when the component itself is compiled below.
+        let mut validator = Validator::new_with_features(engine.features());
+        let mut types = None;
+        for payload in Parser::new(0).parse_all(binary) {
+            let payload = payload?;
+            if let Payload::Version {
+                encoding: Encoding::Component,
+                ..
+            } = &payload
+            {
+                bail!("expected a core wasm module, found a component");
+            }
+            if let ValidPayload::End(t) = validator.payload(&payload)? {
+                types = Some(t);
+            }
+        }
+        let types = types.context("core wasm module is truncated")?;
+        let types = types.as_ref();
+        if types.core_imports().into_iter().flatten().next().is_some() {
+            bail!("cannot wrap a module with imports in a component");
+        }
+
+        let mut builder = ComponentBuilder::default();
+        let module = builder.core_module_raw(None, binary);
+        let instance = builder.core_instantiate(None, module, []);
+        let mut unsupported = Vec::new();
+        for (name, ty) in types.core_exports().into_iter().flatten() {
+            let (EntityType::Func(id) | EntityType::FuncExact(id)) = ty else {
+                continue;
+            };
+            if wasmparser::names::KebabStr::new(name).is_none() {
+                unsupported.push(format!("`{name}`: not a valid component export name"));
+                continue;
+            }
+            let ty = types[id].unwrap_func();
+            let params = ty
+                .params()
+                .iter()
+                .map(flat_type)
+                .collect::<Option<Vec<_>>>();
+            let results = ty
+                .results()
+                .iter()
+                .map(flat_type)
+                .collect::<Option<Vec<_>>>();
+            let (Some(params), Some(results)) = (params, results) else {
+                unsupported.push(format!(
+                    "`{name}`: uses types without a flat ABI equivalent"
+                ));
+                continue;
+            };
+            if params.len() > MAX_FLAT_PARAMS || results.len() > MAX_FLAT_RESULTS {
+                unsupported.push(format!("`{name}`: too many parameters or results"));
+                continue;
+            }
+
+            let param_names = (0..params.len())
+                .map(|i| format!("p{i}"))
+                .collect::<Vec<_>>();
+            let (func_ty, mut encoder) = builder.type_function(None);
+            encoder
+                .params(param_names.iter().map(|n| n.as_str()).zip(params))
+                .result(results.first().map(|ty| (*ty).into()));
+            let core_func = builder.core_alias_export(None, instance, name, ExportKind::Func);
+            let func = builder.lift_func(None, core_func, func_ty, []);
+            builder.export(name, ComponentExportKind::Func, func, None);
+        }
+        if !unsupported.is_empty() {
+            bail!(
+                "cannot represent module exports in a component:\n  {}",
+                unsupported.join("\n  ")
+            );
+        }
+
+        Component::from_binary(engine, &builder.finish())
+    }
+
+    /// Returns a rough estimate, in bytes, of the peak memory needed to
+    /// compile the component in `bytes` with `engine`.
+    ///
+    /// This does not compile anything. Instead `bytes` is run through the
+    /// structural half of validation, the same pass performed by
+    /// [`Component::new`] before function bodies are validated, and the
+    /// estimate is derived from the number of functions and the size of their
+    /// bodies along with how many functions may be compiled in parallel.
+    ///
+    /// The returned value is intended to help schedule compilations, for
+    /// example to avoid running too many at once on a build machine. It is a
+    /// heuristic and not a guarantee: actual memory usage depends on the
+    /// compiler, its settings, and the shape of the code being compiled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a structurally valid component for
+    /// `engine`. Only the binary format is accepted.
+    pub fn estimate_compile_memory(engine: &Engine, bytes: &[u8]) -> Result<u64> {
+        // Fixed cost of translation metadata kept for each function.
+        const PER_FUNCTION: u64 = 512;
+        // Emitted machine code and its relocations relative to wasm bytes.
+        const CODE_EXPANSION: u64 = 8;
+        // Compiler IR and register allocation state for a function relative
+        // to the size of its body, alive only while it's being compiled.
+        const IR_EXPANSION: u64 = 128;
+
+        let mut validator = wasmparser::Validator::new_with_features(engine.features());
+        let mut functions = 0u64;
+        let mut code_size = 0u64;
+        let mut largest = Vec::new();
+        for (i, payload) in wasmparser::Parser::new(0).parse_all(bytes).enumerate() {
+            let payload = payload?;
+            if let wasmparser::Payload::Version {
+                encoding: wasmparser::Encoding::Module,
+                ..
+            } = &payload
+            {
+                if i == 0 {
+                    bail!("core module passed to component compile memory estimate");
+                }
+            }
+            if let wasmparser::ValidPayload::Func(_, body) = validator.payload(&payload)? {
+                let size = u64::try_from(body.range().len()).unwrap();
+                functions += 1;
+                code_size += size;
+                largest.push(size);
+            }
+        }
+
+        let parallelism = if engine.config().parallel_compilation {
+            #[cfg(feature = "std")]
+            {
+                std::thread::available_parallelism().map_or(1, |n| n.get())
+            }
+            #[cfg(not(feature = "std"))]
+            {
+                1
+            }
+        } else {
+            1
+        };
+        largest.sort_unstable_by(|a, b| b.cmp(a));
+        let in_flight: u64 = largest.iter().take(parallelism).sum();
+
+        Ok(u64::try_from(bytes.len()).unwrap()
+            + functions * PER_FUNCTION
+            + code_size * CODE_EXPANSION
+            + in_flight * IR_EXPANSION)
+    }
+
     /// Same as [`Module::deserialize`], but for components.
     ///
     /// Note that the bytes referenced here must contain contents previously
@@ -213,10 +556,69 @@ impl Component {
     ///
     /// [`Module::deserialize`]: crate::Module::deserialize
     pub unsafe fn deserialize(engine: &Engine, bytes: impl AsRef<[u8]>) -> Result<Component> {
+        ensure_uncompressed(bytes.as_ref())?;
         let code = engine.load_code_bytes(bytes.as_ref(), ObjectKind::Component)?;
         Component::from_parts(engine, code, None)
     }
 
+    /// Same as [`Component::deserialize`], but for artifacts produced by
+    /// [`Component::serialize_compressed`].
+    ///
+    /// The artifact is decompressed into memory and then loaded exactly as
+    /// [`Component::deserialize`] would, including checking it for
+    /// compatibility with `engine` before any of its code is mapped as
+    /// executable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` isn't a compressed artifact, for example if
+    /// it was produced by [`Component::serialize`] instead, if it fails to
+    /// decompress, or if the decompressed artifact can't be deserialized.
+    ///
+    /// # Unsafety
+    ///
+    /// All of the safety notes from [`Component::deserialize`] apply here as
+    /// well, to the decompressed artifact.
+    #[cfg(feature = "zstd")]
+    pub unsafe fn deserialize_compressed(
+        engine: &Engine,
+        bytes: impl AsRef<[u8]>,
+    ) -> Result<Component> {
+        let Some(frame) = bytes.as_ref().strip_prefix(COMPRESSED_MAGIC) else {
+            bail!("artifact is not compressed, use `Component::deserialize` to load it");
+        };
+        let bytes = zstd::stream::decode_all(frame).context("failed to decompress component")?;
+        // SAFETY: the contract of `deserialize` is the same as this function.
+        unsafe { Component::deserialize(engine, bytes) }
+    }
+
+    /// Same as [`Component::deserialize`], except that the component isn't
+    /// validated against `engine`'s instance allocator.
+    ///
+    /// Deserialization normally checks that the component fits within the
+    /// limits of the allocator, such as those configured for the pooling
+    /// allocator, so that an error is returned up front rather than during
+    /// instantiation. This method skips that check, which can be useful on
+    /// hot restart paths where an artifact is loaded into an engine with the
+    /// same configuration as the one which just produced it. The artifact is
+    /// otherwise decoded and checked for compatibility as usual.
+    ///
+    /// # Unsafety
+    ///
+    /// All of the safety notes from [`Component::deserialize`] apply here as
+    /// well. Additionally the component must be one which
+    /// [`Component::validate_for_allocator`] would accept for `engine`:
+    /// instantiating a component which exceeds the limits of the allocator,
+    /// for example the pooling allocator's limits, is undefined behavior.
+    pub unsafe fn deserialize_unchecked(
+        engine: &Engine,
+        bytes: impl AsRef<[u8]>,
+    ) -> Result<Component> {
+        ensure_uncompressed(bytes.as_ref())?;
+        let code = engine.load_code_bytes(bytes.as_ref(), ObjectKind::Component)?;
+        Component::from_parts_with(engine, code, None, false)
+    }
+
     /// Same as [`Module::deserialize_raw`], but for components.
     ///
     /// See [`Component::deserialize`] for additional information; this method
@@ -236,6 +638,34 @@ impl Component {
         Component::from_parts(engine, code, None)
     }
 
+    /// Creates a component from the two halves of its artifact as produced by
+    /// [`Component::serialize_split`].
+    ///
+    /// # Unsafety
+    ///
+    /// All of the safety notes from [`Component::deserialize`] apply here as
+    /// well, to both `code` and `metadata`. Additionally `metadata` must have
+    /// been produced alongside `code` by the same call to
+    /// [`Component::serialize_split`], or by a call on a component with
+    /// identical machine code.
+    pub unsafe fn from_parts_split(
+        engine: &Engine,
+        code: impl AsRef<[u8]>,
+        metadata: impl AsRef<[u8]>,
+    ) -> Result<Component> {
+        let code = engine.load_code_bytes(code.as_ref(), ObjectKind::Component)?;
+        let artifacts = postcard::from_bytes(metadata.as_ref())
+            .context("failed to decode component metadata")?;
+        let component = Component::from_parts(engine, code, Some(artifacts))?;
+        // The metadata within `code` was zeroed out, so record the hash of
+        // the real metadata now.
+        component
+            .inner
+            .compilation_id
+            .get_or_init(|| component.compute_compilation_id(metadata.as_ref()));
+        Ok(component)
+    }
+
     /// Same as [`Module::deserialize_file`], but for components.
     ///
     /// Note that the file referenced here must contain contents previously
@@ -259,11 +689,131 @@ impl Component {
         Component::from_parts(engine, code, None)
     }
 
+    /// Same as [`Component::deserialize_file`], except that `options`
+    /// configures how the file is loaded.
+    ///
+    /// See [`DeserializeFileOptions`] for the available options. The returned
+    /// component behaves the same regardless of how its file was loaded.
+    ///
+    /// # Unsafety
+    ///
+    /// All of the safety notes from [`Component::deserialize_file`] apply
+    /// here as well, except that when [`DeserializeFileOptions::mmap`] is
+    /// disabled the file may be modified after this method returns.
+    #[cfg(feature = "std")]
+    pub unsafe fn deserialize_file_with(
+        engine: &Engine,
+        path: impl AsRef<Path>,
+        options: &DeserializeFileOptions,
+    ) -> Result<Component> {
+        let path = path.as_ref();
+        if options.mmap {
+            // SAFETY: the contract of `deserialize_file` is the same as this
+            // function's.
+            return unsafe { Component::deserialize_file(engine, path) };
+        }
+        let bytes =
+            std::fs::read(path).with_context(|| format!("failed to read: {}", path.display()))?;
+        let code = engine
+            .load_code_bytes(&bytes, ObjectKind::Component)
+            .with_context(|| format!("failed to load code for: {}", path.display()))?;
+        Component::from_parts(engine, code, None)
+    }
+
+    /// Returns the type of the component serialized in `bytes` without
+    /// loading it.
+    ///
+    /// The `bytes` must have been produced by [`Component::serialize`] or
+    /// [`Engine::precompile_component`]. Only the metadata of the artifact is
+    /// decoded, so unlike [`Component::deserialize`] none of its code is
+    /// mapped into memory, which makes this a cheap way to inspect the imports
+    /// and exports of many artifacts.
+    ///
+    /// This method is safe because the artifact's code is never executed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` isn't a component artifact produced by this
+    /// version of Wasmtime, including if it's a precompiled core wasm module
+    /// instead. An error is also returned if the core wasm types within the
+    /// component refer to one another, as with the GC proposal, since those
+    /// types can only be represented once the component is loaded with
+    /// [`Component::deserialize`].
+    pub fn peek_type(engine: &Engine, bytes: &[u8]) -> Result<types::Component> {
+        if let Some(crate::Precompiled::Module) = Engine::detect_precompiled(bytes) {
+            bail!(
+                "expected a precompiled component but found a core wasm module, \
+                 use `Module::deserialize` to load it"
+            );
+        }
+        ensure_uncompressed(bytes)?;
+        let info = engine.artifact_wasmtime_info(bytes, ObjectKind::Component)?;
+        let ComponentArtifacts { ty, types, .. } =
+            postcard::from_bytes(info).context("failed to decode component metadata")?;
+        if types
+            .module_types()
+            .wasm_types()
+            .any(|(_, ty)| !ty.is_canonicalized_for_runtime_usage())
+        {
+            bail!("component contains core wasm types which can't be inspected without loading it");
+        }
+        let types = Arc::new(types);
+        Ok(types::Component::from(
+            ty,
+            &InstanceType {
+                // There's no component loaded here, so resources are given a
+                // fresh identity.
+                component: CompiledModuleId::new(),
+                types: &types,
+                resources: &Arc::new(PrimaryMap::new()),
+            },
+        ))
+    }
+
+    /// Checks whether the component serialized in `bytes` could be loaded into
+    /// `engine` with [`Component::deserialize`].
+    ///
+    /// The `bytes` must have been produced by [`Component::serialize`] or
+    /// [`Engine::precompile_component`]. Only the header metadata of the
+    /// artifact is inspected, such as the Wasmtime version, target, compiler
+    /// flags, and enabled WebAssembly features, so none of its code is mapped
+    /// into memory. This can be used to detect a stale cache of artifacts, for
+    /// example one written by an older version of Wasmtime, and recompile them
+    /// instead of failing to deserialize them.
+    ///
+    /// This method is safe because the artifact's code is never executed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` isn't an uncompressed component artifact
+    /// produced by Wasmtime at all, including if it's a precompiled core wasm
+    /// module instead. Artifacts which are merely incompatible with `engine`
+    /// are reported with [`Compatibility::Incompatible`].
+    pub fn artifact_compatible(engine: &Engine, bytes: &[u8]) -> Result<Compatibility> {
+        ensure_uncompressed(bytes)?;
+        Ok(
+            match engine.artifact_compatibility(bytes, ObjectKind::Component)? {
+                Ok(()) => Compatibility::Compatible,
+                Err(e) => Compatibility::Incompatible {
+                    reason: format!("{e:#}"),
+                },
+            },
+        )
+    }
+
     /// Returns the type of this component as a [`types::Component`].
     ///
     /// This method enables runtime introspection of the type of a component
     /// before instantiation, if necessary.
     ///
+    /// This is a cheap operation which does not build the type tree of the
+    /// component up front. The returned [`types::Component`] shares this
+    /// component's type information and each import or export type is only
+    /// materialized when it's accessed through methods such as
+    /// [`types::Component::get_import`] or [`types::Component::exports`], so
+    /// inspecting just a few items of many components only pays for those
+    /// items.
+    ///
     /// ## Component types and Resources
     ///
     /// An important point to note here is that the precise type of imports and
@@ -387,12 +937,386 @@ impl Component {
         self.with_uninstantiated_instance_type(|ty| types::Component::from(self.inner.ty, ty))
     }
 
+    /// Iterates over the imports of this component along with their types.
+    ///
+    /// This yields the same items as [`types::Component::imports`] on the
+    /// result of [`Component::component_type`], but borrows from this
+    /// component directly instead of creating an intermediate
+    /// [`types::Component`].
+    pub fn imports(&self) -> impl ExactSizeIterator<Item = (&str, types::ComponentItem)> + '_ {
+        let ty = self.uninstantiated_instance_type();
+        self.types()[self.inner.ty]
+            .imports
+            .iter()
+            .map(move |(name, def)| {
+                (
+                    name.as_str(),
+                    types::ComponentItem::from(self.engine(), def, &ty),
+                )
+            })
+    }
+
+    /// Iterates over the exports of this component along with their types.
+    ///
+    /// This yields the same items as [`types::Component::exports`] on the
+    /// result of [`Component::component_type`], but borrows from this
+    /// component directly instead of creating an intermediate
+    /// [`types::Component`].
+    pub fn exports(&self) -> impl ExactSizeIterator<Item = (&str, types::ComponentItem)> + '_ {
+        let ty = self.uninstantiated_instance_type();
+        self.types()[self.inner.ty]
+            .exports
+            .iter()
+            .map(move |(name, def)| {
+                (
+                    name.as_str(),
+                    types::ComponentItem::from(self.engine(), def, &ty),
+                )
+            })
+    }
+
+    /// Iterates over the instances exported from this component along with
+    /// their types.
+    ///
+    /// This is a shorthand for filtering the exports of
+    /// [`Component::component_type`] down to
+    /// [`ComponentItem::ComponentInstance`](types::ComponentItem::ComponentInstance).
+    /// Nested exports of each instance can be explored further with
+    /// [`types::ComponentInstance::exports`],
+    /// [`types::ComponentInstance::instances`], and
+    /// [`types::ComponentInstance::funcs`].
+    pub fn exported_instances<'a>(
+        &'a self,
+        engine: &'a Engine,
+    ) -> impl Iterator<Item = (&'a str, types::ComponentInstance)> + 'a {
+        self.types()[self.inner.ty]
+            .exports
+            .iter()
+            .filter_map(move |(name, def)| {
+                let item = self.with_uninstantiated_instance_type(|ty| {
+                    types::ComponentItem::from(engine, def, ty)
+                });
+                match item {
+                    types::ComponentItem::ComponentInstance(instance) => {
+                        Some((name.as_str(), instance))
+                    }
+                    _ => None,
+                }
+            })
+    }
+
+    /// Iterates over the functions exported directly from this component,
+    /// along with their types.
+    ///
+    /// Functions exported from within instances are not included. This is a
+    /// shorthand for filtering the exports of [`Component::component_type`]
+    /// down to
+    /// [`ComponentItem::ComponentFunc`](types::ComponentItem::ComponentFunc),
+    /// which are typically the functions an embedder calls directly.
+    pub fn entry_points<'a>(
+        &'a self,
+        engine: &'a Engine,
+    ) -> impl Iterator<Item = (String, types::ComponentFunc)> + 'a {
+        self.types()[self.inner.ty]
+            .exports
+            .iter()
+            .filter_map(move |(name, def)| {
+                let item = self.with_uninstantiated_instance_type(|ty| {
+                    types::ComponentItem::from(engine, def, ty)
+                });
+                match item {
+                    types::ComponentItem::ComponentFunc(func) => Some((name.clone(), func)),
+                    _ => None,
+                }
+            })
+    }
+
+    /// Iterates over every export of this component, including those nested
+    /// within exported instances, along with the full path of names leading
+    /// to each one.
+    ///
+    /// Exported instances are yielded themselves and are then followed by all
+    /// of their own exports, recursively. For example a function `read`
+    /// within the exported instance `wasi:io/streams` is yielded with the path
+    /// `["wasi:io/streams", "read"]`.
+    pub fn exports_recursive(
+        &self,
+        engine: &Engine,
+    ) -> impl Iterator<Item = (Vec<String>, types::ComponentItem)> + use<> {
+        fn visit(
+            engine: &Engine,
+            path: &mut Vec<String>,
+            exports: impl Iterator<Item = (impl Into<String>, types::ComponentItem)>,
+            items: &mut Vec<(Vec<String>, types::ComponentItem)>,
+        ) {
+            for (name, item) in exports {
+                path.push(name.into());
+                let nested = match &item {
+                    types::ComponentItem::ComponentInstance(instance) => Some(instance.clone()),
+                    _ => None,
+                };
+                items.push((path.clone(), item));
+                if let Some(instance) = nested {
+                    visit(engine, path, instance.exports(engine), items);
+                }
+                path.pop();
+            }
+        }
+
+        let mut items = Vec::new();
+        visit(
+            engine,
+            &mut Vec::new(),
+            self.component_type().exports(engine),
+            &mut items,
+        );
+        items.into_iter()
+    }
+
+    /// Returns the type of the resource exported from this component under
+    /// `name`.
+    ///
+    /// This is a shorthand for looking up `name` in the exports of
+    /// [`Component::component_type`] and matching on
+    /// [`ComponentItem::Resource`](types::ComponentItem::Resource). Note that,
+    /// as with [`Component::component_type`], the returned type is not equal
+    /// to the type of the resource in any particular instantiation of this
+    /// component. Use [`Instance::get_resource`] to learn the type of the
+    /// resource within an instance, or
+    /// [`ResourceType::same_definition`](types::ResourceType::same_definition)
+    /// to relate the two.
+    ///
+    /// Returns `None` if `name` isn't exported or isn't a resource.
+    ///
+    /// [`Instance::get_resource`]: crate::component::Instance::get_resource
+    pub fn export_resource_type(&self, engine: &Engine, name: &str) -> Option<types::ResourceType> {
+        match self.component_type().get_export(engine, name)? {
+            types::ComponentItem::Resource(ty) => Some(ty),
+            _ => None,
+        }
+    }
+
+    /// Returns the type of the item exported from this component at `path`.
+    ///
+    /// The first element of `path` names an export of this component and each
+    /// following element names an export of the instance found so far, which
+    /// allows looking up items nested arbitrarily deeply within exported
+    /// instances.
+    ///
+    /// Returns `None` if `path` is empty or if any element of `path` isn't
+    /// exported, or isn't exported from an instance.
+    pub fn type_of(&self, engine: &Engine, path: &[&str]) -> Option<types::ComponentItem> {
+        let (first, rest) = path.split_first()?;
+        let mut item = self.component_type().get_export(engine, first)?;
+        for name in rest {
+            item = match item {
+                types::ComponentItem::ComponentInstance(instance) => {
+                    instance.get_export(engine, name)?
+                }
+                _ => return None,
+            };
+        }
+        Some(item)
+    }
+
+    /// Returns the core wasm module that this component exports as `name`.
+    ///
+    /// The returned [`Module`] shares its compiled code with this component
+    /// and can be instantiated on its own, for example to re-host it outside
+    /// of the component.
+    ///
+    /// Returns `None` if `name` isn't exported, isn't a module, or is a
+    /// module which this component itself imports.
+    pub fn export_module(&self, name: &str) -> Option<&Module> {
+        let component = self.env_component();
+        let index = component.exports.get(name, &NameMapNoIntern)?;
+        match &component.export_items[*index] {
+            Export::ModuleStatic { index, .. } => Some(self.static_module(*index)),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over all core wasm modules bundled within this
+    /// component.
+    ///
+    /// Modules are yielded in the order they're stored in the compiled
+    /// component, which is stable across [`Component::serialize`] and
+    /// [`Component::deserialize`]. This includes modules from nested
+    /// components as well as adapter modules synthesized by Wasmtime during
+    /// compilation, but not modules which this component imports.
+    ///
+    /// Each returned [`Module`] shares its compiled code with this component.
+    pub fn modules(&self) -> impl ExactSizeIterator<Item = Module> + '_ {
+        self.inner.static_modules.values().cloned()
+    }
+
+    /// Returns the trampoline which the exported function `name` is lifted
+    /// from, if any.
+    ///
+    /// Most exported functions are lifted from a function exported by a core
+    /// wasm instance, in which case there is no trampoline and `None` is
+    /// returned. A function is lifted from a trampoline when, for example, a
+    /// component lowers one of its imports and then lifts the result back up
+    /// as an export.
+    ///
+    /// Returns `None` as well if `name` isn't an exported function.
+    #[doc(hidden)]
+    pub fn export_trampoline(&self, name: &str) -> Option<TrampolineIndex> {
+        let component = self.env_component();
+        let index = component.exports.get(name, &NameMapNoIntern)?;
+        match &component.export_items[*index] {
+            Export::LiftedFunction {
+                func: CoreDef::Trampoline(index),
+                ..
+            } => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Iterates over the names of the interfaces, or instances, that this
+    /// component imports.
+    ///
+    /// Unlike the full list of imports in [`Component::component_type`] this
+    /// only yields imports which are instances, for example
+    /// `wasi:cli/stdout@0.2.0`, and skips individually imported functions,
+    /// types, and so on. This is useful to summarize which interfaces a
+    /// component depends on.
+    pub fn imported_interfaces(&self) -> impl Iterator<Item = &str> + '_ {
+        self.env_component()
+            .import_types
+            .values()
+            .filter_map(|(name, ty)| match ty {
+                TypeDef::ComponentInstance(_) => Some(name.as_str()),
+                _ => None,
+            })
+    }
+
+    /// Returns the total number of items that must be provided to instantiate
+    /// this component, counting every item within imported instances.
+    ///
+    /// A top-level import of an instance counts as the sum of the items that
+    /// instance exports, recursively, rather than as one import. Functions,
+    /// resources, modules, and components each count as one item while
+    /// non-resource types are not counted since they don't need to be
+    /// provided. This can be used to gauge how much wiring a host needs to
+    /// perform, for example with a [`Linker`].
+    pub fn total_imports(&self) -> usize {
+        fn count(types: &ComponentTypes, ty: &TypeDef) -> usize {
+            match ty {
+                TypeDef::ComponentInstance(i) => {
+                    types[*i].exports.values().map(|ty| count(types, ty)).sum()
+                }
+                TypeDef::Interface(_) => 0,
+                TypeDef::Component(_)
+                | TypeDef::ComponentFunc(_)
+                | TypeDef::Module(_)
+                | TypeDef::CoreFunc(_)
+                | TypeDef::Resource(_) => 1,
+            }
+        }
+        let types = self.types();
+        types[self.inner.ty]
+            .imports
+            .values()
+            .map(|ty| count(types, ty))
+            .sum()
+    }
+
+    /// Returns every item that the host must provide to instantiate this
+    /// component, with imported instances flattened into their exports.
+    ///
+    /// Each exported item of an imported instance is reported individually,
+    /// recursing into nested instances, with [`HostImport::instance`] set to
+    /// the dotted path of instance names leading to it. Items imported at the
+    /// top level of the component have no instance. Imported instances
+    /// without any exports are reported as themselves so that they're not
+    /// overlooked.
+    ///
+    /// Imported resources are reported as [`types::ComponentItem::Resource`]
+    /// and are distinct from the functions which operate on them. Imported
+    /// types are included as well, even though a [`Linker`] doesn't need a
+    /// definition for them, since they describe what the host's functions
+    /// receive and return.
+    pub fn required_host_imports(&self) -> Vec<HostImport> {
+        fn flatten(
+            engine: &Engine,
+            instance: Option<String>,
+            name: &str,
+            item: types::ComponentItem,
+            imports: &mut Vec<HostImport>,
+        ) {
+            if let types::ComponentItem::ComponentInstance(ty) = &item {
+                if ty.exports(engine).len() > 0 {
+                    let path = match &instance {
+                        Some(instance) => format!("{instance}.{name}"),
+                        None => name.to_string(),
+                    };
+                    for (name, item) in ty.exports(engine) {
+                        flatten(engine, Some(path.clone()), name, item, imports);
+                    }
+                    return;
+                }
+            }
+            imports.push(HostImport {
+                instance,
+                name: name.to_string(),
+                item,
+            });
+        }
+
+        let mut imports = Vec::new();
+        for (name, item) in self.imports() {
+            flatten(self.engine(), None, name, item, &mut imports);
+        }
+        imports
+    }
+
+    /// Returns the shared memories that this component expects the host to
+    /// provide.
+    ///
+    /// Shared memories can't be imported into a component directly, so these
+    /// are the shared memories imported by core modules which are themselves
+    /// imported by this component. Each item is the name of the component's
+    /// module import paired with a description of the core memory import.
+    /// Shared memories defined within the component, or within modules that it
+    /// defines, are not included.
+    pub fn imported_shared_memories(&self) -> impl Iterator<Item = (&str, MemorySpec)> + '_ {
+        let types = self.types();
+        types[self.inner.ty]
+            .imports
+            .iter()
+            .filter_map(|(name, ty)| match ty {
+                TypeDef::Module(module) => Some((name, &types[*module])),
+                _ => None,
+            })
+            .flat_map(|(name, module)| {
+                module
+                    .imports
+                    .iter()
+                    .filter_map(move |((module, field), ty)| match ty {
+                        EntityType::Memory(memory) if memory.shared => Some((
+                            name.as_str(),
+                            MemorySpec {
+                                module: module.clone(),
+                                name: field.clone(),
+                                ty: MemoryType::from_wasmtime_memory(memory),
+                            },
+                        )),
+                        _ => None,
+                    })
+            })
+    }
+
     fn with_uninstantiated_instance_type<R>(&self, f: impl FnOnce(&InstanceType<'_>) -> R) -> R {
-        let resources = Arc::new(PrimaryMap::new());
-        f(&InstanceType {
+        f(&self.uninstantiated_instance_type())
+    }
+
+    fn uninstantiated_instance_type(&self) -> InstanceType<'_> {
+        InstanceType {
+            component: self.id(),
             types: self.types(),
-            resources: &resources,
-        })
+            resources: &self.inner.uninstantiated_resources,
+        }
     }
 
     /// Final assembly step for a component from its in-memory representation.
@@ -403,6 +1327,18 @@ impl Component {
         engine: &Engine,
         code_memory: Arc<CodeMemory>,
         artifacts: Option<ComponentArtifacts>,
+    ) -> Result<Component> {
+        Component::from_parts_with(engine, code_memory, artifacts, true)
+    }
+
+    /// Same as [`Component::from_parts`], except that validation against the
+    /// engine's instance allocator is skipped if `validate_allocator` is
+    /// `false`.
+    fn from_parts_with(
+        engine: &Engine,
+        code_memory: Arc<CodeMemory>,
+        artifacts: Option<ComponentArtifacts>,
+        validate_allocator: bool,
     ) -> Result<Component> {
         let ComponentArtifacts {
             ty,
@@ -411,6 +1347,7 @@ impl Component {
             mut types,
             mut static_modules,
             checksum,
+            original_wasm,
         } = match artifacts {
             Some(artifacts) => artifacts,
             None => postcard::from_bytes(code_memory.wasmtime_info())?,
@@ -419,11 +1356,13 @@ impl Component {
 
         // Validate that the component can be used with the current instance
         // allocator.
-        engine.allocator().validate_component(
-            &info.component,
-            &VMComponentOffsets::new(HostPtr, &info.component),
-            &|module_index| &static_modules[module_index].module,
-        )?;
+        if validate_allocator {
+            engine.allocator().validate_component(
+                &info.component,
+                &VMComponentOffsets::new(HostPtr, &info.component),
+                &|module_index| &static_modules[module_index].module,
+            )?;
+        }
 
         // Create a signature registration with the `Engine` for all trampolines
         // and core wasm types found within this component, both for the
@@ -436,7 +1375,7 @@ impl Component {
 
         // Assemble the `EngineCode` artifact which is shared by all core wasm
         // modules as well as the final component.
-        let types = Arc::new(types);
+        let types = engine.intern_component_types(types)?;
         let code = Arc::new(EngineCode::new(code_memory, signatures, types.into()));
 
         // Convert all information about static core wasm modules into actual
@@ -466,6 +1405,11 @@ impl Component {
                 index,
                 realloc_func_type,
                 checksum,
+                original_wasm: original_wasm.map(Vec::into_boxed_slice),
+                live_instances: AtomicUsize::new(0),
+                name_resolver: Default::default(),
+                compilation_id: Default::default(),
+                uninstantiated_resources: Arc::new(PrimaryMap::new()),
             }),
         })
     }
@@ -570,11 +1514,238 @@ impl Component {
         Ok(self.engine_code().image().to_vec())
     }
 
-    /// Creates a new `VMFuncRef` with all fields filled out for the destructor
-    /// specified.
+    /// Same as [`Component::serialize`], except that the artifact is written
+    /// directly to `writer` instead of being copied into a new `Vec<u8>`.
     ///
-    /// The `dtor`'s own `VMFuncRef` won't have `wasm_call` filled out but this
-    /// component may have `resource_drop_wasm_to_native_trampoline` filled out
+    /// This avoids an intermediate allocation of the entire artifact for
+    /// callers which stream it to a file or socket. The `writer` is not
+    /// flushed by this method, so buffering remains under the caller's
+    /// control.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `writer` fails, including if it stops
+    /// accepting bytes before the whole artifact has been written.
+    #[cfg(feature = "std")]
+    pub fn serialize_to_writer<W: std::io::Write>(&self, mut writer: W) -> Result<()> {
+        writer
+            .write_all(self.engine_code().image())
+            .context("failed to write component to writer")?;
+        Ok(())
+    }
+
+    /// Same as [`Component::serialize`], except that the artifact is written
+    /// into `buf` instead of a freshly allocated `Vec<u8>`.
+    ///
+    /// Any previous contents of `buf` are cleared first, and its existing
+    /// capacity is reused where possible. The resulting bytes are identical
+    /// to those returned by [`Component::serialize`].
+    pub fn serialize_into(&self, buf: &mut Vec<u8>) -> Result<()> {
+        buf.clear();
+        buf.extend_from_slice(self.engine_code().image());
+        Ok(())
+    }
+
+    /// Same as [`Component::serialize`], except that the artifact is
+    /// compressed with zstd.
+    ///
+    /// The result is prefixed with a header identifying it as compressed and
+    /// must be loaded with [`Component::deserialize_compressed`];
+    /// [`Component::deserialize`] returns an error for it.
+    #[cfg(feature = "zstd")]
+    pub fn serialize_compressed(&self) -> Result<Vec<u8>> {
+        let mut bytes = COMPRESSED_MAGIC.to_vec();
+        zstd::stream::copy_encode(self.engine_code().image(), &mut bytes, 0)
+            .context("failed to compress component")?;
+        Ok(bytes)
+    }
+
+    /// Same as [`Component::serialize`], except that the artifact is split
+    /// into its compiled code and its metadata, returned in that order.
+    ///
+    /// The metadata describes this component's types, exports, and
+    /// initialization program, while the code contains everything else. The
+    /// metadata's contents are zeroed out in the returned code, so the code of
+    /// components which share identical machine code, but differ in metadata
+    /// of the same size, is byte-for-byte identical and can be deduplicated by
+    /// content-addressed stores.
+    ///
+    /// The two halves are passed to [`Component::from_parts_split`] to
+    /// recreate the component. The code on its own is not a valid input to
+    /// [`Component::deserialize`].
+    pub fn serialize_split(&self) -> Result<(Vec<u8>, Vec<u8>)> {
+        let code = self.engine_code();
+        let image = code.image();
+        let metadata = code.wasmtime_info();
+        let start = metadata.as_ptr().addr() - image.as_ptr().addr();
+        let mut bytes = image.to_vec();
+        bytes[start..][..metadata.len()].fill(0);
+        Ok((bytes, metadata.to_vec()))
+    }
+
+    /// Converts this component into a [`DetachedComponent`] which holds only
+    /// its serialized artifact.
+    ///
+    /// Unlike a [`Component`], a [`DetachedComponent`] holds no references to
+    /// the [`Engine`] it was created within, so once all other handles to the
+    /// engine are dropped the engine is fully released. The component can
+    /// later be reloaded with [`DetachedComponent::attach`], for example into
+    /// a fresh engine with a compatible configuration.
+    pub fn detach(self) -> Result<DetachedComponent> {
+        Ok(DetachedComponent {
+            bytes: self.serialize()?,
+        })
+    }
+
+    /// Returns the original WebAssembly binary that this component was compiled
+    /// from, if it was preserved.
+    ///
+    /// The binary is only available if
+    /// [`Config::preserve_wasm_source`](crate::Config::preserve_wasm_source)
+    /// was enabled when this component was compiled. It is embedded in the
+    /// compiled artifact, so it's also available for components created with
+    /// [`Component::deserialize`] and friends.
+    pub fn original_wasm(&self) -> Option<&[u8]> {
+        self.inner.original_wasm.as_deref()
+    }
+
+    /// Recompiles this component from its [original WebAssembly
+    /// binary](Component::original_wasm) within a new [`Engine`] created with
+    /// `config`.
+    ///
+    /// This can be used to compare compilation settings, such as optimization
+    /// levels, on a component which is already loaded without supplying its
+    /// source again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the original binary wasn't preserved with
+    /// [`Config::preserve_wasm_source`](crate::Config::preserve_wasm_source),
+    /// if `config` is invalid, or if compilation fails.
+    #[cfg(any(feature = "cranelift", feature = "winch"))]
+    pub fn rebuild_with_config(&self, config: &crate::Config) -> Result<Component> {
+        let wasm = self.original_wasm().ok_or_else(|| {
+            format_err!(
+                "cannot rebuild a component whose source wasn't preserved with \
+                 `Config::preserve_wasm_source`"
+            )
+        })?;
+        let engine = Engine::new(config)?;
+        Component::from_binary(&engine, wasm)
+    }
+
+    /// Extracts the names of the functions within this component's core
+    /// modules into a standalone blob.
+    ///
+    /// The names come from the `name` section of the original core modules,
+    /// and are what's used to symbolicate backtraces. The returned blob can be
+    /// attached with [`Component::attach_symbols`] to another compilation of
+    /// the same component whose names were stripped, for example to only ship
+    /// stripped binaries to production while still being able to symbolicate
+    /// crashes when debugging.
+    pub fn extract_symbols(&self) -> Result<Vec<u8>> {
+        let modules = self
+            .inner
+            .static_modules
+            .values()
+            .map(|module| {
+                let compiled = module.compiled_module();
+                module
+                    .env_module()
+                    .functions
+                    .keys()
+                    .filter_map(|idx| Some((idx.as_u32(), compiled.func_name(idx)?.to_string())))
+                    .collect()
+            })
+            .collect();
+        Ok(postcard::to_allocvec(&ComponentSymbols { modules })?)
+    }
+
+    /// Attaches function names previously returned from
+    /// [`Component::extract_symbols`] to this component.
+    ///
+    /// Attached names are used for functions which don't have a name of their
+    /// own, such as in backtraces of traps, and this doesn't affect the
+    /// compiled code of this component in any way. As names are part of the
+    /// shared metadata of a component they're visible through all clones of
+    /// this component.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `symbols` wasn't produced by
+    /// [`Component::extract_symbols`] for a component with the same structure
+    /// as this one, or if symbols have already been attached to this
+    /// component.
+    pub fn attach_symbols(&self, symbols: &[u8]) -> Result<()> {
+        let symbols: ComponentSymbols =
+            postcard::from_bytes(symbols).context("failed to decode component symbols")?;
+        ensure!(
+            symbols.modules.len() == self.inner.static_modules.len(),
+            "symbols describe {} core modules but this component has {}",
+            symbols.modules.len(),
+            self.inner.static_modules.len(),
+        );
+        // Validate the names for all modules before attaching any of them so
+        // invalid symbols don't leave this component partially named.
+        let names = self
+            .inner
+            .static_modules
+            .values()
+            .zip(symbols.modules)
+            .map(|(module, names)| {
+                let functions = &module.env_module().functions;
+                names
+                    .into_iter()
+                    .map(|(idx, name)| {
+                        let idx = FuncIndex::from_u32(idx);
+                        ensure!(
+                            functions.get(idx).is_some(),
+                            "symbols name function {} which doesn't exist",
+                            idx.as_u32()
+                        );
+                        Ok((idx, name))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for (module, names) in self.inner.static_modules.values().zip(names) {
+            module.compiled_module().attach_func_names(names)?;
+        }
+        Ok(())
+    }
+
+    /// Makes this component share the compiled code of `other` if the two were
+    /// produced from byte-for-byte identical artifacts.
+    ///
+    /// Loading the same precompiled artifact twice, for example from two
+    /// different cache paths, results in two independent mappings of the same
+    /// code. Calling this method on one of them allows the duplicate mapping to
+    /// be released once all other clones of `self` are dropped.
+    ///
+    /// Returns `true` if `self` now shares `other`'s code, including the case
+    /// where they were already shared, and `false` if the components differ or
+    /// belong to different engines. When `true` is returned `self` becomes a
+    /// clone of `other`, so [`ComponentExportIndex`] values previously
+    /// obtained from `self` must be looked up again through
+    /// [`Component::get_export_index`].
+    pub fn deduplicate_with(&mut self, other: &Component) -> bool {
+        if Arc::ptr_eq(&self.inner, &other.inner) {
+            return true;
+        }
+        if !Engine::same(self.engine(), other.engine())
+            || self.engine_code().image() != other.engine_code().image()
+        {
+            return false;
+        }
+        self.inner = other.inner.clone();
+        true
+    }
+
+    /// Creates a new `VMFuncRef` with all fields filled out for the destructor
+    /// specified.
+    ///
+    /// The `dtor`'s own `VMFuncRef` won't have `wasm_call` filled out but this
+    /// component may have `resource_drop_wasm_to_native_trampoline` filled out
     /// if necessary in which case it's filled in here.
     pub(crate) fn resource_drop_func_ref(&self, dtor: &crate::func::HostFunc) -> VMFuncRef {
         // Host functions never have their `wasm_call` filled in at this time.
@@ -650,23 +1821,41 @@ impl Component {
     /// # Ok(()) }
     /// ```
     pub fn resources_required(&self) -> Option<ResourcesRequired> {
+        self.resources_required_report().ok()
+    }
+
+    /// Same as [`Component::resources_required`], except that when the
+    /// resources can't be determined statically the reason why is returned.
+    ///
+    /// The returned [`UnknownResourceReason`] lists the imports supplying the
+    /// core modules which this component instantiates, since the resources
+    /// those modules require aren't known until instantiation. Hints for them
+    /// can then be provided with [`Component::resources_required_with`].
+    pub fn resources_required_report(&self) -> Result<ResourcesRequired, UnknownResourceReason> {
+        let component = self.env_component();
         let mut resources = ResourcesRequired {
             num_memories: 0,
             max_initial_memory_size: None,
             num_tables: 0,
             max_initial_table_size: None,
         };
-        for init in &self.env_component().initializers {
+        let mut imported_modules = Vec::<String>::new();
+        for init in &component.initializers {
             match init {
                 GlobalInitializer::InstantiateModule(inst, _) => match inst {
                     InstantiateModule::Static(index, _) => {
                         let module = self.static_module(*index);
                         resources.add(&module.resources_required());
                     }
-                    InstantiateModule::Import(_, _) => {
+                    InstantiateModule::Import(index, _) => {
                         // We can't statically determine the resources required
-                        // to instantiate this component.
-                        return None;
+                        // to instantiate this component, but keep going to
+                        // find all such imports.
+                        let (import, _) = component.imports[*index];
+                        let name = &component.import_types[import].0;
+                        if !imported_modules.contains(name) {
+                            imported_modules.push(name.clone());
+                        }
                     }
                 },
                 GlobalInitializer::LowerImport { .. }
@@ -678,167 +1867,1350 @@ impl Component {
                 | GlobalInitializer::Resource(_) => {}
             }
         }
-        Some(resources)
+        if imported_modules.is_empty() {
+            Ok(resources)
+        } else {
+            Err(UnknownResourceReason { imported_modules })
+        }
     }
 
-    /// Returns the range, in the host's address space, that this module's
-    /// compiled code resides at.
+    /// Same as [`Component::resources_required`], except that core modules
+    /// imported by this component are accounted for with caller-supplied
+    /// hints.
     ///
-    /// For more information see
-    /// [`Module::image_range`](crate::Module::image_range).
-    pub fn image_range(&self) -> Range<*const u8> {
-        self.inner.code.image().as_ptr_range()
+    /// Each entry of `imports` maps the name of a top-level import of this
+    /// component to the resources required to instantiate the core module
+    /// supplied for that import. If the module is provided as an export of an
+    /// imported instance then the hint is looked up by the name of that
+    /// instance import. A hint is added once for each time its module is
+    /// instantiated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this component instantiates an imported core
+    /// module whose import name has no entry in `imports`.
+    pub fn resources_required_with(
+        &self,
+        imports: &HashMap<String, ResourcesRequired>,
+    ) -> Result<ResourcesRequired> {
+        let component = self.env_component();
+        let mut resources = ResourcesRequired {
+            num_memories: 0,
+            max_initial_memory_size: None,
+            num_tables: 0,
+            max_initial_table_size: None,
+        };
+        for init in &component.initializers {
+            let GlobalInitializer::InstantiateModule(inst, _) = init else {
+                continue;
+            };
+            match inst {
+                InstantiateModule::Static(index, _) => {
+                    let module = self.static_module(*index);
+                    resources.add(&module.resources_required());
+                }
+                InstantiateModule::Import(index, _) => {
+                    let (import, _) = component.imports[*index];
+                    let name = &component.import_types[import].0;
+                    let Some(hint) = imports.get(name) else {
+                        bail!("no resource hint provided for imported module `{name}`");
+                    };
+                    resources.add(hint);
+                }
+            }
+        }
+        Ok(resources)
     }
 
-    /// Force initialization of copy-on-write images to happen here-and-now
-    /// instead of when they're requested during first instantiation.
+    /// Returns whether any core module embedded within this component defines
+    /// or imports a 64-bit memory.
     ///
-    /// When [copy-on-write memory
-    /// initialization](crate::Config::memory_init_cow) is enabled then Wasmtime
-    /// will lazily create the initialization image for a component. This method
-    /// can be used to explicitly dictate when this initialization happens.
+    /// Components using memory64 require different handling in the canonical
+    /// ABI, for example pointers and lengths are passed as `i64` rather than
+    /// `i32`, so hosts may need to check this before interacting with the
+    /// component's linear memory directly.
+    pub fn uses_memory64(&self) -> bool {
+        self.inner
+            .static_modules
+            .values()
+            .flat_map(|module| module.env_module().memories.values())
+            .any(|memory| memory.idx_type == IndexType::I64)
+    }
+
+    /// Returns a human-readable summary of how each linear memory within this
+    /// component is configured.
     ///
-    /// Note that this largely only matters on Linux when memfd is used.
-    /// Otherwise the copy-on-write image typically comes from disk and in that
-    /// situation the creation of the image is trivial as the image is always
-    /// sourced from disk. On Linux, though, when memfd is used a memfd is
-    /// created and the initialization image is written to it.
+    /// Each memory, both defined and imported, of every core module within
+    /// this component gets one line describing whether its bounds checks can
+    /// be elided in favor of guard pages ("static") or must be performed
+    /// explicitly ("dynamic"), the size of its guard region, and whether it's
+    /// a 64-bit memory. This is intended to help diagnose unexpected
+    /// out-of-bounds traps and the exact format is not stable.
+    pub fn memory_config_summary(&self) -> String {
+        let tunables = self.engine().tunables();
+        #[cfg(has_virtual_memory)]
+        let host_page_size_log2 =
+            u8::try_from(crate::runtime::vm::host_page_size().ilog2()).unwrap();
+        // Without virtual memory no memory can rely on guard pages, which a
+        // host page size larger than any wasm page size expresses.
+        #[cfg(not(has_virtual_memory))]
+        let host_page_size_log2 = u8::MAX;
+        let mut summary = String::new();
+        for (module_index, module) in self.inner.static_modules.iter() {
+            for (memory_index, memory) in module.env_module().memories.iter() {
+                let strategy = if memory.can_elide_bounds_check(tunables, host_page_size_log2) {
+                    "static"
+                } else {
+                    "dynamic"
+                };
+                let guard_size = if memory.can_use_virtual_memory(tunables, host_page_size_log2) {
+                    tunables.memory_guard_size
+                } else {
+                    0
+                };
+                summary.push_str(&format!(
+                    "module {} memory {}: {strategy} bounds checks, guard size {guard_size:#x}, index64 {}\n",
+                    module_index.as_u32(),
+                    memory_index.as_u32(),
+                    memory.idx_type == IndexType::I64,
+                ));
+            }
+        }
+        summary
+    }
+
+    /// Returns counts of the trampolines within this component by kind.
     ///
-    /// Also note that this method is not required to be called, it's available
-    /// as a performance optimization if required but is otherwise handled
-    /// automatically.
-    pub fn initialize_copy_on_write_image(&self) -> Result<()> {
-        for (_, module) in self.inner.static_modules.iter() {
-            module.initialize_copy_on_write_image()?;
+    /// Trampolines are the compiled glue between core wasm and the host or
+    /// the canonical ABI, such as lowered imports and resource intrinsics.
+    /// A component dominated by one kind of trampoline can hint at the ABI
+    /// patterns it exercises most.
+    pub fn trampoline_kind_counts(&self) -> TrampolineCounts {
+        use wasmtime_environ::component::TrampolineKind;
+
+        let mut counts = TrampolineCounts::default();
+        for kind in self.inner.info.trampoline_kinds.values() {
+            match kind {
+                TrampolineKind::LowerImport => counts.lowerings += 1,
+                TrampolineKind::ResourceDrop => counts.resource_drops += 1,
+                TrampolineKind::Trap => counts.always_trap += 1,
+                TrampolineKind::Other => counts.other += 1,
+            }
         }
-        Ok(())
+        counts
     }
 
-    /// Looks up a specific export of this component by `name` optionally nested
-    /// within the `instance` provided.
+    /// Returns the indices of the trampolines within this component which
+    /// unconditionally trap.
     ///
-    /// See related method [`Self::get_export`] for additional docs and
-    /// examples.
+    /// These are counted as [`TrampolineCounts::always_trap`] and are used by
+    /// the adapters generated between components to raise traps for invalid
+    /// operations, such as calls which would violate the canonical ABI. A
+    /// trap raised through one of them at runtime typically points to
+    /// components which were wired together incorrectly.
+    pub fn always_trap_trampolines(&self) -> impl Iterator<Item = TrampolineIndex> + '_ {
+        self.inner
+            .info
+            .trampoline_kinds
+            .iter()
+            .filter(|(_, kind)| **kind == wasmtime_environ::component::TrampolineKind::Trap)
+            .map(|(index, _)| index)
+    }
+
+    /// Returns the number of trampolines compiled for this component.
     ///
-    /// This method is primarily used to acquire a [`ComponentExportIndex`]
-    /// which can be used with [`Instance`](crate::component::Instance) when
-    /// looking up exports. Export lookup with [`ComponentExportIndex`] can
-    /// skip string lookups at runtime and instead use a more efficient
-    /// index-based lookup.
+    /// Trampolines implement lowered imports and the intrinsics of the
+    /// canonical ABI, see [`Component::trampoline_kind_counts`] for a
+    /// breakdown by kind. Lowered functions which are never passed to a core
+    /// instance aren't compiled and have no trampoline. Valid indices for
+    /// [`Component::trampoline_signature`] range from zero up to this number.
+    pub fn num_trampolines(&self) -> usize {
+        self.env_component().trampolines.len()
+    }
+
+    /// Returns the core wasm signature of the trampoline at `index`.
     ///
-    /// This method only returns the [`ComponentExportIndex`]. If you need the
-    /// corresponding [`types::ComponentItem`], use the related function
-    /// [`Self::get_export`].
+    /// This is the type of the core function that each trampoline implements,
+    /// which can be used to label trampoline calls, for example when
+    /// diagnosing a type mismatch in a lowering.
+    ///
+    /// Returns `None` if `index` is not less than
+    /// [`Component::num_trampolines`].
+    pub fn trampoline_signature(&self, index: usize) -> Option<FuncType> {
+        let index = u32::try_from(index).ok()?;
+        let ty = *self
+            .env_component()
+            .trampolines
+            .get(TrampolineIndex::from_u32(index))?;
+        let ty = self.signatures().shared_type(ty)?;
+        Some(FuncType::from_shared_type_index(self.engine(), ty))
+    }
+
+    /// Returns whether this component extracts a `realloc` function from one
+    /// of its core instances.
     ///
+    /// A `realloc` is required by the canonical ABI to pass strings, lists,
+    /// and other values which live in linear memory into a core instance. If
+    /// this returns `false` then only values which are passed directly as
+    /// core wasm values are used across this component's lifts and lowers.
+    pub fn has_realloc(&self) -> bool {
+        self.env_component()
+            .initializers
+            .iter()
+            .any(|i| matches!(i, GlobalInitializer::ExtractRealloc(_)))
+    }
+
+    /// Returns the number of nested component instances created when this
+    /// component is instantiated.
     ///
-    /// [`Instance`](crate::component::Instance) has a corresponding method
-    /// [`Instance::get_export_index`](crate::component::Instance::get_export_index).
-    pub fn get_export_index(
-        &self,
-        instance: Option<&ComponentExportIndex>,
-        name: &str,
-    ) -> Option<ComponentExportIndex> {
-        let index = self.lookup_export_index(instance, name)?;
-        Some(ComponentExportIndex {
-            id: self.inner.id,
-            index,
-        })
+    /// Nested components are flattened into this component when it's
+    /// compiled, so this counts each instantiation of a sub-component,
+    /// recursively, rather than each sub-component definition: a nested
+    /// component which is never instantiated isn't counted and one which is
+    /// instantiated twice is counted twice. The outermost component itself
+    /// isn't included, and neither are core modules, which are described by
+    /// [`Component::module_graph`].
+    pub fn num_nested_components(&self) -> usize {
+        let instances = self.env_component().num_runtime_component_instances;
+        usize::try_from(instances.saturating_sub(1)).unwrap()
     }
 
-    /// Looks up a specific export of this component by `name` optionally nested
-    /// within the `instance` provided.
+    /// Returns the name of this component, if one was specified in its
+    /// `component-name` custom section.
     ///
-    /// This method is primarily used to acquire a [`ComponentExportIndex`]
-    /// which can be used with [`Instance`](crate::component::Instance) when
-    /// looking up exports. Export lookup with [`ComponentExportIndex`] can
-    /// skip string lookups at runtime and instead use a more efficient
-    /// index-based lookup.
+    /// Only the name of the outermost component is recorded, and it's
+    /// preserved across [`Component::serialize`] and
+    /// [`Component::deserialize`].
+    pub fn name(&self) -> Option<&str> {
+        self.env_component().name.as_deref()
+    }
+
+    /// Returns a SHA-256 hash identifying the compiled artifact of this
+    /// component, suitable as a key for content-addressed caches.
     ///
-    /// This method takes a few arguments:
+    /// The hash covers the compilation settings of the engine which produced
+    /// the artifact, such as the target and optimization level, along with the
+    /// component's compiled metadata and type information. Components
+    /// compiled from the same bytes with the same engine configuration have
+    /// equal ids, including after a round trip through [`Component::serialize`]
+    /// and [`Component::deserialize`], while changing a compilation setting
+    /// changes the id. It's computed on first use and then cached.
+    pub fn compilation_id(&self) -> [u8; 32] {
+        *self
+            .inner
+            .compilation_id
+            .get_or_init(|| self.compute_compilation_id(self.engine_code().wasmtime_info()))
+    }
+
+    fn compute_compilation_id(&self, metadata: &[u8]) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+
+        let (version, settings) = self
+            .engine()
+            .artifact_engine_section(self.engine_code().image(), ObjectKind::Component)
+            .expect("component artifacts are validated when loaded");
+        let mut hasher = Sha256::new();
+        for part in [version, settings, metadata] {
+            hasher.update(u64::try_from(part.len()).unwrap().to_le_bytes());
+            hasher.update(part);
+        }
+        hasher.finalize().into()
+    }
+
+    /// Returns a SHA-256 digest of the compiled code of the core module at
+    /// `index` within this component.
     ///
-    /// * `engine` - the engine that was used to compile this component.
-    /// * `instance` - an optional "parent instance" for the export being looked
-    ///   up. If this is `None` then the export is looked up on the root of the
-    ///   component itself, and otherwise the export is looked up on the
-    ///   `instance` specified. Note that `instance` must have come from a
-    ///   previous invocation of this method.
-    /// * `name` - the name of the export that's being looked up.
+    /// The digest covers the machine code of each function defined in the
+    /// module, in order, which only depends on the module itself and on how
+    /// it was compiled. Identical modules embedded in different components,
+    /// such as a shared adapter module, therefore have identical digests when
+    /// compiled by compatible engines, enabling module-level deduplication in
+    /// content-addressed stores.
     ///
-    /// If the export is located then two values are returned: a
-    /// [`types::ComponentItem`] which enables introspection about the type of
-    /// the export and a [`ComponentExportIndex`]. The index returned notably
-    /// implements the [`InstanceExportLookup`] trait which enables using it
-    /// with [`Instance::get_func`](crate::component::Instance::get_func) for
-    /// example.
+    /// The `index` of a module is that of [`ModuleGraphNode::static_module`].
     ///
-    /// The returned [`types::ComponentItem`] is more expensive to calculate
-    /// than the [`ComponentExportIndex`]. If you only consume the
-    /// [`ComponentExportIndex`], use the related method
-    /// [`Self::get_export_index`] instead.
+    /// # Panics
     ///
-    /// [`Instance`](crate::component::Instance) has a corresponding method
-    /// [`Instance::get_export`](crate::component::Instance::get_export).
+    /// Panics if `index` is out of bounds for this component's core modules.
+    pub fn module_digest(&self, index: StaticModuleIndex) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+
+        let module = &self.inner.static_modules[index];
+        let text = self.inner.code.text();
+        let mut hasher = Sha256::new();
+        for (_, range) in module.compiled_module().finished_function_ranges() {
+            hasher.update(u64::try_from(range.len()).unwrap().to_le_bytes());
+            hasher.update(&text[range]);
+        }
+        hasher.finalize().into()
+    }
+
+    /// Returns the dependency structure between the core instances created
+    /// when this component is instantiated.
     ///
-    /// # Examples
+    /// Each node of the returned graph is one core wasm module instantiation,
+    /// in the order they happen, and each edge connects an export of one
+    /// instance to the import of a later instance that it satisfies. Imports
+    /// satisfied by something other than a core instance's export, such as
+    /// lowered host functions, don't produce edges.
+    pub fn module_graph(&self) -> ModuleGraph {
+        let component = self.env_component();
+        let mut graph = ModuleGraph::default();
+        // The module instantiated by each node, if it's statically known,
+        // to resolve the names of exports referred to by index.
+        let mut node_modules = Vec::new();
+        let add_edge = |graph: &mut ModuleGraph,
+                        node_modules: &[Option<&Module>],
+                        def: &CoreDef,
+                        import: (&str, &str)| {
+            let CoreDef::Export(export) = def else {
+                return;
+            };
+            let from = export.instance.as_u32() as usize;
+            let export = match &export.item {
+                ExportItem::Name(name) => Some(name.clone()),
+                ExportItem::Index(index) => node_modules[from].and_then(|module| {
+                    let module = module.env_module();
+                    module
+                        .exports
+                        .iter()
+                        .find(|(_, i)| *i == index)
+                        .map(|(name, _)| module.strings[name].to_string())
+                }),
+            };
+            graph.edges.push(ModuleGraphEdge {
+                from,
+                export,
+                to: graph.nodes.len(),
+                import: (import.0.to_string(), import.1.to_string()),
+            });
+        };
+        for initializer in component.initializers.iter() {
+            let GlobalInitializer::InstantiateModule(instantiate, _) = initializer else {
+                continue;
+            };
+            match instantiate {
+                InstantiateModule::Static(index, args) => {
+                    let module = self.static_module(*index);
+                    for ((name, field, _), def) in module.env_module().imports().zip(args) {
+                        add_edge(&mut graph, &node_modules, def, (name, field));
+                    }
+                    graph.nodes.push(ModuleGraphNode {
+                        static_module: Some(*index),
+                        name: module.name().map(|s| s.to_string()),
+                    });
+                    node_modules.push(Some(module));
+                }
+                InstantiateModule::Import(index, args) => {
+                    for (name, fields) in args {
+                        for (field, def) in fields {
+                            add_edge(&mut graph, &node_modules, def, (name, field));
+                        }
+                    }
+                    let (import, _) = component.imports[*index];
+                    graph.nodes.push(ModuleGraphNode {
+                        static_module: None,
+                        name: Some(component.import_types[import].0.clone()),
+                    });
+                    node_modules.push(None);
+                }
+            }
+        }
+        graph
+    }
+
+    /// Compares the type of this component against the type of `other`,
+    /// returning the imports and exports that were added, removed, or changed.
     ///
-    /// ```
-    /// use wasmtime::{Engine, Store};
-    /// use wasmtime::component::{Component, Linker};
-    /// use wasmtime::component::types::ComponentItem;
+    /// `self` is treated as the older version of the two components, so for
+    /// example imports present in `other` but not `self` are reported as
+    /// added. An import or export present in both is reported as changed if
+    /// its type differs between the two components. See [`TypeDiff`] for how
+    /// resource types are compared.
     ///
-    /// # fn main() -> wasmtime::Result<()> {
-    /// let engine = Engine::default();
-    /// let component = Component::new(
-    ///     &engine,
-    ///     r#"
-    ///         (component
-    ///             (core module $m
-    ///                 (func (export "f"))
-    ///             )
-    ///             (core instance $i (instantiate $m))
-    ///             (func (export "f")
-    ///                 (canon lift (core func $i "f")))
-    ///         )
-    ///     "#,
-    /// )?;
+    /// # Panics
     ///
-    /// // Perform a lookup of the function "f" before instantiaton.
-    /// let (ty, export) = component.get_export(None, "f").unwrap();
-    /// assert!(matches!(ty, ComponentItem::ComponentFunc(_)));
+    /// Panics if either component belongs to a different engine than
+    /// `engine`.
+    pub fn diff_type(&self, engine: &Engine, other: &Component) -> TypeDiff {
+        self.component_type().diff(engine, &other.component_type())
+    }
+
+    /// Returns the number of instances of this component which are currently
+    /// alive, across all stores.
     ///
-    /// // After instantiation use `export` to lookup the function in question
-    /// // which notably does not do a string lookup at runtime.
-    /// let mut store = Store::new(&engine, ());
-    /// let instance = Linker::new(&engine).instantiate(&mut store, &component)?;
-    /// let func = instance.get_typed_func::<(), ()>(&mut store, &export)?;
-    /// // ...
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn get_export(
-        &self,
-        instance: Option<&ComponentExportIndex>,
-        name: &str,
-    ) -> Option<(types::ComponentItem, ComponentExportIndex)> {
-        let info = self.env_component();
-        let index = self.lookup_export_index(instance, name)?;
-        let item = self.with_uninstantiated_instance_type(|instance| {
-            types::ComponentItem::from_export(
-                &self.inner.engine,
-                &info.export_items[index],
-                instance,
-            )
-        });
-        Some((
-            item,
-            ComponentExportIndex {
-                id: self.inner.id,
-                index,
-            },
-        ))
+    /// Instances are counted from when they're created until the store which
+    /// owns them is dropped. All clones of a [`Component`] share the same
+    /// count.
+    pub fn live_instance_count(&self) -> usize {
+        self.inner.live_instances.load(Ordering::Relaxed)
     }
 
-    pub(crate) fn lookup_export_index(
+    pub(crate) fn instance_created(&self) {
+        self.inner.live_instances.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn instance_dropped(&self) {
+        self.inner.live_instances.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Installs `resolver` to name functions of this component's core modules
+    /// which have no entry in their module's name section.
+    ///
+    /// The resolver receives the index of the core module within this
+    /// component, as in [`ModuleGraphNode::static_module`], and the index of
+    /// the function within that module. This enables stripped components to
+    /// be symbolicated with names from an external source, such as a symbol
+    /// map or DWARF sidecar file.
+    ///
+    /// The resolver is consulted when symbolicating frames of trap backtraces
+    /// and when building profiles with
+    /// [`GuestProfiler::new_component`](crate::GuestProfiler::new_component).
+    /// Names from the name section always take precedence. The resolver is
+    /// shared by all clones of this [`Component`] and replaces any previously
+    /// installed one. Backtraces of traps only use the resolver installed when
+    /// the trapping instance, or another instance of this component in the
+    /// same store, was created. By default no resolver is installed.
+    pub fn set_name_resolver(
+        &self,
+        resolver: impl Fn(StaticModuleIndex, FuncIndex) -> Option<String> + Send + Sync + 'static,
+    ) {
+        *self.inner.name_resolver.write() = Some(Arc::new(resolver));
+    }
+
+    /// Resolves the name of `func` within `module` with the resolver
+    /// installed by [`Component::set_name_resolver`], if any, and if `module`
+    /// is one of this component's core modules.
+    pub(crate) fn resolve_func_name(&self, module: &Module, func: FuncIndex) -> Option<String> {
+        let resolver = self.name_resolver()?;
+        resolver(self.static_module_index(module)?, func)
+    }
+
+    /// Returns the resolver installed by [`Component::set_name_resolver`], if
+    /// any.
+    pub(crate) fn name_resolver(&self) -> Option<Arc<NameResolver>> {
+        self.inner.name_resolver.read().clone()
+    }
+
+    /// Returns the index of `module` within this component, if it's one of
+    /// this component's core modules.
+    pub(crate) fn static_module_index(&self, module: &Module) -> Option<StaticModuleIndex> {
+        let (index, _) = self
+            .inner
+            .static_modules
+            .iter()
+            .find(|(_, m)| Module::same(m, module))?;
+        Some(index)
+    }
+
+    /// Returns whether any core module embedded within this component
+    /// contains floating-point instructions which may produce a NaN.
+    ///
+    /// This includes scalar and SIMD floating-point arithmetic, rounding,
+    /// `min`/`max`, and conversions between `f32` and `f64`. The bit patterns
+    /// of NaN values produced by these instructions differ across platforms,
+    /// so hosts which require fully deterministic execution may wish to
+    /// reject such components, or alternatively enable
+    /// [`Config::cranelift_nan_canonicalization`](crate::Config::cranelift_nan_canonicalization).
+    ///
+    /// Instructions which only move or reinterpret the bits of floating-point
+    /// values, such as loads, stores, constants, `abs`, `neg`, `copysign`,
+    /// and `f32.reinterpret_i32`, are deterministic and aren't considered to
+    /// use floating point.
+    pub fn uses_floating_point(&self) -> bool {
+        self.inner
+            .static_modules
+            .values()
+            .any(|module| module.env_module().uses_floating_point)
+    }
+
+    /// Returns a [`Config`] with only the WebAssembly proposals enabled that
+    /// this component was found to use.
+    ///
+    /// Starting from [`Config::new`], each proposal below is enabled if a core
+    /// module within this component requires it and disabled otherwise, as
+    /// determined from the types, definitions, constant expressions, function
+    /// locals, and instructions within each module: SIMD, relaxed SIMD,
+    /// threads, memory64, multi-memory, tail calls, function references, GC,
+    /// exceptions, wide arithmetic, custom page sizes, stack switching, and
+    /// shared-everything threads. Component model features are copied from
+    /// the [`Engine`] this component was compiled with, and all other
+    /// settings keep their defaults. Engines created from the returned
+    /// configuration, which a host can restrict further, expose a minimal
+    /// surface while still being able to compile this component.
+    ///
+    /// [`Config`]: crate::Config
+    /// [`Config::new`]: crate::Config::new
+    pub fn minimal_config(&self) -> crate::Config {
+        const TOGGLED: WasmFeatures = WasmFeatures::SIMD
+            .union(WasmFeatures::RELAXED_SIMD)
+            .union(WasmFeatures::THREADS)
+            .union(WasmFeatures::MEMORY64)
+            .union(WasmFeatures::MULTI_MEMORY)
+            .union(WasmFeatures::TAIL_CALL)
+            .union(WasmFeatures::FUNCTION_REFERENCES)
+            .union(WasmFeatures::GC)
+            .union(WasmFeatures::EXCEPTIONS)
+            .union(WasmFeatures::LEGACY_EXCEPTIONS)
+            .union(WasmFeatures::WIDE_ARITHMETIC)
+            .union(WasmFeatures::CUSTOM_PAGE_SIZES)
+            .union(WasmFeatures::STACK_SWITCHING)
+            .union(WasmFeatures::SHARED_EVERYTHING_THREADS);
+        const COMPONENT_MODEL: WasmFeatures = WasmFeatures::CM_ASYNC
+            .union(WasmFeatures::CM_ASYNC_BUILTINS)
+            .union(WasmFeatures::CM_ASYNC_STACKFUL)
+            .union(WasmFeatures::CM_THREADING)
+            .union(WasmFeatures::CM_ERROR_CONTEXT)
+            .union(WasmFeatures::CM_GC)
+            .union(WasmFeatures::CM_FIXED_LENGTH_LISTS);
+
+        let module_types = self.types().module_types();
+        let mut required = WasmFeatures::empty();
+        for (_, ty) in module_types.wasm_types() {
+            required |= ty.required_features();
+        }
+        if module_types
+            .rec_groups()
+            .any(|(_, range)| range.end.as_u32() - range.start.as_u32() > 1)
+        {
+            required |= WasmFeatures::GC;
+        }
+
+        for module in self.inner.static_modules.values() {
+            let module = module.env_module();
+            required |= WasmFeatures::from_bits_truncate(module.function_features);
+            if module.memories.len() > 1 {
+                required |= WasmFeatures::MULTI_MEMORY;
+            }
+            for memory in module.memories.values() {
+                if memory.idx_type == IndexType::I64 {
+                    required |= WasmFeatures::MEMORY64;
+                }
+                if memory.shared {
+                    required |= WasmFeatures::THREADS;
+                }
+                if memory.page_size_log2 != 16 {
+                    required |= WasmFeatures::CUSTOM_PAGE_SIZES;
+                }
+            }
+            for table in module.tables.values() {
+                if table.idx_type == IndexType::I64 {
+                    required |= WasmFeatures::MEMORY64;
+                }
+                required |= table.ref_type.required_features();
+            }
+            for global in module.globals.values() {
+                required |= global.wasm_ty.required_features();
+            }
+            for init in module.global_initializers.values() {
+                for op in init.ops() {
+                    required |= op.required_features();
+                }
+            }
+            if !module.tags.is_empty() {
+                required |= WasmFeatures::EXCEPTIONS;
+            }
+        }
+        if self.imported_shared_memories().next().is_some() {
+            required |= WasmFeatures::THREADS;
+        }
+
+        // Account for the proposals that the required ones build upon.
+        if required.contains(WasmFeatures::GC) {
+            required |= WasmFeatures::FUNCTION_REFERENCES;
+        }
+        if required.contains(WasmFeatures::FUNCTION_REFERENCES) {
+            required |= WasmFeatures::REFERENCE_TYPES;
+        }
+        if required.contains(WasmFeatures::RELAXED_SIMD) {
+            required |= WasmFeatures::SIMD;
+        }
+        required |= self.engine().features() & COMPONENT_MODEL;
+
+        let mut config = crate::Config::new();
+        config
+            .wasm_features(required, true)
+            .wasm_features(TOGGLED.difference(required), false);
+        config
+    }
+
+    /// Checks that executing this component is deterministic, returning an
+    /// error describing the first source of nondeterminism found otherwise.
+    ///
+    /// This is intended as a single gate for hosts which require reproducible
+    /// execution. A component is rejected if any core module within it:
+    ///
+    /// * contains floating-point instructions, as described in
+    ///   [`Component::uses_floating_point`], but the component was compiled
+    ///   without [`Config::cranelift_nan_canonicalization`](crate::Config::cranelift_nan_canonicalization);
+    /// * defines or imports a shared memory, which can be accessed
+    ///   concurrently by multiple threads;
+    /// * contains SIMD instructions where they're disallowed, which is the
+    ///   case for relaxed SIMD instructions unless the component was compiled
+    ///   with [`Config::relaxed_simd_deterministic`](crate::Config::relaxed_simd_deterministic).
+    ///
+    /// Other SIMD instructions are deterministic and therefore allowed, with
+    /// floating-point SIMD instructions being subject to the NaN
+    /// canonicalization requirement above. Shared memories listed by
+    /// [`Component::imported_shared_memories`] are rejected as well.
+    ///
+    /// Both compilation settings are read from the compiled artifact of this
+    /// component.
+    pub fn validate_determinism(&self) -> Result<()> {
+        let image = self.engine_code().image();
+        let nan_canonicalization = self
+            .engine()
+            .artifact_shared_flag(image, ObjectKind::Component, "enable_nan_canonicalization")?
+            .is_some_and(|value| value == wasmtime_environ::FlagValue::Bool(true));
+        let relaxed_simd_deterministic = self
+            .engine()
+            .artifact_tunables(image, ObjectKind::Component)?
+            .relaxed_simd_deterministic;
+
+        for (i, module) in self.inner.static_modules.values().enumerate() {
+            let module = module.env_module();
+            if module.uses_floating_point && !nan_canonicalization {
+                bail!(
+                    "core module {i} uses floating-point instructions but NaN \
+                     canonicalization is not enabled"
+                );
+            }
+            if module.memories.values().any(|memory| memory.shared) {
+                bail!("core module {i} uses a shared memory");
+            }
+            if WasmFeatures::from_bits_truncate(module.function_features)
+                .contains(WasmFeatures::RELAXED_SIMD)
+                && !relaxed_simd_deterministic
+            {
+                bail!(
+                    "core module {i} uses relaxed SIMD instructions, which are \
+                     disallowed unless deterministic relaxed SIMD is enabled"
+                );
+            }
+        }
+        if let Some((name, spec)) = self.imported_shared_memories().next() {
+            bail!(
+                "imported core module `{name}` imports shared memory `{}::{}`",
+                spec.module,
+                spec.name
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks that no function compiled from a core module within this
+    /// component has more than `max_bytes` of machine code, returning an
+    /// error naming the first function which does otherwise.
+    ///
+    /// Exceptionally large functions can be a sign of adversarial input or of
+    /// a miscompilation, and are detrimental to instruction cache behavior.
+    /// Only functions defined in core wasm are checked, not the trampolines
+    /// generated for the canonical ABI.
+    pub fn validate_max_function_size(&self, max_bytes: u32) -> Result<()> {
+        for (i, module) in self.inner.static_modules.values().enumerate() {
+            let compiled = module.compiled_module();
+            for defined in module.env_module().defined_func_indices() {
+                let length = compiled.func_loc(defined).length;
+                if length > max_bytes {
+                    let index = module.env_module().func_index(defined);
+                    let name = match compiled.func_name(index) {
+                        Some(name) => format!(" (`{name}`)"),
+                        None => String::new(),
+                    };
+                    bail!(
+                        "function {} of core module {i}{name} has {length} bytes of \
+                         machine code, exceeding the limit of {max_bytes}",
+                        index.as_u32()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that this component stays within a host's budget for resource
+    /// types and handle tables, returning an error naming the first limit
+    /// which is exceeded otherwise.
+    ///
+    /// * `max_types` bounds the number of resource types, both imported and
+    ///   defined, within this component.
+    /// * `max_handles` bounds the number of handle tables that each instance
+    ///   of this component requires: one per nested component instance when
+    ///   resources are used, plus one for each table of futures, streams, and
+    ///   error contexts.
+    ///
+    /// This is a static check of the component's metadata intended for
+    /// admission control. The number of handles stored in each table at
+    /// runtime depends on what the component does and is not bounded by
+    /// this.
+    pub fn validate_resource_counts(&self, max_types: usize, max_handles: u32) -> Result<()> {
+        let component = self.env_component();
+        let types = usize::try_from(component.num_resources).unwrap();
+        if types > max_types {
+            bail!(
+                "component declares {types} resource type(s), exceeding the \
+                 limit of {max_types}"
+            );
+        }
+
+        let resource_tables = if component.num_resources > 0 {
+            component.num_runtime_component_instances
+        } else {
+            0
+        };
+        let tables = u64::from(resource_tables)
+            + u64::try_from(component.num_future_tables).unwrap()
+            + u64::try_from(component.num_stream_tables).unwrap()
+            + u64::try_from(component.num_error_context_tables).unwrap();
+        if tables > u64::from(max_handles) {
+            bail!(
+                "component requires {tables} handle table(s), exceeding the \
+                 limit of {max_handles}"
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the compiler backend which produced the code for this
+    /// component.
+    ///
+    /// This is either [`Strategy::Cranelift`] or [`Strategy::Winch`], never
+    /// [`Strategy::Auto`], and is read from the artifact's metadata as the
+    /// [`BuildInfo::compiler`] of [`Component::build_info`]. For components
+    /// created with [`Component::deserialize`] this therefore reflects the
+    /// backend of the original compilation as well.
+    pub fn compiled_with(&self) -> Strategy {
+        self.build_info().compiler
+    }
+
+    /// Returns how the compiled artifact of this component was produced, as
+    /// recorded in its metadata at compile time.
+    ///
+    /// This can be used to audit which toolchain compiled an artifact, for
+    /// example one loaded with [`Component::deserialize`], without keeping
+    /// separate records.
+    pub fn build_info(&self) -> BuildInfo {
+        let (version, target, winch) = self
+            .engine()
+            .artifact_build_info(self.engine_code().image(), ObjectKind::Component)
+            .expect("component artifacts are validated when loaded");
+        BuildInfo {
+            version: version.to_string(),
+            target,
+            compiler: if winch {
+                Strategy::Winch
+            } else {
+                Strategy::Cranelift
+            },
+        }
+    }
+
+    /// Checks whether the compiled artifact of this component could be loaded
+    /// into `engine`.
+    ///
+    /// This performs the same checks as [`Component::deserialize`] does
+    /// against the settings this component was compiled with, such as the
+    /// target, compiler flags, and enabled WebAssembly features, without
+    /// having to serialize this component first. Unlike deserialization
+    /// however all incompatible settings are reported together so they can be
+    /// fixed at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing each setting in `engine` that's
+    /// incompatible with this component.
+    pub fn assert_compatible_with(&self, engine: &Engine) -> Result<()> {
+        engine.check_artifact_compatible(self.engine_code().image(), ObjectKind::Component)
+    }
+
+    /// Validates that this component can be instantiated with the instance
+    /// allocator configured within `engine`.
+    ///
+    /// This same validation is performed automatically when a component is
+    /// created, but only against the allocator of the engine the component is
+    /// created within. This method can be used to check a component against a
+    /// different engine's allocator, for example to determine whether a
+    /// component that works with the on-demand allocator will also fit within
+    /// the limits of a [pooling
+    /// allocator](crate::PoolingAllocationConfig).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing which limit of the allocator was exceeded
+    /// if this component cannot be instantiated with `engine`'s allocator.
+    pub fn validate_for_allocator(&self, engine: &Engine) -> Result<()> {
+        let component = self.env_component();
+        engine.allocator().validate_component(
+            component,
+            &VMComponentOffsets::new(HostPtr, component),
+            &|module_index| self.static_module(module_index).env_module(),
+        )
+    }
+
+    /// Returns the canonical ABI options this component uses when lowering
+    /// the imported function `name`.
+    ///
+    /// The `name` is the name of a top-level function import. Functions
+    /// imported through an instance are named by the instance's import name
+    /// followed by `#` and the function's name, for example
+    /// `wasi:cli/stdout@0.2.0#get-stdout`.
+    ///
+    /// This can be used by hosts with hand-written glue to confirm that the
+    /// component's choices of string encoding, memory, and so on match their
+    /// expectations. If the same import is lowered multiple times then the
+    /// options of the first lowering are returned.
+    ///
+    /// Returns `None` if `name` isn't an imported function or if the
+    /// component never lowers it.
+    pub fn import_canonical_options(&self, name: &str) -> Option<CanonicalOptions> {
+        let component = self.env_component();
+        component.initializers.iter().find_map(|init| {
+            let GlobalInitializer::LowerImport {
+                import, options, ..
+            } = init
+            else {
+                return None;
+            };
+            let (index, path) = &component.imports[*import];
+            let mut full_name = component.import_types[*index].0.clone();
+            for segment in path {
+                full_name.push('#');
+                full_name.push_str(segment);
+            }
+            if full_name != name {
+                return None;
+            }
+            Some(CanonicalOptions::new(&component.options[*options]))
+        })
+    }
+
+    /// Returns a SHA-256 digest of the canonical ABI conventions this
+    /// component uses at its boundary with the host.
+    ///
+    /// The digest covers the distinct [`CanonicalOptions`] of the imports
+    /// lowered by this component's initialization program and of the
+    /// functions it exports, such as their string encoding and whether a
+    /// memory, `realloc`, or `post-return` is configured. Two components which
+    /// use the same set of conventions for lowering and for lifting have
+    /// equal digests regardless of their interfaces, so hosts with
+    /// hand-written lifting and lowering glue can key caches of it on this.
+    pub fn abi_digest(&self) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+
+        fn encode(role: u8, options: CanonicalOptions) -> [u8; 6] {
+            let string_encoding = match options.string_encoding {
+                StringEncoding::Utf8 => 0,
+                StringEncoding::Utf16 => 1,
+                StringEncoding::CompactUtf16 => 2,
+            };
+            [
+                role,
+                string_encoding,
+                u8::from(options.memory),
+                u8::from(options.realloc),
+                u8::from(options.post_return),
+                u8::from(options.async_),
+            ]
+        }
+
+        let component = self.env_component();
+        let mut conventions = alloc::collections::BTreeSet::new();
+        for init in component.initializers.iter() {
+            if let GlobalInitializer::LowerImport { options, .. } = init {
+                let options = CanonicalOptions::new(&component.options[*options]);
+                conventions.insert(encode(0, options));
+            }
+        }
+        for export in component.export_items.values() {
+            if let Export::LiftedFunction { options, .. } = export {
+                let options = CanonicalOptions::new(&component.options[*options]);
+                conventions.insert(encode(1, options));
+            }
+        }
+
+        let mut hasher = Sha256::new();
+        for convention in conventions {
+            hasher.update(convention);
+        }
+        hasher.finalize().into()
+    }
+
+    /// Returns the `post-return` functions which this component uses.
+    ///
+    /// These are read from the component's initializers which extract each
+    /// post-return function, and each is identified by its
+    /// `RuntimePostReturnIndex` along with the [`CoreDef`] it's extracted
+    /// from. That's typically the export of a core instance, but may also be
+    /// a trampoline, for example when a lowered import is used as a
+    /// post-return function.
+    ///
+    /// Post-return functions are automatically invoked by Wasmtime after the
+    /// results of a call have been lifted, so this is primarily useful for
+    /// tooling which wants to audit how a component manages the memory of its
+    /// return values.
+    pub fn post_return_funcs(&self) -> impl Iterator<Item = &ExtractPostReturn> + '_ {
+        self.env_component()
+            .initializers
+            .iter()
+            .filter_map(|init| match init {
+                GlobalInitializer::ExtractPostReturn(post_return) => Some(post_return),
+                _ => None,
+            })
+    }
+
+    /// Returns the core wasm signature of each function exported by this
+    /// component, as seen at the canonical ABI boundary.
+    ///
+    /// Each signature is the lifted function's component-level parameters and
+    /// results flattened to core wasm values, as computed during compilation.
+    /// When more values would be needed than the canonical ABI's flattening
+    /// limits allow they're instead passed indirectly through linear memory,
+    /// which is reflected here as well. This is useful for bridges which call
+    /// the core wasm functions behind exports directly.
+    ///
+    /// Functions exported through an instance are named by the instance's
+    /// export name followed by `#` and the function's name, in the same manner
+    /// as [`Component::import_canonical_options`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `engine` is not the engine this component was created with.
+    pub fn lowered_export_signatures(&self, engine: &Engine) -> Vec<(String, FuncType)> {
+        fn visit(
+            component: &Component,
+            engine: &Engine,
+            prefix: Option<&str>,
+            exports: &NameMap<String, ExportIndex>,
+            signatures: &mut Vec<(String, FuncType)>,
+        ) {
+            for (name, index) in exports.raw_iter() {
+                let name = match prefix {
+                    Some(prefix) => format!("{prefix}#{name}"),
+                    None => name.clone(),
+                };
+                match &component.env_component().export_items[*index] {
+                    Export::LiftedFunction { options, .. } => {
+                        let core_type = component.env_component().options[*options].core_type;
+                        let index = component.signatures().shared_type(core_type).unwrap();
+                        signatures.push((name, FuncType::from_shared_type_index(engine, index)));
+                    }
+                    Export::Instance { exports, .. } => {
+                        visit(component, engine, Some(&name), exports, signatures);
+                    }
+                    Export::ModuleStatic { .. } | Export::ModuleImport { .. } | Export::Type(_) => {
+                    }
+                }
+            }
+        }
+
+        assert!(
+            Engine::same(engine, self.engine()),
+            "cannot use a component with a different `Engine`"
+        );
+        let mut signatures = Vec::new();
+        visit(
+            self,
+            engine,
+            None,
+            &self.env_component().exports,
+            &mut signatures,
+        );
+        signatures
+    }
+
+    /// Returns counts of the canonical ABI operations performed by this
+    /// component.
+    ///
+    /// Lowered imports and the extraction of `realloc` and `post-return`
+    /// functions are counted from the initializers run when this component is
+    /// instantiated, while lifts are counted from the functions it exports.
+    /// This provides a quick sense of how much of this component's
+    /// interaction with its host goes through the canonical ABI.
+    pub fn abi_summary(&self) -> AbiSummary {
+        let component = self.env_component();
+        let mut summary = AbiSummary::default();
+        for export in component.export_items.values() {
+            if let Export::LiftedFunction { .. } = export {
+                summary.lifts += 1;
+            }
+        }
+        for initializer in component.initializers.iter() {
+            match initializer {
+                GlobalInitializer::LowerImport { .. } => summary.lowers += 1,
+                GlobalInitializer::ExtractRealloc(_) => summary.reallocs += 1,
+                GlobalInitializer::ExtractPostReturn(_) => summary.post_returns += 1,
+                GlobalInitializer::InstantiateModule(..)
+                | GlobalInitializer::ExtractMemory(_)
+                | GlobalInitializer::ExtractCallback(_)
+                | GlobalInitializer::ExtractTable(_)
+                | GlobalInitializer::Resource(_) => {}
+            }
+        }
+        summary
+    }
+
+    /// Performs a trial instantiation of this component in a throwaway store
+    /// to confirm that instantiation would structurally succeed.
+    ///
+    /// This goes further than static validation by running the full
+    /// initialization program of the component: all instance allocation,
+    /// resource limits, and type checks are performed. Side effects are
+    /// avoided, however, as all imports of the component are satisfied with
+    /// stubs which trap if they're called, and the `start` functions of core
+    /// wasm modules are not run. The instance is discarded once created, and
+    /// like that of [`Component::warmup`] it's neither reported to the
+    /// [`component_instantiate_observer`](crate::Config::component_instantiate_observer)
+    /// nor counted by [`Component::live_instance_count`].
+    ///
+    /// This can be useful for admission control, to reject components which
+    /// could never be instantiated before handing them to the real host.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this component imports core modules or components
+    /// which can't be stubbed out, or if instantiation would fail.
+    pub fn dry_run(&self) -> Result<()> {
+        let engine = self.engine();
+        let mut linker = Linker::<()>::new(engine);
+        linker.define_unknown_imports_as_traps(self)?;
+        let pre = linker.instantiate_pre(self)?;
+        let mut store = Store::new(engine, ());
+        pre.instantiate_throwaway(&mut store, Instantiation::ThrowawayWithoutStart)
+    }
+
+    /// Instantiates this component once in a throwaway store to prime caches
+    /// ahead of the first real instantiation.
+    ///
+    /// This faults in the compiled code of this component, including any
+    /// `start` functions of its core modules, and populates the engine's
+    /// type registry with the types it uses. Latency-sensitive hosts can call
+    /// this during startup so the first instantiation on a request path
+    /// doesn't pay these costs. The instance is discarded once created, and
+    /// it's neither reported to the
+    /// [`component_instantiate_observer`](crate::Config::component_instantiate_observer)
+    /// nor counted by [`Component::live_instance_count`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this component requires imports to instantiate,
+    /// as counted by [`Component::total_imports`], or if instantiation fails.
+    pub fn warmup(&self) -> Result<()> {
+        let imports = self.total_imports();
+        ensure!(
+            imports == 0,
+            "cannot warm up a component which requires {imports} import(s) to instantiate"
+        );
+        let engine = self.engine();
+        let pre = Linker::<()>::new(engine).instantiate_pre(self)?;
+        let mut store = Store::new(engine, ());
+        pre.instantiate_throwaway(&mut store, Instantiation::Throwaway)
+    }
+
+    /// Returns the amount of host memory retained by this component.
+    ///
+    /// This is intended to be cheap enough to call for every entry of a cache
+    /// of components, for example to drive eviction, and doesn't allocate or
+    /// take any locks. See [`ComponentMemoryUsage`] for what's measured.
+    pub fn memory_usage(&self) -> ComponentMemoryUsage {
+        let static_modules_text_size = self
+            .inner
+            .static_modules
+            .values()
+            .map(|module| {
+                let compiled = module.compiled_module();
+                module
+                    .env_module()
+                    .defined_func_indices()
+                    .map(|i| compiled.func_loc(i).length as usize)
+                    .sum::<usize>()
+            })
+            .sum();
+        ComponentMemoryUsage {
+            image_size: self.inner.code.image().len(),
+            text_size: self.inner.code.text().len(),
+            types_size: self.types().estimated_size(),
+            num_static_modules: self.inner.static_modules.len(),
+            static_modules_text_size,
+        }
+    }
+
+    /// Returns the range, in the host's address space, that this module's
+    /// compiled code resides at.
+    ///
+    /// For more information see
+    /// [`Module::image_range`](crate::Module::image_range).
+    pub fn image_range(&self) -> Range<*const u8> {
+        self.inner.code.image().as_ptr_range()
+    }
+
+    /// Returns the ranges, in the host's address space, of the compiled code
+    /// of each core wasm module within this component.
+    ///
+    /// The modules of a component share its image, so the
+    /// [`Module::image_range`] of each of them is the same as
+    /// [`Component::image_range`]. This instead returns the sub-range of the
+    /// image's text spanned by each module's functions, for example to
+    /// register each module separately with a profiler. The returned ranges
+    /// don't overlap one another and are each contained within
+    /// [`Component::image_range`].
+    ///
+    /// Modules which don't define any functions have no code and are omitted.
+    pub fn module_image_ranges(&self) -> Vec<(StaticModuleIndex, Range<*const u8>)> {
+        let text = self.inner.code.text();
+        self.inner
+            .static_modules
+            .iter()
+            .filter_map(|(index, module)| {
+                let (start, end) = module
+                    .compiled_module()
+                    .finished_function_ranges()
+                    .map(|(_, range)| (range.start, range.end))
+                    .reduce(|(a, b), (c, d)| (a.min(c), b.max(d)))?;
+                Some((index, text[start..end].as_ptr_range()))
+            })
+            .collect()
+    }
+
+    /// Returns the names and contents of the custom sections of the core wasm
+    /// module `idx` within this component, in their original order.
+    ///
+    /// Custom sections are only retained in compiled artifacts if
+    /// [`Config::retain_custom_sections`] was enabled when this component was
+    /// compiled, otherwise this is always empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` isn't a valid module index for this component.
+    ///
+    /// [`Config::retain_custom_sections`]: crate::Config::retain_custom_sections
+    pub fn module_custom_sections(
+        &self,
+        idx: StaticModuleIndex,
+    ) -> impl Iterator<Item = (&str, &[u8])> + '_ {
+        self.static_module(idx).compiled_module().custom_sections()
+    }
+
+    /// Checks that no page spanned by [`Component::image_range`] is writable,
+    /// returning an error describing why pages may be writable otherwise.
+    ///
+    /// When a component is created or deserialized its image is made
+    /// read-only, and then the compiled code within it read/execute, so that
+    /// memory is never both writable and executable. This verifies that those
+    /// protections were applied, which isn't possible for example on
+    /// platforms without virtual memory. Images in memory given to
+    /// [`Component::deserialize_raw`] are never modified and pass this check.
+    ///
+    /// With an interpreter such as Pulley, compiled code is never executed
+    /// natively so it remains read-only rather than read/execute.
+    pub fn assert_wx(&self) -> Result<()> {
+        match self.inner.code.writable_reason() {
+            Some(reason) => bail!("component code may be writable: {reason}"),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns how many of the host pages spanned by
+    /// [`Component::image_range`] are currently resident in memory.
+    ///
+    /// Pages of the compiled image which have never been accessed, or which
+    /// have been paged out by the operating system, are not resident. This
+    /// can be used to observe how warm a component's code is, for example to
+    /// tune prefaulting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if querying page residency isn't supported on this
+    /// platform, which is currently the case everywhere but Unix.
+    pub fn resident_code_pages(&self) -> Result<usize> {
+        #[cfg(has_virtual_memory)]
+        return crate::runtime::vm::resident_pages(self.image_range());
+        #[cfg(not(has_virtual_memory))]
+        bail!("querying page residency requires virtual memory support");
+    }
+
+    /// Returns a [`FrozenComponent`] handle which guarantees that the compiled
+    /// image of this component is immutable.
+    ///
+    /// The image of a component is position-independent and, once frozen, can
+    /// be copied into memory shared between processes. Other processes can
+    /// then create a [`Component`] directly from the shared memory, without
+    /// copying it, through [`Component::deserialize_raw`] with the bytes
+    /// returned by [`FrozenComponent::image`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image of this component is not published as
+    /// read-only memory, for example on platforms without virtual memory
+    /// support where the image lives in a plain heap allocation.
+    ///
+    /// # Cross-process safety
+    ///
+    /// Placing the image in shared memory is only safe if the contract of
+    /// [`Component::deserialize_raw`] is upheld in every process which uses
+    /// it: the shared region must be mapped for as long as any component
+    /// created from it is alive and must never be modified in the meantime.
+    /// Additionally each process must use an [`Engine`] configured compatibly
+    /// with the one that compiled the component, as is already required by
+    /// [`Component::deserialize`].
+    pub fn freeze(&self) -> Result<FrozenComponent> {
+        ensure!(
+            self.inner.code.is_frozen(),
+            "component image is not backed by read-only memory and cannot be frozen"
+        );
+        Ok(FrozenComponent {
+            component: self.clone(),
+        })
+    }
+
+    /// Force initialization of copy-on-write images to happen here-and-now
+    /// instead of when they're requested during first instantiation.
+    ///
+    /// When [copy-on-write memory
+    /// initialization](crate::Config::memory_init_cow) is enabled then Wasmtime
+    /// will lazily create the initialization image for a component. This method
+    /// can be used to explicitly dictate when this initialization happens.
+    ///
+    /// Note that this largely only matters on Linux when memfd is used.
+    /// Otherwise the copy-on-write image typically comes from disk and in that
+    /// situation the creation of the image is trivial as the image is always
+    /// sourced from disk. On Linux, though, when memfd is used a memfd is
+    /// created and the initialization image is written to it.
+    ///
+    /// Also note that this method is not required to be called, it's available
+    /// as a performance optimization if required but is otherwise handled
+    /// automatically.
+    pub fn initialize_copy_on_write_image(&self) -> Result<()> {
+        for (_, module) in self.inner.static_modules.iter() {
+            module.initialize_copy_on_write_image()?;
+        }
+        Ok(())
+    }
+
+    /// Looks up a specific export of this component by `name` optionally nested
+    /// within the `instance` provided.
+    ///
+    /// See related method [`Self::get_export`] for additional docs and
+    /// examples.
+    ///
+    /// This method is primarily used to acquire a [`ComponentExportIndex`]
+    /// which can be used with [`Instance`](crate::component::Instance) when
+    /// looking up exports. Export lookup with [`ComponentExportIndex`] can
+    /// skip string lookups at runtime and instead use a more efficient
+    /// index-based lookup.
+    ///
+    /// This method only returns the [`ComponentExportIndex`]. If you need the
+    /// corresponding [`types::ComponentItem`], use the related function
+    /// [`Self::get_export`].
+    ///
+    ///
+    /// [`Instance`](crate::component::Instance) has a corresponding method
+    /// [`Instance::get_export_index`](crate::component::Instance::get_export_index).
+    pub fn get_export_index(
+        &self,
+        instance: Option<&ComponentExportIndex>,
+        name: &str,
+    ) -> Option<ComponentExportIndex> {
+        let index = self.lookup_export_index(instance, name)?;
+        Some(ComponentExportIndex {
+            id: self.inner.id,
+            index,
+        })
+    }
+
+    /// Looks up a specific export of this component by `name` optionally nested
+    /// within the `instance` provided.
+    ///
+    /// This method is primarily used to acquire a [`ComponentExportIndex`]
+    /// which can be used with [`Instance`](crate::component::Instance) when
+    /// looking up exports. Export lookup with [`ComponentExportIndex`] can
+    /// skip string lookups at runtime and instead use a more efficient
+    /// index-based lookup.
+    ///
+    /// This method takes a few arguments:
+    ///
+    /// * `engine` - the engine that was used to compile this component.
+    /// * `instance` - an optional "parent instance" for the export being looked
+    ///   up. If this is `None` then the export is looked up on the root of the
+    ///   component itself, and otherwise the export is looked up on the
+    ///   `instance` specified. Note that `instance` must have come from a
+    ///   previous invocation of this method.
+    /// * `name` - the name of the export that's being looked up.
+    ///
+    /// If the export is located then two values are returned: a
+    /// [`types::ComponentItem`] which enables introspection about the type of
+    /// the export and a [`ComponentExportIndex`]. The index returned notably
+    /// implements the [`InstanceExportLookup`] trait which enables using it
+    /// with [`Instance::get_func`](crate::component::Instance::get_func) for
+    /// example.
+    ///
+    /// The returned [`types::ComponentItem`] is more expensive to calculate
+    /// than the [`ComponentExportIndex`]. If you only consume the
+    /// [`ComponentExportIndex`], use the related method
+    /// [`Self::get_export_index`] instead.
+    ///
+    /// [`Instance`](crate::component::Instance) has a corresponding method
+    /// [`Instance::get_export`](crate::component::Instance::get_export).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wasmtime::{Engine, Store};
+    /// use wasmtime::component::{Component, Linker};
+    /// use wasmtime::component::types::ComponentItem;
+    ///
+    /// # fn main() -> wasmtime::Result<()> {
+    /// let engine = Engine::default();
+    /// let component = Component::new(
+    ///     &engine,
+    ///     r#"
+    ///         (component
+    ///             (core module $m
+    ///                 (func (export "f"))
+    ///             )
+    ///             (core instance $i (instantiate $m))
+    ///             (func (export "f")
+    ///                 (canon lift (core func $i "f")))
+    ///         )
+    ///     "#,
+    /// )?;
+    ///
+    /// // Perform a lookup of the function "f" before instantiaton.
+    /// let (ty, export) = component.get_export(None, "f").unwrap();
+    /// assert!(matches!(ty, ComponentItem::ComponentFunc(_)));
+    ///
+    /// // After instantiation use `export` to lookup the function in question
+    /// // which notably does not do a string lookup at runtime.
+    /// let mut store = Store::new(&engine, ());
+    /// let instance = Linker::new(&engine).instantiate(&mut store, &component)?;
+    /// let func = instance.get_typed_func::<(), ()>(&mut store, &export)?;
+    /// // ...
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_export(
+        &self,
+        instance: Option<&ComponentExportIndex>,
+        name: &str,
+    ) -> Option<(types::ComponentItem, ComponentExportIndex)> {
+        let info = self.env_component();
+        let index = self.lookup_export_index(instance, name)?;
+        let item = self.with_uninstantiated_instance_type(|instance| {
+            types::ComponentItem::from_export(
+                &self.inner.engine,
+                &info.export_items[index],
+                instance,
+            )
+        });
+        Some((
+            item,
+            ComponentExportIndex {
+                id: self.inner.id,
+                index,
+            },
+        ))
+    }
+
+    pub(crate) fn lookup_export_index(
         &self,
         instance: Option<&ComponentExportIndex>,
         name: &str,
@@ -854,47 +3226,654 @@ impl Component {
                     _ => return None,
                 }
             }
-            None => &info.exports,
-        };
-        exports.get(name, &NameMapNoIntern).copied()
+            None => &info.exports,
+        };
+        exports.get(name, &NameMapNoIntern).copied()
+    }
+
+    pub(crate) fn id(&self) -> CompiledModuleId {
+        self.inner.id
+    }
+
+    /// Returns the [`Engine`] that this [`Component`] was compiled by.
+    pub fn engine(&self) -> &Engine {
+        &self.inner.engine
+    }
+
+    /// Releases excess capacity held by the metadata of this component.
+    ///
+    /// Some of the tables describing a component may over-allocate while it's
+    /// being compiled or deserialized. This is a best-effort operation: as
+    /// the metadata of a component is shared by all of its clones, excess
+    /// capacity can only be released when no other clones of this
+    /// [`Component`] exist, and otherwise this method does nothing.
+    ///
+    /// This has no effect on the behavior of the component.
+    pub fn shrink_to_fit(&mut self) {
+        let Some(inner) = Arc::get_mut(&mut self.inner) else {
+            return;
+        };
+        inner.static_modules.shrink_to_fit();
+        let component = &mut inner.info.component;
+        component.import_types.shrink_to_fit();
+        component.imports.shrink_to_fit();
+        component.export_items.shrink_to_fit();
+        component.initializers.shrink_to_fit();
+        component.trampolines.shrink_to_fit();
+        component.imported_resources.shrink_to_fit();
+    }
+
+    pub(crate) fn realloc_func_ty(&self) -> &Arc<FuncType> {
+        &self.inner.realloc_func_type
+    }
+
+    #[allow(
+        unused,
+        reason = "used only for verification with wasmtime `rr` feature \
+        and requires a lot of unnecessary gating across crates"
+    )]
+    pub(crate) fn checksum(&self) -> &WasmChecksum {
+        &self.inner.checksum
+    }
+
+    /// Returns the `Export::LiftedFunction` metadata associated with `export`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `export` is out of bounds or if it isn't a `LiftedFunction`.
+    pub(crate) fn export_lifted_function(
+        &self,
+        export: ExportIndex,
+    ) -> (TypeFuncIndex, &CoreDef, OptionsIndex) {
+        let component = self.env_component();
+        match &component.export_items[export] {
+            Export::LiftedFunction { ty, func, options } => (*ty, func, *options),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// The canonical ABI options that a component uses when lifting or lowering
+/// a function.
+///
+/// This is the return value of [`Component::import_canonical_options`].
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct CanonicalOptions {
+    /// The encoding used for strings passed across the boundary.
+    pub string_encoding: StringEncoding,
+    /// Whether a linear memory was specified with `(memory ...)`.
+    pub memory: bool,
+    /// Whether a `realloc` function was specified with `(realloc ...)`.
+    pub realloc: bool,
+    /// Whether a `post-return` function was specified with
+    /// `(post-return ...)`.
+    pub post_return: bool,
+    /// Whether the async ABI is used.
+    pub async_: bool,
+}
+
+impl CanonicalOptions {
+    fn new(options: &wasmtime_environ::component::CanonicalOptions) -> CanonicalOptions {
+        let (memory, realloc) = match options.data_model {
+            CanonicalOptionsDataModel::Gc {} => (false, false),
+            CanonicalOptionsDataModel::LinearMemory(opts) => {
+                (opts.memory.is_some(), opts.realloc.is_some())
+            }
+        };
+        CanonicalOptions {
+            string_encoding: match options.string_encoding {
+                wasmtime_environ::component::StringEncoding::Utf8 => StringEncoding::Utf8,
+                wasmtime_environ::component::StringEncoding::Utf16 => StringEncoding::Utf16,
+                wasmtime_environ::component::StringEncoding::CompactUtf16 => {
+                    StringEncoding::CompactUtf16
+                }
+            },
+            memory,
+            realloc,
+            post_return: options.post_return.is_some(),
+            async_: options.async_,
+        }
+    }
+}
+
+/// Possible encodings of strings within the component model.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum StringEncoding {
+    /// `string-encoding=utf8`, the default.
+    Utf8,
+    /// `string-encoding=utf16`.
+    Utf16,
+    /// `string-encoding=latin1+utf16`.
+    CompactUtf16,
+}
+
+/// A [`Component`] whose compiled image is guaranteed to be immutable.
+///
+/// This is created with [`Component::freeze`], see its documentation for more
+/// information.
+#[derive(Clone)]
+pub struct FrozenComponent {
+    component: Component,
+}
+
+impl FrozenComponent {
+    /// Returns the component that was frozen.
+    pub fn component(&self) -> &Component {
+        &self.component
+    }
+
+    /// Returns the compiled image of this component.
+    ///
+    /// These bytes may be copied into shared memory and then loaded by other
+    /// processes with [`Component::deserialize_raw`].
+    pub fn image(&self) -> &[u8] {
+        self.component.engine_code().image()
+    }
+}
+
+/// The format of the blob produced by [`Component::extract_symbols`], with the
+/// names of each core module's functions keyed by function index.
+#[derive(Serialize, Deserialize)]
+struct ComponentSymbols {
+    modules: Vec<Vec<(u32, String)>>,
+}
+
+/// The differences between the types of two components, as returned by
+/// [`Component::diff_type`] and [`types::Component::diff`].
+///
+/// Each list contains the names of top-level imports or exports. Items whose
+/// type differs between the two components are listed as changed, where
+/// types defined in separately compiled components are compared
+/// structurally. Resource types are compared by where they appear: since a
+/// resource defined by one component is never the same type as a resource
+/// defined by another, resources with the same name are considered
+/// unchanged, and are then treated as the same type when comparing the
+/// function signatures and other types which mention them.
+///
+/// This can be serialized, for example to record the result of a
+/// compatibility check.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeDiff {
+    /// Imports only present in the newer component.
+    pub added_imports: Vec<String>,
+    /// Imports only present in the older component.
+    pub removed_imports: Vec<String>,
+    /// Imports present in both components with different types.
+    pub changed_imports: Vec<String>,
+    /// Exports only present in the newer component.
+    pub added_exports: Vec<String>,
+    /// Exports only present in the older component.
+    pub removed_exports: Vec<String>,
+    /// Exports present in both components with different types.
+    pub changed_exports: Vec<String>,
+}
+
+impl TypeDiff {
+    /// Returns whether the two components had the same type.
+    pub fn is_empty(&self) -> bool {
+        self.added_imports.is_empty()
+            && self.removed_imports.is_empty()
+            && self.changed_imports.is_empty()
+            && self.added_exports.is_empty()
+            && self.removed_exports.is_empty()
+            && self.changed_exports.is_empty()
+    }
+}
+
+/// Counts of the canonical ABI operations within a [`Component`], as returned
+/// by [`Component::abi_summary`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AbiSummary {
+    /// The number of exported functions lifted from core wasm functions.
+    pub lifts: usize,
+    /// The number of imported functions lowered into core wasm functions.
+    pub lowers: usize,
+    /// The number of `realloc` functions extracted from core instances.
+    pub reallocs: usize,
+    /// The number of `post-return` functions extracted from core instances.
+    pub post_returns: usize,
+}
+
+/// Counts of the trampolines within a [`Component`] by kind, as returned by
+/// [`Component::trampoline_kind_counts`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TrampolineCounts {
+    /// The number of trampolines for imported functions lowered into core
+    /// wasm functions.
+    pub lowerings: usize,
+    /// The number of trampolines implementing `resource.drop`.
+    pub resource_drops: usize,
+    /// The number of trampolines which unconditionally trap, used by the
+    /// adapters generated between components.
+    pub always_trap: usize,
+    /// The number of all other trampolines, such as transcoders and the
+    /// remaining resource and async intrinsics.
+    pub other: usize,
+}
+
+/// How the compiled artifact of a [`Component`] was produced, as returned by
+/// [`Component::build_info`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// The version string embedded into the artifact, as configured with
+    /// [`Config::module_version`](crate::Config::module_version).
+    ///
+    /// By default this is the major version of Wasmtime, and it's empty when
+    /// [`ModuleVersionStrategy::None`](crate::ModuleVersionStrategy::None) is
+    /// used.
+    pub version: String,
+    /// The target triple the artifact was compiled for.
+    pub target: String,
+    /// The compiler backend which produced the artifact, either
+    /// [`Strategy::Cranelift`] or [`Strategy::Winch`].
+    pub compiler: Strategy,
+}
+
+/// Whether a serialized component could be loaded into an [`Engine`], as
+/// returned by [`Component::artifact_compatible`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Compatibility {
+    /// The artifact can be loaded with [`Component::deserialize`].
+    Compatible,
+    /// The artifact was produced by a different version of Wasmtime or with
+    /// settings which are incompatible with the engine, so it needs to be
+    /// recompiled.
+    Incompatible {
+        /// A description of each incompatibility that was found.
+        reason: String,
+    },
+}
+
+/// The host memory retained by a [`Component`], as returned by
+/// [`Component::memory_usage`].
+///
+/// All sizes are in bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ComponentMemoryUsage {
+    /// The size of the mapped compiled image of the component, including its
+    /// executable code and metadata.
+    pub image_size: usize,
+    /// The size of the executable code within the image.
+    pub text_size: usize,
+    /// An estimate of the size of the component's type tables.
+    ///
+    /// This only counts the entries of each table, not the heap allocations
+    /// owned by those entries.
+    pub types_size: usize,
+    /// The number of core wasm modules bundled within the component.
+    pub num_static_modules: usize,
+    /// The size of the compiled functions of those core wasm modules, which
+    /// is a subset of `text_size`.
+    pub static_modules_text_size: usize,
+}
+
+/// The dependency structure between the core instances of a [`Component`], as
+/// returned by [`Component::module_graph`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleGraph {
+    /// Each core wasm module instantiation, in the order they're performed.
+    pub nodes: Vec<ModuleGraphNode>,
+    /// Each import of a core instance satisfied by an export of another.
+    pub edges: Vec<ModuleGraphEdge>,
+}
+
+/// A core wasm module instantiation within a [`ModuleGraph`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleGraphNode {
+    /// The index of the instantiated module among the core modules defined
+    /// within the component, or `None` if the module was imported.
+    pub static_module: Option<StaticModuleIndex>,
+    /// The name of the instantiated module, either from its name section or,
+    /// for imported modules, the name of the component import.
+    pub name: Option<String>,
+}
+
+/// An import of one core instance satisfied by an export of another within a
+/// [`ModuleGraph`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleGraphEdge {
+    /// The index, within [`ModuleGraph::nodes`], of the exporting instance.
+    pub from: usize,
+    /// The name of the export, if it could be determined.
+    pub export: Option<String>,
+    /// The index, within [`ModuleGraph::nodes`], of the importing instance.
+    pub to: usize,
+    /// The module and field names of the import.
+    pub import: (String, String),
+}
+
+/// Options for loading a component from a file with
+/// [`Component::deserialize_file_with`].
+///
+/// By default the file is mapped into memory, the same as
+/// [`Component::deserialize_file`] does.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct DeserializeFileOptions {
+    mmap: bool,
+}
+
+#[cfg(feature = "std")]
+impl DeserializeFileOptions {
+    /// Creates the default set of options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the file is mapped into memory rather than read into it.
+    ///
+    /// Mapping the file avoids copying it and lets the OS share and page out
+    /// its contents, but it also means that changes to the file, or failures
+    /// of the filesystem it's stored on, can affect the loaded component.
+    /// Some network filesystems, for example, may fault on accesses to
+    /// mapped files. When disabled the file is instead read into memory
+    /// owned by the component, which is unaffected by the file afterwards.
+    ///
+    /// This is enabled by default.
+    pub fn mmap(mut self, enable: bool) -> Self {
+        self.mmap = enable;
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for DeserializeFileOptions {
+    fn default() -> Self {
+        Self { mmap: true }
+    }
+}
+
+/// Why the resources required to instantiate a [`Component`] can't be
+/// determined statically, as returned by
+/// [`Component::resources_required_report`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownResourceReason {
+    imported_modules: Vec<String>,
+}
+
+impl UnknownResourceReason {
+    /// Returns the names of the top-level imports which supply the core
+    /// modules that the component instantiates.
+    ///
+    /// If a module is an export of an imported instance then the name of that
+    /// instance import is returned. Each name is returned once, in the order
+    /// its module is first instantiated, and can be used as a key for
+    /// [`Component::resources_required_with`].
+    pub fn imported_modules(&self) -> impl ExactSizeIterator<Item = &str> + '_ {
+        self.imported_modules.iter().map(|s| s.as_str())
+    }
+}
+
+impl core::fmt::Display for UnknownResourceReason {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "resources required by imported core modules are unknown: "
+        )?;
+        for (i, name) in self.imported_modules.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "`{name}`")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for UnknownResourceReason {}
+
+/// An item that the host must provide to instantiate a [`Component`], as
+/// returned by [`Component::required_host_imports`].
+#[derive(Clone, Debug)]
+pub struct HostImport {
+    /// The dotted path of the imported instance that this item is exported
+    /// from, or `None` if the component imports this item directly.
+    pub instance: Option<String>,
+    /// The name of the item, either as a top-level import or as an export of
+    /// `instance`.
+    pub name: String,
+    /// The type of the item.
+    pub item: types::ComponentItem,
+}
+
+/// A shared memory imported by a core module that a [`Component`] imports, as
+/// returned by [`Component::imported_shared_memories`].
+#[derive(Clone, Debug)]
+pub struct MemorySpec {
+    /// The module name of the core memory import.
+    pub module: String,
+    /// The field name of the core memory import.
+    pub name: String,
+    /// The type of the imported memory.
+    pub ty: MemoryType,
+}
+
+/// A hook notified whenever an instance of a [`Component`] traps, installed
+/// with
+/// [`InstancePre::with_trap_recorder`](crate::component::InstancePre::with_trap_recorder).
+pub trait TrapRecorder: Send + Sync {
+    /// Records a trap at `frame`, the innermost wasm frame executing when the
+    /// trap happened.
+    ///
+    /// The `trap` code is provided when the trap was raised by wasm itself, and
+    /// is `None` for errors returned from host functions.
+    fn record(&self, frame: &FrameInfo, trap: Option<Trap>);
+}
+
+/// The state captured when an instance of a [`Component`] created with
+/// [`InstancePre::with_fuzz_trap_capture`](crate::component::InstancePre::with_fuzz_trap_capture)
+/// traps.
+#[derive(Debug)]
+pub struct TrapSnapshot<'a> {
+    /// The trap code, when the trap was raised by wasm itself, or `None` for
+    /// errors returned from host functions.
+    pub trap: Option<Trap>,
+    /// The native program counter of the faulting instruction, when the trap
+    /// was raised by a hardware fault or trap instruction in compiled code.
+    pub pc: Option<usize>,
+    /// The symbolicated wasm frames of the trap, innermost first.
+    pub frames: &'a [FrameInfo],
+}
+
+/// A sink halting execution when an instance of a [`Component`] traps,
+/// configured with
+/// [`InstancePre::with_fuzz_trap_capture`](crate::component::InstancePre::with_fuzz_trap_capture).
+pub trait TrapSnapshotSink: Send + Sync {
+    /// Captures `snapshot` and halts, for example by saving it to disk and
+    /// aborting the process.
+    fn capture(&self, snapshot: &TrapSnapshot<'_>) -> !;
+}
+
+/// Limits on the resources used by each instance of a [`Component`].
+///
+/// These limits are configured with
+/// [`InstancePre::with_limits`](crate::component::InstancePre::with_limits)
+/// and are enforced for every instance created from the returned
+/// [`InstancePre`](crate::component::InstancePre).
+/// Unlike [`StoreLimits`](crate::StoreLimits), which are shared across
+/// everything within a store, these limits apply to each component instance
+/// individually.
+///
+/// By default no limits are configured.
+#[derive(Clone, Debug, Default)]
+pub struct ComponentResourceLimits {
+    memory_size: Option<usize>,
+    table_elements: Option<usize>,
+    instances: Option<usize>,
+    memories: Option<usize>,
+    tables: Option<usize>,
+}
+
+impl ComponentResourceLimits {
+    /// Creates a new set of limits with nothing limited.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The maximum number of bytes each linear memory can grow to.
+    ///
+    /// Instantiation fails if a memory's initial size exceeds this limit.
+    /// Growing a linear memory beyond this limit will fail.
+    pub fn memory_size(mut self, limit: usize) -> Self {
+        self.memory_size = Some(limit);
+        self
+    }
+
+    /// The maximum number of elements in each table.
+    ///
+    /// Instantiation fails if a table's initial size exceeds this limit.
+    /// Growing a table beyond this limit will fail.
+    pub fn table_elements(mut self, limit: usize) -> Self {
+        self.table_elements = Some(limit);
+        self
+    }
+
+    /// The maximum number of core instances that a component instance can
+    /// create.
+    pub fn instances(mut self, limit: usize) -> Self {
+        self.instances = Some(limit);
+        self
+    }
+
+    /// The maximum number of linear memories that a component instance can
+    /// create.
+    pub fn memories(mut self, limit: usize) -> Self {
+        self.memories = Some(limit);
+        self
+    }
+
+    /// The maximum number of tables that a component instance can create.
+    pub fn tables(mut self, limit: usize) -> Self {
+        self.tables = Some(limit);
+        self
+    }
+
+    /// Checks that instantiating `module`, in addition to everything already
+    /// recorded in `usage`, stays within these limits.
+    pub(crate) fn check_module(
+        &self,
+        module: &Module,
+        usage: &mut ComponentResourceUsage,
+    ) -> Result<()> {
+        let module = module.env_module();
+        usage.instances += 1;
+        usage.memories += module.num_defined_memories();
+        usage.tables += module.num_defined_tables();
+
+        if let Some(max) = self.instances {
+            ensure!(
+                usage.instances <= max,
+                "component instance exceeds the limit of {max} core instances"
+            );
+        }
+        if let Some(max) = self.memories {
+            ensure!(
+                usage.memories <= max,
+                "component instance exceeds the limit of {max} memories"
+            );
+        }
+        if let Some(max) = self.tables {
+            ensure!(
+                usage.tables <= max,
+                "component instance exceeds the limit of {max} tables"
+            );
+        }
+
+        if let Some(max) = self.memory_size {
+            let max = u64::try_from(max).unwrap_or(u64::MAX);
+            for (_, memory) in module.memories.iter().skip(module.num_imported_memories) {
+                let minimum = memory.minimum_byte_size().unwrap_or(u64::MAX);
+                ensure!(
+                    minimum <= max,
+                    "memory minimum size of {minimum} bytes exceeds the component \
+                     limit of {max} bytes"
+                );
+                // Shared memories can't have their maximum lowered after
+                // creation since they may be used elsewhere, so require that
+                // their declared maximum already fits.
+                if memory.shared {
+                    let maximum = memory.maximum_byte_size().unwrap_or(u64::MAX);
+                    ensure!(
+                        maximum <= max,
+                        "shared memory maximum size of {maximum} bytes exceeds the \
+                         component limit of {max} bytes"
+                    );
+                }
+            }
+        }
+        if let Some(max) = self.table_elements {
+            let max = u64::try_from(max).unwrap_or(u64::MAX);
+            for (_, table) in module.tables.iter().skip(module.num_imported_tables) {
+                let minimum = table.limits.min;
+                ensure!(
+                    minimum <= max,
+                    "table minimum size of {minimum} elements exceeds the component \
+                     limit of {max} elements"
+                );
+            }
+        }
+        Ok(())
     }
 
-    pub(crate) fn id(&self) -> CompiledModuleId {
-        self.inner.id
+    /// Prevents the memories and tables defined by `instance` from growing
+    /// beyond these limits.
+    pub(crate) fn limit_growth(&self, mut instance: Pin<&mut crate::runtime::vm::Instance>) {
+        let module = instance.env_module().clone();
+        if let Some(max) = self.memory_size {
+            for i in 0..module.num_defined_memories() {
+                instance
+                    .as_mut()
+                    .get_defined_memory_mut(DefinedMemoryIndex::new(i))
+                    .limit_maximum(max);
+            }
+        }
+        if let Some(max) = self.table_elements {
+            for i in 0..module.num_defined_tables() {
+                instance
+                    .as_mut()
+                    .get_defined_table(DefinedTableIndex::new(i))
+                    .limit_maximum(max);
+            }
+        }
     }
+}
 
-    /// Returns the [`Engine`] that this [`Component`] was compiled by.
-    pub fn engine(&self) -> &Engine {
-        &self.inner.engine
-    }
+/// Running totals of resources created by a component instance, used to
+/// enforce [`ComponentResourceLimits`].
+#[derive(Default)]
+pub(crate) struct ComponentResourceUsage {
+    instances: usize,
+    memories: usize,
+    tables: usize,
+}
 
-    pub(crate) fn realloc_func_ty(&self) -> &Arc<FuncType> {
-        &self.inner.realloc_func_type
-    }
+/// A serialized [`Component`] which doesn't keep its [`Engine`] alive.
+///
+/// This is created with [`Component::detach`], see its documentation for more
+/// information.
+#[derive(Clone)]
+pub struct DetachedComponent {
+    bytes: Vec<u8>,
+}
 
-    #[allow(
-        unused,
-        reason = "used only for verification with wasmtime `rr` feature \
-        and requires a lot of unnecessary gating across crates"
-    )]
-    pub(crate) fn checksum(&self) -> &WasmChecksum {
-        &self.inner.checksum
+impl DetachedComponent {
+    /// Returns the serialized artifact of the component, in the same format
+    /// as [`Component::serialize`].
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
     }
 
-    /// Returns the `Export::LiftedFunction` metadata associated with `export`.
+    /// Reloads this component into `engine`.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if `export` is out of bounds or if it isn't a `LiftedFunction`.
-    pub(crate) fn export_lifted_function(
-        &self,
-        export: ExportIndex,
-    ) -> (TypeFuncIndex, &CoreDef, OptionsIndex) {
-        let component = self.env_component();
-        match &component.export_items[export] {
-            Export::LiftedFunction { ty, func, options } => (*ty, func, *options),
-            _ => unreachable!(),
-        }
+    /// Returns an error if `engine` isn't compatible with the engine that the
+    /// component was originally compiled with, in the same manner as
+    /// [`Component::deserialize`].
+    pub fn attach(&self, engine: &Engine) -> Result<Component> {
+        // SAFETY: these bytes were produced by `Component::serialize` and
+        // haven't been modified since.
+        unsafe { Component::deserialize(engine, &self.bytes) }
     }
 }
 
@@ -919,10 +3898,84 @@ impl InstanceExportLookup for ComponentExportIndex {
     }
 }
 
+/// A future which resolves to the result of a compilation running on another
+/// thread, used to implement [`Component::new_async`].
+#[cfg(all(
+    feature = "async",
+    feature = "std",
+    any(feature = "cranelift", feature = "winch")
+))]
+struct BackgroundCompile {
+    state: Arc<std::sync::Mutex<BackgroundCompileState>>,
+}
+
+#[cfg(all(
+    feature = "async",
+    feature = "std",
+    any(feature = "cranelift", feature = "winch")
+))]
+#[derive(Default)]
+struct BackgroundCompileState {
+    result: Option<std::thread::Result<Result<Component>>>,
+    waker: Option<core::task::Waker>,
+}
+
+#[cfg(all(
+    feature = "async",
+    feature = "std",
+    any(feature = "cranelift", feature = "winch")
+))]
+impl BackgroundCompile {
+    fn spawn(compile: impl FnOnce() -> Result<Component> + Send + 'static) -> Result<Self> {
+        let state = Arc::new(std::sync::Mutex::new(BackgroundCompileState::default()));
+        let thread_state = state.clone();
+        std::thread::Builder::new()
+            .name("wasmtime-compile".to_string())
+            .spawn(move || {
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(compile));
+                let mut state = thread_state.lock().unwrap();
+                state.result = Some(result);
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            })
+            .context("failed to spawn a thread for compilation")?;
+        Ok(BackgroundCompile { state })
+    }
+}
+
+#[cfg(all(
+    feature = "async",
+    feature = "std",
+    any(feature = "cranelift", feature = "winch")
+))]
+impl Future for BackgroundCompile {
+    type Output = Result<Component>;
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Result<Component>> {
+        let mut state = self.state.lock().unwrap();
+        match state.result.take() {
+            Some(Ok(result)) => core::task::Poll::Ready(result),
+            // Propagate panics from the compilation thread to the caller the
+            // same way a panic within `Component::new` would.
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                core::task::Poll::Pending
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::component::Component;
-    use crate::{CodeBuilder, Config, Engine};
+    use crate::prelude::*;
+    use crate::{CodeBuilder, Config, Engine, Strategy, WasmFeatures};
+    use alloc::sync::Arc;
     use wasmtime_environ::MemoryInitialization;
 
     #[test]
@@ -949,6 +4002,604 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn deduplicate_with() {
+        let engine = Engine::default();
+        let bytes = engine
+            .precompile_component(b"(component (core module (func (export \"f\"))))")
+            .unwrap();
+        let mut a = unsafe { Component::deserialize(&engine, &bytes).unwrap() };
+        let b = unsafe { Component::deserialize(&engine, &bytes).unwrap() };
+        assert!(!alloc::sync::Arc::ptr_eq(&a.inner, &b.inner));
+        assert!(a.deduplicate_with(&b));
+        assert!(alloc::sync::Arc::ptr_eq(&a.inner, &b.inner));
+        assert!(a.deduplicate_with(&b));
+
+        let mut c = Component::new(&engine, "(component)").unwrap();
+        assert!(!c.deduplicate_with(&b));
+
+        let other_engine = Engine::default();
+        let mut d = unsafe { Component::deserialize(&other_engine, &bytes).unwrap() };
+        assert!(!d.deduplicate_with(&b));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn freeze() {
+        let engine = Engine::default();
+        let component = Component::new(&engine, "(component (core module))").unwrap();
+        let frozen = component.freeze().unwrap();
+        assert_eq!(frozen.image(), &component.serialize().unwrap()[..]);
+        assert!(alloc::sync::Arc::ptr_eq(
+            &frozen.component().inner,
+            &component.inner
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn post_return_funcs() {
+        use wasmtime_environ::component::{CoreDef, TrampolineKind};
+
+        let engine = Engine::default();
+        let component = Component::new(
+            &engine,
+            r#"
+                (component
+                    (import "post" (func $post (param "x" u32)))
+                    (core func $post (canon lower (func $post)))
+                    (core module $m
+                        (func (export "f") (result i32) i32.const 0)
+                        (func (export "g") (result i32) i32.const 0)
+                        (func (export "post") (param i32))
+                    )
+                    (core instance $i (instantiate $m))
+                    (func (export "f") (result u32)
+                        (canon lift (core func $i "f") (post-return (func $i "post"))))
+                    (func (export "g") (result u32)
+                        (canon lift (core func $i "g") (post-return (func $post))))
+                )
+            "#,
+        )
+        .unwrap();
+
+        let post_returns = component.post_return_funcs().collect::<Vec<_>>();
+        assert_eq!(post_returns.len(), 2);
+        assert_ne!(post_returns[0].index, post_returns[1].index);
+        assert!(
+            post_returns
+                .iter()
+                .any(|post_return| matches!(post_return.def, CoreDef::Export(_)))
+        );
+        let trampoline = post_returns
+            .iter()
+            .find_map(|post_return| match post_return.def {
+                CoreDef::Trampoline(index) => Some(index),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            component.inner.info.trampoline_kinds[trampoline],
+            TrampolineKind::LowerImport
+        );
+
+        let component = Component::new(&engine, "(component)").unwrap();
+        assert_eq!(component.post_return_funcs().count(), 0);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn export_trampoline() {
+        let engine = Engine::default();
+        let component = Component::new(
+            &engine,
+            r#"
+                (component
+                    (import "f" (func $f))
+                    (core func $lowered (canon lower (func $f)))
+                    (func (export "lifted-lowered") (canon lift (core func $lowered)))
+
+                    (core module $m (func (export "g")))
+                    (core instance $i (instantiate $m))
+                    (func (export "lifted") (canon lift (core func $i "g")))
+                )
+            "#,
+        )
+        .unwrap();
+
+        let index = component.export_trampoline("lifted-lowered").unwrap();
+        assert!(component.env_component().trampolines.get(index).is_some());
+        assert!(component.export_trampoline("lifted").is_none());
+        assert!(component.export_trampoline("missing").is_none());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn shrink_to_fit() -> Result<()> {
+        let engine = Engine::default();
+        let mut component = Component::new(
+            &engine,
+            r#"
+                (component
+                    (import "f" (func $f))
+                    (core module $m (func (export "g")))
+                    (core instance $i (instantiate $m))
+                    (func (export "g") (canon lift (core func $i "g")))
+                )
+            "#,
+        )?;
+
+        // Clones share metadata so nothing can be released.
+        let clone = component.clone();
+        component.shrink_to_fit();
+        drop(clone);
+
+        component.shrink_to_fit();
+        let env = component.env_component();
+        assert_eq!(env.initializers.capacity(), env.initializers.len());
+        assert_eq!(component.inner.static_modules.len(), 1);
+        assert!(component.get_export_index(None, "g").is_some());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn shared_component_types() -> Result<()> {
+        let wat = r#"
+            (component
+                (import "f" (func (param "x" (list string)) (result u32)))
+                (core module (func (export "f") (param i32)))
+            )
+        "#;
+
+        let engine = Engine::default();
+        let a = Component::new(&engine, wat)?;
+        let b = Component::new(&engine, wat)?;
+        assert!(!Arc::ptr_eq(a.types(), b.types()));
+
+        let mut config = Config::new();
+        config.shared_component_types(true);
+        let engine = Engine::new(&config)?;
+        let a = Component::new(&engine, wat)?;
+        let b = Component::new(&engine, wat)?;
+        let c = Component::new(&engine, "(component (import \"f\" (func)))")?;
+        assert!(Arc::ptr_eq(a.types(), b.types()));
+        assert!(!Arc::ptr_eq(a.types(), c.types()));
+
+        let serialized = a.serialize()?;
+        drop((a, b));
+        let d = unsafe { Component::deserialize(&engine, &serialized)? };
+        let e = unsafe { Component::deserialize(&engine, &serialized)? };
+        assert!(Arc::ptr_eq(d.types(), e.types()));
+
+        // Entries for types which are no longer in use are removed.
+        drop((c, d, e));
+        Component::new(&engine, "(component)")?;
+        assert_eq!(engine.num_shared_component_types(), 1);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn resident_code_pages() -> Result<()> {
+        let engine = Engine::default();
+        let component = Component::new(
+            &engine,
+            r#"
+                (component
+                    (core module (func (export "f")))
+                )
+            "#,
+        )?;
+        let result = component.resident_code_pages();
+        if cfg!(unix) {
+            let range = component.image_range();
+            let page_size = crate::runtime::vm::host_page_size();
+            let max = (range.end.addr() - range.start.addr()).div_ceil(page_size) + 1;
+            let resident = result?;
+            // The image was just written so at least some of it is resident.
+            assert!(resident > 0);
+            assert!(resident <= max);
+        } else {
+            assert!(result.is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn attach_symbols() -> Result<()> {
+        use crate::{Store, WasmBacktrace, component::Linker};
+
+        let component = |func: &str| {
+            format!(
+                r#"
+                    (component
+                        (core module $m
+                            (func {func} (export "f") unreachable)
+                        )
+                        (core instance $i (instantiate $m))
+                        (func (export "f") (canon lift (core func $i "f")))
+                    )
+                "#
+            )
+        };
+        let engine = Engine::default();
+        let named = Component::new(&engine, component("$my_func"))?;
+        let stripped = Component::new(&engine, component(""))?;
+        let symbols = named.extract_symbols()?;
+
+        let trap_func_name = |component: &Component| -> Result<Option<String>> {
+            let mut store = Store::new(&engine, ());
+            let instance = Linker::new(&engine).instantiate(&mut store, component)?;
+            let f = instance.get_typed_func::<(), ()>(&mut store, "f")?;
+            let err = f.call(&mut store, ()).unwrap_err();
+            let backtrace = err.downcast_ref::<WasmBacktrace>().unwrap();
+            Ok(backtrace.frames()[0].func_name().map(|s| s.to_string()))
+        };
+        assert_eq!(trap_func_name(&named)?.as_deref(), Some("my_func"));
+        assert_eq!(trap_func_name(&stripped)?, None);
+
+        stripped.attach_symbols(&symbols)?;
+        assert_eq!(trap_func_name(&stripped)?.as_deref(), Some("my_func"));
+        assert!(stripped.attach_symbols(&symbols).is_err());
+
+        let other = Component::new(&engine, "(component)")?;
+        assert!(other.attach_symbols(&symbols).is_err());
+        assert!(other.attach_symbols(b"not symbols").is_err());
+
+        // Symbols which are invalid for a later module don't attach names to
+        // the earlier ones.
+        let two = Component::new(
+            &engine,
+            r#"
+                (component
+                    (core module (func))
+                    (core module (func))
+                )
+            "#,
+        )?;
+        let symbols = |modules| postcard::to_allocvec(&super::ComponentSymbols { modules });
+        let invalid = symbols(vec![vec![(0, "a".to_string())], vec![(1, "b".to_string())]])?;
+        assert!(two.attach_symbols(&invalid).is_err());
+        let valid = symbols(vec![vec![(0, "a".to_string())], vec![(0, "b".to_string())]])?;
+        two.attach_symbols(&valid)?;
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn minimal_config() -> Result<()> {
+        let engine = Engine::default();
+        let wat = r#"
+            (component
+                (core module
+                    (memory 1)
+                    (func (result i32) i32.const 0 i32.load)
+                )
+            )
+        "#;
+        let component = Component::new(&engine, wat)?;
+        let config = component.minimal_config();
+        let minimal = Engine::new(&config)?;
+        let features = minimal.features();
+        assert!(!features.contains(WasmFeatures::SIMD));
+        assert!(!features.contains(WasmFeatures::THREADS));
+        assert!(!features.contains(WasmFeatures::MULTI_MEMORY));
+        assert!(!features.contains(WasmFeatures::TAIL_CALL));
+        assert!(!features.contains(WasmFeatures::GC));
+        Component::new(&minimal, wat)?;
+
+        let wat = r#"
+            (component
+                (core module
+                    (memory 1)
+                    (memory 1 1 shared)
+                    (func (result v128) v128.const i64x2 0 0)
+                )
+            )
+        "#;
+        let component = Component::new(&engine, wat)?;
+        let config = component.minimal_config();
+        let minimal = Engine::new(&config)?;
+        let features = minimal.features();
+        assert!(features.contains(WasmFeatures::SIMD));
+        assert!(features.contains(WasmFeatures::THREADS));
+        assert!(features.contains(WasmFeatures::MULTI_MEMORY));
+        assert!(!features.contains(WasmFeatures::RELAXED_SIMD));
+        Component::new(&minimal, wat)?;
+
+        // Features which are only visible in types, locals, and tables must
+        // be covered as well, including proposals which are disabled by
+        // default and must be turned on for the component to recompile.
+        let mut config = Config::new();
+        config
+            .wasm_function_references(true)
+            .wasm_wide_arithmetic(true)
+            .wasm_custom_page_sizes(true);
+        let mut cases = vec![
+            (
+                "(component (core module (type (func (param v128)))))",
+                WasmFeatures::SIMD,
+            ),
+            (
+                "(component (core module (func (result i32) (local v128) i32.const 0)))",
+                WasmFeatures::SIMD,
+            ),
+            (
+                "(component (core module (table i64 1 funcref)))",
+                WasmFeatures::MEMORY64,
+            ),
+            (
+                "(component (core module
+                    (memory 1)
+                    (func (param i32) (result i32) local.get 0 i32.atomic.load)))",
+                WasmFeatures::THREADS,
+            ),
+            (
+                "(component (core module
+                    (type $f (func))
+                    (func (param (ref $f)) local.get 0 call_ref $f)))",
+                WasmFeatures::FUNCTION_REFERENCES,
+            ),
+            (
+                "(component (core module (func return_call 0)))",
+                WasmFeatures::TAIL_CALL,
+            ),
+            (
+                "(component (core module
+                    (func (param i64 i64 i64 i64) (result i64 i64)
+                        local.get 0 local.get 1 local.get 2 local.get 3
+                        i64.add128)))",
+                WasmFeatures::WIDE_ARITHMETIC,
+            ),
+            (
+                "(component (core module (memory 1 (pagesize 1))))",
+                WasmFeatures::CUSTOM_PAGE_SIZES,
+            ),
+        ];
+        if cfg!(feature = "gc") {
+            config.wasm_gc(true).wasm_exceptions(true);
+            cases.extend([
+                (
+                    "(component (core module (type (struct)) (func (param anyref))))",
+                    WasmFeatures::GC,
+                ),
+                (
+                    "(component (core module (tag) (func throw 0)))",
+                    WasmFeatures::EXCEPTIONS,
+                ),
+            ]);
+        }
+        let engine = Engine::new(&config)?;
+        for (wat, feature) in cases {
+            let component = Component::new(&engine, wat)?;
+            let minimal = Engine::new(&component.minimal_config())?;
+            assert!(minimal.features().contains(feature), "{wat}");
+            Component::new(&minimal, wat)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn with_instantiation_timeout() -> Result<()> {
+        use crate::{AsContextMut, Store, component::Linker};
+        use core::time::Duration;
+
+        let wat = r#"
+            (component
+                (core module $m
+                    (func $start (loop br 0))
+                    (start $start)
+                )
+                (core instance (instantiate $m))
+            )
+        "#;
+
+        let engine = Engine::default();
+        let component = Component::new(&engine, "(component)")?;
+        let pre = Linker::new(&engine)
+            .instantiate_pre(&component)?
+            .with_instantiation_timeout(Duration::from_secs(1));
+        let mut store = Store::new(&engine, ());
+        let err = pre.instantiate(&mut store).unwrap_err();
+        assert!(
+            format!("{err}").contains("`Config::epoch_interruption`"),
+            "{err}"
+        );
+
+        let mut config = Config::new();
+        config.epoch_interruption(true);
+        let engine = Engine::new(&config)?;
+        let component = Component::new(&engine, wat)?;
+        let timed = Linker::new(&engine)
+            .instantiate_pre(&component)?
+            .with_instantiation_timeout(Duration::from_millis(50));
+
+        let done = Arc::new(core::sync::atomic::AtomicBool::new(false));
+        let ticker = std::thread::spawn({
+            let engine = engine.clone();
+            let done = done.clone();
+            move || {
+                while !done.load(core::sync::atomic::Ordering::SeqCst) {
+                    engine.increment_epoch();
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+            }
+        });
+
+        let mut store = Store::new(&engine, ());
+        store.set_epoch_deadline(u64::MAX / 2);
+        let deadline = store.as_context_mut().0.get_epoch_deadline();
+        let err = timed.instantiate(&mut store).unwrap_err();
+        assert!(
+            format!("{err:?}").contains("component instantiation timed out after 50ms"),
+            "{err:?}"
+        );
+        assert_eq!(store.as_context_mut().0.get_epoch_deadline(), deadline);
+
+        // The store's configured epoch deadline behavior still applies while
+        // instantiating.
+        let timed = Linker::<u32>::new(&engine)
+            .instantiate_pre(&component)?
+            .with_instantiation_timeout(Duration::from_secs(60));
+        let mut store = Store::new(&engine, 0);
+        store.set_epoch_deadline(1);
+        store.epoch_deadline_callback(|mut store| {
+            *store.data_mut() += 1;
+            Ok(if *store.data() == 3 {
+                crate::UpdateDeadline::Interrupt
+            } else {
+                crate::UpdateDeadline::Continue(1)
+            })
+        });
+        let err = timed.instantiate(&mut store).unwrap_err();
+        done.store(true, core::sync::atomic::Ordering::SeqCst);
+        ticker.join().unwrap();
+        assert_eq!(
+            err.downcast_ref::<crate::Trap>(),
+            Some(&crate::Trap::Interrupt)
+        );
+        assert_eq!(*store.data(), 3);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn instantiation_timeout_cancelled() -> Result<()> {
+        use crate::runtime::vm::VMStore;
+        use crate::store::InstantiationTimeout;
+        use crate::{AsContextMut, Store, StoreContextMut, UpdateDeadline};
+        use core::time::Duration;
+
+        let mut config = Config::new();
+        config.epoch_interruption(true);
+        let engine = Engine::new(&config)?;
+        let mut store = Store::new(&engine, ());
+        store.set_epoch_deadline(10);
+        let deadline = store.as_context_mut().0.get_epoch_deadline();
+
+        // Dropping the guard without completing instantiation, as happens
+        // when an instantiation future is dropped, restores the store.
+        let mut cx = store.as_context_mut();
+        let mut guard = InstantiationTimeout::new(&mut cx, Some(Duration::from_secs(60)));
+        let cx: &mut StoreContextMut<'_, ()> = &mut guard;
+        assert_ne!(cx.0.get_epoch_deadline(), deadline);
+        assert!(matches!(
+            cx.0.new_epoch_updated_deadline()?,
+            UpdateDeadline::Continue(1)
+        ));
+        drop(guard);
+
+        let cx = store.as_context_mut();
+        assert_eq!(cx.0.get_epoch_deadline(), deadline);
+        assert!(matches!(
+            cx.0.new_epoch_updated_deadline()?,
+            UpdateDeadline::Interrupt
+        ));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn validate_max_function_size() -> Result<()> {
+        let engine = Engine::default();
+        let component = Component::new(
+            &engine,
+            r#"
+                (component
+                    (core module (func))
+                    (core module
+                        (import "" "f" (func))
+                        (func)
+                        (func $big (param i32) (result i32)
+                            local.get 0 i32.const 1 i32.add
+                            local.get 0 i32.mul
+                            local.get 0 i32.div_u
+                            local.get 0 i32.rem_s)
+                    )
+                )
+            "#,
+        )?;
+        component.validate_max_function_size(u32::MAX)?;
+        let err = component.validate_max_function_size(0).unwrap_err();
+        assert!(
+            format!("{err}").starts_with("function 0 of core module 0 has"),
+            "{err}"
+        );
+
+        // Empty functions are all the same size, so only `$big` exceeds it.
+        let empty = component.inner.static_modules.values().next().unwrap();
+        let empty = empty
+            .compiled_module()
+            .func_loc(wasmtime_environ::DefinedFuncIndex::from_u32(0))
+            .length;
+        let err = component.validate_max_function_size(empty).unwrap_err();
+        assert!(
+            format!("{err}").starts_with("function 2 of core module 1 (`big`) has"),
+            "{err}"
+        );
+        Ok(())
+    }
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn build_info() -> Result<()> {
+        let engine = Engine::default();
+        let component = Component::new(&engine, "(component)")?;
+        let info = component.build_info();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION_MAJOR"));
+        assert_eq!(info.target, engine.target().to_string());
+        assert_eq!(info.compiler, Strategy::Cranelift);
+
+        let bytes = component.serialize()?;
+        let deserialized = unsafe { Component::deserialize(&engine, &bytes)? };
+        assert_eq!(deserialized.build_info(), info);
+
+        let mut config = Config::new();
+        config.module_version(crate::ModuleVersionStrategy::Custom("custom".to_string()))?;
+        let engine = Engine::new(&config)?;
+        let component = Component::new(&engine, "(component)")?;
+        assert_eq!(component.build_info().version, "custom");
+        Ok(())
+    }
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn deserialize_rejects_compressed() {
+        let engine = Engine::default();
+        let mut bytes = super::COMPRESSED_MAGIC.to_vec();
+        bytes.extend_from_slice(b"frame");
+        let Err(err) = (unsafe { Component::deserialize(&engine, &bytes) }) else {
+            panic!("compressed artifact was accepted");
+        };
+        assert!(format!("{err}").contains("deserialize_compressed"), "{err}");
+        let Err(err) = (unsafe { Component::deserialize_unchecked(&engine, &bytes) }) else {
+            panic!("compressed artifact was accepted");
+        };
+        assert!(format!("{err}").contains("deserialize_compressed"), "{err}");
+    }
+    #[test]
+    #[cfg(feature = "zstd")]
+    #[cfg_attr(miri, ignore)]
+    fn serialize_compressed() -> Result<()> {
+        let engine = Engine::default();
+        let component = Component::new(
+            &engine,
+            "(component (core module (func (export \"f\") (result i32) i32.const 1)))",
+        )?;
+        let compressed = component.serialize_compressed()?;
+        assert!(compressed.starts_with(super::COMPRESSED_MAGIC));
+        let deserialized = unsafe { Component::deserialize_compressed(&engine, &compressed)? };
+        assert_eq!(deserialized.serialize()?, component.serialize()?);
+
+        let uncompressed = component.serialize()?;
+        assert!(unsafe { Component::deserialize_compressed(&engine, &uncompressed) }.is_err());
+        let mut corrupt = compressed.clone();
+        corrupt.truncate(super::COMPRESSED_MAGIC.len() + 4);
+        assert!(unsafe { Component::deserialize_compressed(&engine, &corrupt) }.is_err());
+        Ok(())
+    }
     #[test]
     #[cfg_attr(miri, ignore)]
     fn image_range_is_whole_image() {
@@ -1,10 +1,11 @@
 use crate::component::RuntimeInstance;
+use crate::component::component::{ComponentResourceLimits, ComponentResourceUsage};
 use crate::component::func::HostFunc;
 use crate::component::matching::InstanceType;
 use crate::component::store::{ComponentInstanceId, StoreComponentInstanceId};
 use crate::component::{
     Component, ComponentExportIndex, ComponentNamedList, Func, Lift, Lower, ResourceType,
-    TypedFunc, types::ComponentItem,
+    TrapRecorder, TrapSnapshotSink, TypedFunc, types::ComponentItem,
 };
 use crate::instance::OwnedImports;
 use crate::linker::DefinitionType;
@@ -18,7 +19,7 @@ use core::marker;
 use core::pin::Pin;
 use core::ptr::NonNull;
 use wasmtime_environ::{EngineOrModuleTypeIndex, component::*};
-use wasmtime_environ::{EntityIndex, EntityType, PrimaryMap};
+use wasmtime_environ::{EntityIndex, EntityRef, EntityType, PrimaryMap};
 
 /// An instantiated component.
 ///
@@ -236,6 +237,92 @@ impl Instance {
         }
     }
 
+    /// Returns the core wasm instance created for one of the core modules
+    /// defined within this instance's component.
+    ///
+    /// The `module` index is that of [`ModuleGraphNode::static_module`]. If the
+    /// module is instantiated more than once then its first instance is
+    /// returned. The returned instance provides raw access to the core
+    /// module's exports, bypassing the canonical ABI, which can be useful for
+    /// debugging.
+    ///
+    /// Returns `None` if `module` is out of bounds or isn't instantiated.
+    ///
+    /// [`ModuleGraphNode::static_module`]: crate::component::ModuleGraphNode::static_module
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this instance.
+    pub fn core_instance(
+        &self,
+        mut store: impl AsContextMut,
+        module: StaticModuleIndex,
+    ) -> Option<crate::Instance> {
+        let store = store.as_context_mut().0;
+        let data = self.id().get(store);
+        let index = data
+            .component()
+            .env_component()
+            .initializers
+            .iter()
+            .filter_map(|init| match init {
+                GlobalInitializer::InstantiateModule(instantiate, _) => Some(instantiate),
+                _ => None,
+            })
+            .position(|instantiate| {
+                matches!(
+                    instantiate,
+                    InstantiateModule::Static(index, _) if *index == module
+                )
+            })?;
+        let id = data.instance(RuntimeInstanceIndex::new(index));
+        Some(crate::Instance::from_wasmtime(id, store))
+    }
+
+    /// Looks up the core wasm linear memory used by the exported function
+    /// `name` within this [`Instance`].
+    ///
+    /// Components cannot export memories directly, so this instead resolves
+    /// the `memory` canonical option that `name` was lifted with back to the
+    /// core wasm memory it was extracted from. The returned [`Memory`] can be
+    /// used to inspect or modify the guest's memory directly, bypassing the
+    /// canonical ABI, which can be useful for debugging.
+    ///
+    /// Returns `None` if `name` isn't an exported function, if it wasn't lifted
+    /// with a `memory` option, or if the memory is a shared memory.
+    ///
+    /// [`Memory`]: crate::Memory
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this instance.
+    pub fn get_export_memory(
+        &self,
+        mut store: impl AsContextMut,
+        name: impl InstanceExportLookup,
+    ) -> Option<crate::Memory> {
+        let store = store.as_context_mut().0;
+        let (data, export) = self.lookup_export(store, name)?;
+        let options = match export {
+            Export::LiftedFunction { options, .. } => *options,
+            _ => return None,
+        };
+        let env_component = data.component().env_component();
+        let memory = env_component.options[options].memory()?;
+        let export = env_component
+            .initializers
+            .iter()
+            .find_map(|init| match init {
+                GlobalInitializer::ExtractMemory(m) if m.index == memory => Some(&m.export),
+                _ => None,
+            })?
+            .clone();
+        match lookup_vmexport(store, self.id().instance(), &export) {
+            vm::Export::Memory(memory) => Some(memory),
+            _ => None,
+        }
+    }
+
     /// Looks up an exported resource type by name within this [`Instance`].
     ///
     /// The `store` argument provided must be the store that this instance
@@ -689,6 +776,9 @@ struct Instantiator<'a> {
     id: ComponentInstanceId,
     core_imports: OwnedImports,
     imports: &'a PrimaryMap<RuntimeImportIndex, RuntimeImport>,
+    run_start_functions: bool,
+    limits: Option<&'a ComponentResourceLimits>,
+    usage: ComponentResourceUsage,
 }
 
 pub(crate) enum RuntimeImport {
@@ -743,6 +833,9 @@ impl<'a> Instantiator<'a> {
             imports,
             core_imports: OwnedImports::empty(),
             id,
+            run_start_functions: true,
+            limits: None,
+            usage: ComponentResourceUsage::default(),
         })
     }
 
@@ -813,6 +906,7 @@ impl<'a> Instantiator<'a> {
             );
         }
 
+        let run_start_functions = self.run_start_functions;
         for initializer in env_component.initializers.iter() {
             match initializer {
                 GlobalInitializer::InstantiateModule(m, component_instance) => {
@@ -823,6 +917,7 @@ impl<'a> Instantiator<'a> {
                         // `args` list is already in the right order.
                         InstantiateModule::Static(idx, args) => {
                             module = self.component.static_module(*idx);
+                            self.check_limits(module)?;
                             self.build_imports(store.0, module, args.iter())
                         }
 
@@ -839,6 +934,7 @@ impl<'a> Instantiator<'a> {
                                 RuntimeImport::Module(m) => m,
                                 _ => unreachable!(),
                             };
+                            self.check_limits(module)?;
                             let args = module
                                 .imports()
                                 .map(|import| &args[import.module()][import.name()]);
@@ -865,14 +961,18 @@ impl<'a> Instantiator<'a> {
                     // already been performed. This means that the unsafety due
                     // to imports having the wrong type should not happen here.
                     //
-                    // Also note we are calling new_started_impl because we have
-                    // already checked for asyncness and are running on a fiber
-                    // if required.
-
-                    let i = unsafe {
-                        crate::Instance::new_started(store, module, imports.as_ref(), asyncness)
+                    // Also note that the start function is run separately so
+                    // any limits on growth are in place before it runs.
+                    let (i, start) = unsafe {
+                        crate::Instance::new_unstarted(store, module, imports.as_ref(), asyncness)
                             .await?
                     };
+                    if let Some(limits) = self.limits {
+                        limits.limit_growth(store.0.instance_mut(i.id()));
+                    }
+                    if let Some(start) = start.filter(|_| run_start_functions) {
+                        i.run_start(store, start, asyncness).await?;
+                    }
 
                     if exit {
                         store.0.exit_guest_sync_call(false)?;
@@ -881,7 +981,7 @@ impl<'a> Instantiator<'a> {
                     self.instance_mut(store.0).push_instance_id(i.id());
                 }
 
-                GlobalInitializer::LowerImport { import, index } => {
+                GlobalInitializer::LowerImport { import, index, .. } => {
                     let func = match &self.imports[*import] {
                         RuntimeImport::Func(func) => func,
                         _ => unreachable!(),
@@ -926,7 +1026,13 @@ impl<'a> Instantiator<'a> {
             .env_component()
             .resource_index(resource.index);
         let instance = self.instance(store);
-        let ty = ResourceType::guest(store.id(), instance, resource.index);
+        let ty = ResourceType::guest(
+            store.id(),
+            instance,
+            self.component.id(),
+            self.component.types(),
+            index,
+        );
         self.instance_mut(store)
             .set_resource_destructor(index, dtor);
         let i = self.instance_resource_types_mut(store).push(ty);
@@ -980,6 +1086,13 @@ impl<'a> Instantiator<'a> {
             .set_runtime_table(table.index, import);
     }
 
+    fn check_limits(&mut self, module: &Module) -> Result<()> {
+        match self.limits {
+            Some(limits) => limits.check_module(module, &mut self.usage),
+            None => Ok(()),
+        }
+    }
+
     fn build_imports<'b>(
         &mut self,
         store: &mut StoreOpaque,
@@ -1077,6 +1190,18 @@ impl<'a> Instantiator<'a> {
     }
 }
 
+/// How an instance created by an [`InstancePre`] is used.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Instantiation {
+    /// The instance is handed to the embedder.
+    Embedder,
+    /// The instance is discarded once created, as by [`Component::warmup`].
+    Throwaway,
+    /// Like `Throwaway`, but the start functions of core wasm modules aren't
+    /// run either, as by [`Component::dry_run`].
+    ThrowawayWithoutStart,
+}
+
 /// A "pre-instantiated" [`Instance`] which has all of its arguments already
 /// supplied and is ready to instantiate.
 ///
@@ -1090,6 +1215,11 @@ pub struct InstancePre<T: 'static> {
     imports: Arc<PrimaryMap<RuntimeImportIndex, RuntimeImport>>,
     resource_types: Arc<PrimaryMap<ResourceIndex, ResourceType>>,
     asyncness: Asyncness,
+    limits: Option<Arc<ComponentResourceLimits>>,
+    trap_recorder: Option<Arc<dyn TrapRecorder>>,
+    trap_sink: Option<Arc<dyn TrapSnapshotSink>>,
+    #[cfg(all(feature = "std", target_has_atomic = "64"))]
+    instantiation_timeout: Option<core::time::Duration>,
     _marker: marker::PhantomData<fn() -> T>,
 }
 
@@ -1101,6 +1231,11 @@ impl<T: 'static> Clone for InstancePre<T> {
             imports: self.imports.clone(),
             resource_types: self.resource_types.clone(),
             asyncness: self.asyncness,
+            limits: self.limits.clone(),
+            trap_recorder: self.trap_recorder.clone(),
+            trap_sink: self.trap_sink.clone(),
+            #[cfg(all(feature = "std", target_has_atomic = "64"))]
+            instantiation_timeout: self.instantiation_timeout,
             _marker: self._marker,
         }
     }
@@ -1132,10 +1267,103 @@ impl<T: 'static> InstancePre<T> {
             imports,
             resource_types,
             asyncness,
+            limits: None,
+            trap_recorder: None,
+            trap_sink: None,
+            #[cfg(all(feature = "std", target_has_atomic = "64"))]
+            instantiation_timeout: None,
             _marker: marker::PhantomData,
         }
     }
 
+    /// Returns a copy of this [`InstancePre`] which enforces `limits` on each
+    /// instance it creates.
+    ///
+    /// Instantiation fails if it would create more core instances, memories,
+    /// or tables than `limits` allows, or if any memory or table starts out
+    /// larger than the configured limit. Linear memories and tables of the
+    /// resulting instances are additionally prevented from growing beyond the
+    /// configured limits at runtime, independently of any
+    /// [`ResourceLimiter`](crate::ResourceLimiter) configured for the store.
+    ///
+    /// Any limits previously configured on `self` are replaced.
+    pub fn with_limits(&self, limits: ComponentResourceLimits) -> InstancePre<T> {
+        InstancePre {
+            limits: Some(Arc::new(limits)),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the limits configured with [`InstancePre::with_limits`], if
+    /// any.
+    pub fn limits(&self) -> Option<&ComponentResourceLimits> {
+        self.limits.as_deref()
+    }
+
+    /// Returns a copy of this [`InstancePre`] whose instances notify
+    /// `recorder` whenever they trap.
+    ///
+    /// The recorder receives the innermost symbolicated wasm frame of the
+    /// trap, so it is only invoked when
+    /// [`Config::wasm_backtrace`](crate::Config::wasm_backtrace) is enabled,
+    /// which it is by default. Like [`InstancePre::with_fuzz_trap_capture`]
+    /// the recorder applies to all instances of the component within a store
+    /// once one of them is created with it. Any recorder previously configured
+    /// on `self` is replaced.
+    pub fn with_trap_recorder(&self, recorder: Arc<dyn TrapRecorder>) -> InstancePre<T> {
+        InstancePre {
+            trap_recorder: Some(recorder),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of this [`InstancePre`] whose instances hand every trap
+    /// to `sink` the instant it happens, for differential fuzzing.
+    ///
+    /// When a trap is raised during instantiation or execution of an instance
+    /// created from the returned [`InstancePre`], `sink` receives a
+    /// [`TrapSnapshot`](crate::component::TrapSnapshot) with the trap code,
+    /// the program counter, and the symbolicated wasm frames of the trap
+    /// before any error is propagated to the caller. Sinks don't return, so
+    /// the trap halts the program with its full context captured instead of
+    /// unwinding through the embedder, making fuzzing failures reproducible.
+    ///
+    /// The frames are only available when
+    /// [`Config::wasm_backtrace`](crate::Config::wasm_backtrace) is enabled,
+    /// and traps without any known wasm frame aren't captured. The sink
+    /// applies to all instances of the component within a store once one of
+    /// them is created with it. Any sink previously configured on `self` is
+    /// replaced.
+    pub fn with_fuzz_trap_capture(&self, sink: Arc<dyn TrapSnapshotSink>) -> InstancePre<T> {
+        InstancePre {
+            trap_sink: Some(sink),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of this [`InstancePre`] whose instantiation fails if it
+    /// takes longer than `timeout`, for example due to a runaway `start`
+    /// function.
+    ///
+    /// The timeout is enforced with
+    /// [epoch-based interruption](crate::Config::epoch_interruption), which
+    /// must be enabled, so it's checked whenever the engine's epoch is
+    /// incremented with [`Engine::increment_epoch`] while wasm is running.
+    /// The granularity of the timeout is therefore that of the epoch ticks
+    /// driven by the host. The store's configured epoch deadline and its
+    /// behavior remain in effect during instantiation.
+    ///
+    /// Instantiation exceeding `timeout` fails with an error saying it timed
+    /// out. Instantiation also fails if epoch interruption isn't enabled. Any
+    /// timeout previously configured on `self` is replaced.
+    #[cfg(all(feature = "std", target_has_atomic = "64"))]
+    pub fn with_instantiation_timeout(&self, timeout: core::time::Duration) -> InstancePre<T> {
+        InstancePre {
+            instantiation_timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
     /// Returns the underlying component that will be instantiated.
     pub fn component(&self) -> &Component {
         &self.component
@@ -1147,6 +1375,7 @@ impl<T: 'static> InstancePre<T> {
     /// was determined by the Linker.
     pub fn instance_type(&self) -> InstanceType<'_> {
         InstanceType {
+            component: self.component.id(),
             types: &self.component.types(),
             resources: &self.resource_types,
         }
@@ -1169,7 +1398,26 @@ impl<T: 'static> InstancePre<T> {
         store.0.set_async_required(self.asyncness);
         store.0.validate_sync_call()?;
 
-        vm::assert_ready(self._instantiate(store, Asyncness::No))
+        vm::assert_ready(self._instantiate(store, Asyncness::No, Instantiation::Embedder))
+    }
+
+    /// Same as [`Self::instantiate`], except that the resulting instance is
+    /// discarded, as by [`Component::warmup`] and [`Component::dry_run`].
+    ///
+    /// Such instances aren't reported to the engine's
+    /// [`component_instantiate_observer`](crate::Config::component_instantiate_observer)
+    /// nor counted by [`Component::live_instance_count`].
+    pub(crate) fn instantiate_throwaway(
+        &self,
+        mut store: impl AsContextMut<Data = T>,
+        kind: Instantiation,
+    ) -> Result<()> {
+        debug_assert!(kind != Instantiation::Embedder);
+        let store = store.as_context_mut();
+        store.0.set_async_required(self.asyncness);
+        store.0.validate_sync_call()?;
+        vm::assert_ready(self._instantiate(store, Asyncness::No, kind))?;
+        Ok(())
     }
     /// Performs the instantiation process into the store specified.
     ///
@@ -1178,22 +1426,44 @@ impl<T: 'static> InstancePre<T> {
     // TODO: needs more docs
     #[cfg(feature = "async")]
     pub async fn instantiate_async(&self, store: impl AsContextMut<Data = T>) -> Result<Instance> {
-        self._instantiate(store, Asyncness::Yes).await
+        self._instantiate(store, Asyncness::Yes, Instantiation::Embedder)
+            .await
     }
 
     async fn _instantiate(
         &self,
         mut store: impl AsContextMut<Data = T>,
         asyncness: Asyncness,
+        kind: Instantiation,
     ) -> Result<Instance> {
         let mut store = store.as_context_mut();
         store.0.set_async_required(self.asyncness);
+        #[cfg(all(feature = "std", target_has_atomic = "64"))]
+        let timeout = self.instantiation_timeout;
+        #[cfg(all(feature = "std", target_has_atomic = "64"))]
+        ensure!(
+            timeout.is_none() || store.engine().tunables().epoch_interruption,
+            "instantiation timeouts require `Config::epoch_interruption` to be enabled"
+        );
         store
             .engine()
             .allocator()
             .increment_component_instance_count()?;
+        store.0.component_data_mut().register_trap_hooks(
+            &self.component,
+            self.trap_recorder.as_ref(),
+            self.trap_sink.as_ref(),
+        );
         let mut instantiator = Instantiator::new(&self.component, store.0, &self.imports)?;
-        instantiator.run(&mut store, asyncness).await.map_err(|e| {
+        instantiator.run_start_functions = kind != Instantiation::ThrowawayWithoutStart;
+        instantiator.limits = self.limits.as_deref();
+
+        let result = {
+            #[cfg(all(feature = "std", target_has_atomic = "64"))]
+            let mut store = crate::store::InstantiationTimeout::new(&mut store, timeout);
+            instantiator.run(&mut store, asyncness).await
+        };
+        result.map_err(|e| {
             store
                 .engine()
                 .allocator()
@@ -1203,6 +1473,12 @@ impl<T: 'static> InstancePre<T> {
 
         let instance = Instance::from_wasmtime(store.0, instantiator.id);
         store.0.push_component_instance(instance);
+        if kind == Instantiation::Embedder {
+            instantiator.instance_mut(store.0).mark_live();
+            if let Some(observer) = &store.engine().config().component_instantiate_observer {
+                observer(&self.component, &instance);
+            }
+        }
         Ok(instance)
     }
 }
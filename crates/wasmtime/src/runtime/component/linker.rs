@@ -5,7 +5,8 @@ use crate::component::instance::RuntimeImport;
 use crate::component::matching::{InstanceType, TypeChecker};
 use crate::component::types;
 use crate::component::{
-    Component, ComponentNamedList, Instance, InstancePre, Lift, Lower, ResourceType, Val,
+    Component, ComponentNamedList, Instance, InstancePre, Lift, Lower, Resource, ResourceTable,
+    ResourceType, Val,
 };
 use crate::hash_map::HashMap;
 use crate::prelude::*;
@@ -163,6 +164,7 @@ impl<T: 'static> Linker<T> {
     fn typecheck<'a>(&'a self, component: &'a Component) -> Result<TypeChecker<'a>> {
         let mut cx = TypeChecker {
             engine: &self.engine,
+            component: component.id(),
             types: component.types(),
             strings: &self.strings,
             imported_resources: Default::default(),
@@ -187,6 +189,7 @@ impl<T: 'static> Linker<T> {
         Ok(types::Component::from(
             component.ty(),
             &InstanceType {
+                component: cx.component,
                 types: cx.types,
                 resources: &cx.imported_resources,
             },
@@ -746,6 +749,40 @@ impl<T: 'static> LinkerInstance<'_, T> {
         Ok(())
     }
 
+    /// Defines a new resource whose representation is an index into a
+    /// [`ResourceTable`] holding host state of type `R`.
+    ///
+    /// This is a convenience over [`Self::resource`] for the common case of
+    /// storing richer host state than a single `u32` for each resource. The
+    /// resource is defined with [`ResourceType::host::<R>`] and values of `R`
+    /// are added to the store with [`ResourceTable::push`], which yields a
+    /// [`Resource<R>`](crate::component::Resource) that can be passed to the
+    /// guest. The `table` closure projects the store's data to the table that
+    /// values are pushed into.
+    ///
+    /// When the guest drops an owned handle to this resource its entry is
+    /// deleted from the table, dropping the associated `R` value. As with
+    /// [`Self::resource`] entries are not removed when a host-owned resource
+    /// is destroyed; the host is expected to delete those itself.
+    ///
+    /// # Errors
+    ///
+    /// The destructor returns an error, trapping the guest, if the entry for
+    /// a dropped resource isn't present in the table or still has children.
+    pub fn resource_in_table<R>(
+        &mut self,
+        name: &str,
+        table: impl Fn(&mut T) -> &mut ResourceTable + Send + Sync + 'static,
+    ) -> Result<()>
+    where
+        R: Send + 'static,
+    {
+        self.resource(name, ResourceType::host::<R>(), move |mut store, rep| {
+            table(store.data_mut()).delete(Resource::<R>::new_own(rep))?;
+            Ok(())
+        })
+    }
+
     /// Identical to [`Self::resource`], except that it takes an async destructor.
     #[cfg(feature = "async")]
     pub fn resource_async<F>(&mut self, name: &str, ty: ResourceType, dtor: F) -> Result<()>
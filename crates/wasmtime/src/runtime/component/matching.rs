@@ -3,6 +3,7 @@ use crate::component::ResourceType;
 use crate::component::func::HostFunc;
 use crate::component::linker::{Definition, Strings};
 use crate::component::types::{FutureType, StreamType};
+use crate::runtime::vm::CompiledModuleId;
 use crate::runtime::vm::component::ComponentInstance;
 use crate::types::matching;
 use crate::{Engine, prelude::*};
@@ -16,6 +17,7 @@ use wasmtime_environ::component::{
 
 pub struct TypeChecker<'a> {
     pub engine: &'a Engine,
+    pub component: CompiledModuleId,
     pub types: &'a Arc<ComponentTypes>,
     pub strings: &'a Strings,
     pub imported_resources: Arc<PrimaryMap<ResourceIndex, ResourceType>>,
@@ -24,6 +26,7 @@ pub struct TypeChecker<'a> {
 #[derive(Copy, Clone)]
 #[doc(hidden)]
 pub struct InstanceType<'a> {
+    pub component: CompiledModuleId,
     pub types: &'a Arc<ComponentTypes>,
     pub resources: &'a Arc<PrimaryMap<ResourceIndex, ResourceType>>,
 }
@@ -168,6 +171,7 @@ impl TypeChecker<'_> {
 
     fn func(&self, expected: TypeFuncIndex, actual: &HostFunc) -> Result<()> {
         let instance_type = InstanceType {
+            component: self.component,
             types: self.types,
             resources: &self.imported_resources,
         };
@@ -189,6 +193,7 @@ impl Definition {
 impl<'a> InstanceType<'a> {
     pub fn new(instance: &'a ComponentInstance) -> InstanceType<'a> {
         InstanceType {
+            component: instance.component().id(),
             types: instance.component().types(),
             resources: instance.resource_types(),
         }
@@ -196,12 +201,12 @@ impl<'a> InstanceType<'a> {
 
     pub fn resource_type(&self, index: TypeResourceTableIndex) -> ResourceType {
         match self.types[index] {
-            TypeResourceTable::Concrete { ty, .. } => self
-                .resources
-                .get(ty)
-                .copied()
-                .unwrap_or_else(|| ResourceType::uninstantiated(&self.types, ty)),
-            TypeResourceTable::Abstract(ty) => ResourceType::abstract_(&self.types, ty),
+            TypeResourceTable::Concrete { ty, .. } => {
+                self.resources.get(ty).copied().unwrap_or_else(|| {
+                    ResourceType::uninstantiated(self.component, &self.types, ty)
+                })
+            }
+            TypeResourceTable::Abstract(ty) => ResourceType::abstract_(self.component, ty),
         }
     }
 
@@ -116,7 +116,14 @@ mod storage;
 pub(crate) mod store;
 pub mod types;
 mod values;
-pub use self::component::{Component, ComponentExportIndex};
+#[cfg(feature = "std")]
+pub use self::component::DeserializeFileOptions;
+pub use self::component::{
+    AbiSummary, BuildInfo, CanonicalOptions, Compatibility, Component, ComponentExportIndex,
+    ComponentMemoryUsage, ComponentResourceLimits, DetachedComponent, FrozenComponent, HostImport,
+    MemorySpec, ModuleGraph, ModuleGraphEdge, ModuleGraphNode, StringEncoding, TrampolineCounts,
+    TrapRecorder, TrapSnapshot, TrapSnapshotSink, TypeDiff, UnknownResourceReason,
+};
 #[cfg(feature = "component-model-async")]
 pub use self::concurrent::{
     Access, Accessor, AccessorTask, AsAccessor, Destination, DirectDestination, DirectSource,
@@ -1,12 +1,13 @@
 //! This module defines the `ResourceType` type in the public API of Wasmtime,
 //! which is all possible types of resources.
 
+use crate::ValType;
+use crate::runtime::vm::CompiledModuleId;
 use crate::runtime::vm::component::ComponentInstance;
 use crate::store::StoreId;
 use core::any::TypeId;
-use wasmtime_environ::component::{
-    AbstractResourceIndex, ComponentTypes, DefinedResourceIndex, ResourceIndex,
-};
+use wasmtime_environ::WasmValType;
+use wasmtime_environ::component::{AbstractResourceIndex, ComponentTypes, ResourceIndex};
 
 /// Representation of a resource type in the component model.
 ///
@@ -68,32 +69,107 @@ impl ResourceType {
     pub(crate) fn guest(
         store: StoreId,
         instance: &ComponentInstance,
-        id: DefinedResourceIndex,
+        component: CompiledModuleId,
+        types: &ComponentTypes,
+        index: ResourceIndex,
     ) -> ResourceType {
         ResourceType {
             kind: ResourceTypeKind::Guest {
                 store,
                 instance: instance as *const _ as usize,
-                id,
+                component,
+                index,
+                rep: types.resource_rep(index),
             },
         }
     }
 
-    pub(crate) fn uninstantiated(types: &ComponentTypes, index: ResourceIndex) -> ResourceType {
+    pub(crate) fn uninstantiated(
+        component: CompiledModuleId,
+        types: &ComponentTypes,
+        index: ResourceIndex,
+    ) -> ResourceType {
         ResourceType {
             kind: ResourceTypeKind::Uninstantiated {
-                component: types as *const _ as usize,
+                component,
                 index,
+                rep: types.resource_rep(index),
             },
         }
     }
 
-    pub(crate) fn abstract_(types: &ComponentTypes, index: AbstractResourceIndex) -> ResourceType {
+    pub(crate) fn abstract_(
+        component: CompiledModuleId,
+        index: AbstractResourceIndex,
+    ) -> ResourceType {
         ResourceType {
-            kind: ResourceTypeKind::Abstract {
-                component: types as *const _ as usize,
-                index,
-            },
+            kind: ResourceTypeKind::Abstract { component, index },
+        }
+    }
+
+    /// Returns whether `self` and `other` originate from the same resource
+    /// definition within a component.
+    ///
+    /// Each instantiation of a component produces fresh resource types for
+    /// the resources it defines, so two [`ResourceType`]s taken from two
+    /// instances of the same component compare as not equal. This method
+    /// instead compares the underlying definition, making it possible to group
+    /// together "the same logical resource" across instances. The type seen
+    /// through [`Component::component_type`] is also considered the same
+    /// definition as the types of its instantiations.
+    ///
+    /// Resource types which aren't defined by a component, such as host
+    /// resource types, are only the same definition as themselves.
+    ///
+    /// [`Component::component_type`]: crate::component::Component::component_type
+    pub fn same_definition(&self, other: &ResourceType) -> bool {
+        match (self.definition(), other.definition()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self == other,
+        }
+    }
+
+    /// Returns the core wasm representation of this resource, as declared
+    /// with `(rep ...)` in the component which defines it.
+    ///
+    /// This is `Some` for resources defined by a component, for example those
+    /// found through [`Component::exports`], and `None` for resources whose
+    /// representation isn't known, such as host resources created with
+    /// [`ResourceType::host`] and resources imported into a component.
+    ///
+    /// [`Component::exports`]: crate::component::Component::exports
+    pub fn rep(&self) -> Option<ValType> {
+        let rep = match self.kind {
+            ResourceTypeKind::Guest { rep, .. } | ResourceTypeKind::Uninstantiated { rep, .. } => {
+                rep?
+            }
+            ResourceTypeKind::Host(_)
+            | ResourceTypeKind::HostDynamic(_)
+            | ResourceTypeKind::Abstract { .. } => return None,
+        };
+        // Validation only permits numeric representations, so no engine is
+        // needed to describe reference types here.
+        match rep {
+            WasmValType::I32 => Some(ValType::I32),
+            WasmValType::I64 => Some(ValType::I64),
+            WasmValType::F32 => Some(ValType::F32),
+            WasmValType::F64 => Some(ValType::F64),
+            WasmValType::V128 => Some(ValType::V128),
+            WasmValType::Ref(_) => None,
+        }
+    }
+
+    fn definition(&self) -> Option<(CompiledModuleId, ResourceIndex)> {
+        match self.kind {
+            ResourceTypeKind::Guest {
+                component, index, ..
+            }
+            | ResourceTypeKind::Uninstantiated {
+                component, index, ..
+            } => Some((component, index)),
+            ResourceTypeKind::Host(_)
+            | ResourceTypeKind::HostDynamic(_)
+            | ResourceTypeKind::Abstract { .. } => None,
         }
     }
 
@@ -122,21 +198,28 @@ enum ResourceTypeKind {
         // that this guest corresponds to. It's used to distinguish different
         // instantiations of the same component within the store.
         instance: usize,
-        id: DefinedResourceIndex,
+        // Same as `component` in `Uninstantiated` below, and along with
+        // `index` identifies the definition of this resource.
+        component: CompiledModuleId,
+        index: ResourceIndex,
+        // The `(rep ...)` declared for this resource, which is determined by
+        // `component` and `index`.
+        rep: Option<WasmValType>,
     },
     Uninstantiated {
-        // Like `instance` in `Guest` above this is a pointer and is used to
-        // distinguish between two components. Technically susceptible to ABA
-        // issues but the consequence is a nonexistent resource would be equal
-        // to a new resource so there's not really any issue with that.
-        component: usize,
+        // The unique ID of the component which defines this resource, used to
+        // distinguish between two components. This can't be the address of
+        // the component's type information since that may be shared between
+        // components with identical types.
+        component: CompiledModuleId,
         index: ResourceIndex,
+        rep: Option<WasmValType>,
     },
     /// The type of this resource is considered "abstract" meaning that it
     /// doesn't actually correspond to anything at runtime but instead it just
     /// needs to be kept distinct from everything but itself.
     Abstract {
-        component: usize,
+        component: CompiledModuleId,
         index: AbstractResourceIndex,
     },
 }
@@ -1,6 +1,10 @@
+use crate::hash_map::HashMap;
 use crate::prelude::*;
+use crate::runtime::component::component::NameResolver;
 use crate::runtime::component::concurrent::ConcurrentState;
-use crate::runtime::component::{HostResourceData, Instance};
+use crate::runtime::component::{
+    Component, HostResourceData, Instance, TrapRecorder, TrapSnapshot, TrapSnapshotSink,
+};
 use crate::runtime::vm;
 #[cfg(feature = "component-model-async")]
 use crate::runtime::vm::VMStore;
@@ -8,7 +12,8 @@ use crate::runtime::vm::component::{
     CallContext, ComponentInstance, HandleTable, OwnedComponentInstance,
 };
 use crate::store::{StoreData, StoreId, StoreOpaque};
-use crate::{Engine, StoreContextMut};
+use crate::{Engine, FrameInfo, StoreContextMut, Trap};
+use alloc::sync::Arc;
 use core::pin::Pin;
 use wasmtime_environ::PrimaryMap;
 use wasmtime_environ::component::RuntimeComponentInstanceIndex;
@@ -23,6 +28,11 @@ pub struct ComponentStoreData {
     /// Whether an instance belonging to this store has trapped.
     trapped: bool,
 
+    /// The hooks invoked when instances of components in this store trap,
+    /// keyed by the address of the components' compiled code. Components
+    /// without any hook have no entry.
+    trap_hooks: HashMap<usize, TrapHooks>,
+
     /// Total number of component instances in this store, used to track
     /// resources in the instance allocator.
     num_component_instances: usize,
@@ -64,6 +74,7 @@ impl ComponentStoreData {
         ComponentStoreData {
             instances: Default::default(),
             trapped: false,
+            trap_hooks: HashMap::new(),
             num_component_instances: 0,
             component_host_table: Default::default(),
             host_resource_data: Default::default(),
@@ -144,6 +155,87 @@ impl ComponentStoreData {
         }
     }
 
+    /// Registers the hooks invoked when instances of `component` in this
+    /// store trap.
+    ///
+    /// This is called whenever an instance of `component` is created, and a
+    /// `recorder` or `sink` replaces the one previously registered for
+    /// `component`, if any. The name resolver of `component` is also captured
+    /// here so that traps don't need to take its lock.
+    pub(crate) fn register_trap_hooks(
+        &mut self,
+        component: &Component,
+        recorder: Option<&Arc<dyn TrapRecorder>>,
+        sink: Option<&Arc<dyn TrapSnapshotSink>>,
+    ) {
+        let code = Arc::as_ptr(component.engine_code()).addr();
+        let name_resolver = component.name_resolver();
+        if recorder.is_none() && sink.is_none() && name_resolver.is_none() {
+            return;
+        }
+        let hooks = self.trap_hooks.entry(code).or_insert_with(|| TrapHooks {
+            component: component.clone(),
+            recorder: None,
+            sink: None,
+            name_resolver: None,
+        });
+        if let Some(recorder) = recorder {
+            hooks.recorder = Some(recorder.clone());
+        }
+        if let Some(sink) = sink {
+            hooks.sink = Some(sink.clone());
+        }
+        hooks.name_resolver = name_resolver;
+    }
+
+    /// Returns whether any hook is registered to be invoked when an instance
+    /// in this store traps.
+    #[inline]
+    pub(crate) fn has_trap_hooks(&self) -> bool {
+        !self.trap_hooks.is_empty()
+    }
+
+    fn trap_hooks(&self, frame: &FrameInfo) -> Option<&TrapHooks> {
+        let code = Arc::as_ptr(frame.module().engine_code()).addr();
+        self.trap_hooks.get(&code)
+    }
+
+    /// Notifies the trap recorder of the component that `frame` belongs to, if
+    /// an instance of that component was created in this store with a
+    /// recorder configured.
+    pub(crate) fn record_trap(&self, frame: &FrameInfo, trap: Option<Trap>) {
+        if let Some(recorder) = self.trap_hooks(frame).and_then(|h| h.recorder.as_ref()) {
+            recorder.record(frame, trap);
+        }
+    }
+
+    /// Hands a snapshot of a trap to the trap snapshot sink of the component
+    /// that the innermost of `frames` belongs to, if an instance of that
+    /// component was created in this store with a sink configured.
+    ///
+    /// The sink doesn't return if it's invoked.
+    pub(crate) fn capture_trap(&self, frames: &[FrameInfo], trap: Option<Trap>, pc: Option<usize>) {
+        let Some(frame) = frames.first() else {
+            return;
+        };
+        if let Some(sink) = self.trap_hooks(frame).and_then(|h| h.sink.as_ref()) {
+            sink.capture(&TrapSnapshot { trap, pc, frames });
+        }
+    }
+
+    /// Resolves the name of the function executing in `frame` with the name
+    /// resolver of the component that `frame` belongs to, if one was installed
+    /// when an instance of that component was created in this store.
+    pub(crate) fn resolve_func_name(&self, frame: &FrameInfo) -> Option<String> {
+        let hooks = self.trap_hooks(frame)?;
+        let resolver = hooks.name_resolver.as_ref()?;
+        let module = hooks.component.static_module_index(frame.module())?;
+        resolver(
+            module,
+            wasmtime_environ::FuncIndex::from_u32(frame.func_index()),
+        )
+    }
+
     pub fn decrement_allocator_resources(&mut self, allocator: &dyn vm::InstanceAllocator) {
         for _ in 0..self.num_component_instances {
             allocator.decrement_component_instance_count();
@@ -151,6 +243,15 @@ impl ComponentStoreData {
     }
 }
 
+/// The hooks invoked when an instance of a component traps, see
+/// [`ComponentStoreData::register_trap_hooks`].
+struct TrapHooks {
+    component: Component,
+    recorder: Option<Arc<dyn TrapRecorder>>,
+    sink: Option<Arc<dyn TrapSnapshotSink>>,
+    name_resolver: Option<Arc<NameResolver>>,
+}
+
 /// A type used to represent an allocated `ComponentInstance` located within a
 /// store.
 ///
@@ -268,7 +369,6 @@ impl StoreOpaque {
         self.store_data_mut().components.trapped = true;
     }
 
-    #[cfg(feature = "component-model-async")]
     pub(crate) fn component_data(&self) -> &ComponentStoreData {
         &self.store_data().components
     }
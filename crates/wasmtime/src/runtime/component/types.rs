@@ -3,6 +3,8 @@
 #[cfg(feature = "component-model-async")]
 use crate::component::ComponentType;
 use crate::component::matching::InstanceType;
+use crate::prelude::*;
+use crate::runtime::vm::CompiledModuleId;
 use crate::{Engine, ExternType, FuncType};
 use alloc::sync::Arc;
 use core::fmt;
@@ -39,6 +41,7 @@ pub use crate::component::resources::ResourceType;
 #[derive(Clone)]
 struct Handle<T> {
     index: T,
+    component: CompiledModuleId,
     types: Arc<ComponentTypes>,
     resources: Arc<PrimaryMap<ResourceIndex, ResourceType>>,
 }
@@ -47,6 +50,7 @@ impl<T> Handle<T> {
     fn new(index: T, ty: &InstanceType<'_>) -> Handle<T> {
         Handle {
             index,
+            component: ty.component,
             types: ty.types.clone(),
             resources: ty.resources.clone(),
         }
@@ -54,6 +58,7 @@ impl<T> Handle<T> {
 
     fn instance(&self) -> InstanceType<'_> {
         InstanceType {
+            component: self.component,
             types: &self.types,
             resources: &self.resources,
         }
@@ -68,11 +73,14 @@ impl<T> Handle<T> {
         T: PartialEq + Copy,
     {
         (self.index == other.index
+            && self.component == other.component
             && Arc::ptr_eq(&self.types, &other.types)
             && Arc::ptr_eq(&self.resources, &other.resources))
             || type_check(
                 &TypeChecker {
+                    a_component: self.component,
                     a_types: &self.types,
+                    b_component: other.component,
                     b_types: &other.types,
                     a_resource: &self.resources,
                     b_resource: &other.resources,
@@ -93,8 +101,10 @@ impl<T: fmt::Debug> fmt::Debug for Handle<T> {
 
 /// Type checker between two `Handle`s
 struct TypeChecker<'a> {
+    a_component: CompiledModuleId,
     a_types: &'a ComponentTypes,
     a_resource: &'a PrimaryMap<ResourceIndex, ResourceType>,
+    b_component: CompiledModuleId,
     b_types: &'a ComponentTypes,
     b_resource: &'a PrimaryMap<ResourceIndex, ResourceType>,
 }
@@ -182,7 +192,7 @@ impl TypeChecker<'_> {
             // Abstract resource types are only the same if they have the same
             // index and come from the exact same component.
             (TypeResourceTable::Abstract(a), TypeResourceTable::Abstract(b)) => {
-                core::ptr::eq(self.a_types, self.b_types) && a == b
+                self.a_component == self.b_component && a == b
             }
             (TypeResourceTable::Abstract(_), _) => false,
         }
@@ -578,8 +588,10 @@ impl FutureType {
         let my_payload = self.0.types[self.0.index].payload.as_ref();
         match (my_payload, payload) {
             (Some(a), Some(b)) => TypeChecker {
+                a_component: self.0.component,
                 a_types: &self.0.types,
                 a_resource: &self.0.resources,
+                b_component: ty.component,
                 b_types: ty.types,
                 b_resource: ty.resources,
             }
@@ -635,8 +647,10 @@ impl StreamType {
         let my_payload = self.0.types[self.0.index].payload.as_ref();
         match (my_payload, payload) {
             (Some(a), Some(b)) => TypeChecker {
+                a_component: self.0.component,
                 a_types: &self.0.types,
                 a_resource: &self.0.resources,
+                b_component: ty.component,
                 b_types: ty.types,
                 b_resource: ty.resources,
             }
@@ -978,6 +992,10 @@ impl Module {
 }
 
 /// Component type
+///
+/// This is a handle to type information shared with the component it was
+/// created from, and the type of each import and export is created lazily as
+/// it's accessed.
 #[derive(Clone, Debug)]
 pub struct Component(Handle<TypeComponentIndex>);
 
@@ -1028,9 +1046,80 @@ impl Component {
         })
     }
 
+    /// Returns the export found at the dotted `path`, such as
+    /// `"wasi:http/types@0.2.0.fields"`, if it exists.
+    ///
+    /// Each segment of `path` names an export of the component or of the
+    /// instance found so far, and segments are separated with `.`. Export
+    /// names may themselves contain `.` and `/`, as in versioned interface
+    /// names, so at each step the longest export name which matches the start
+    /// of the remaining path is used. A `path` without any separator looks up
+    /// a top-level export just like [`Component::get_export`].
+    ///
+    /// Returns `None` if any segment isn't exported or if a segment other than
+    /// the last one doesn't refer to an instance.
+    pub fn get_export_path(&self, engine: &Engine, path: &str) -> Option<ComponentItem> {
+        let names = self.0.types[self.0.index]
+            .exports
+            .keys()
+            .map(|s| s.as_str());
+        let (name, mut rest) = split_export_path(names, path)?;
+        let mut item = self.get_export(engine, name)?;
+        while let Some(path) = rest {
+            let ComponentItem::ComponentInstance(instance) = &item else {
+                return None;
+            };
+            let names = instance.exports(engine).map(|(name, _)| name);
+            let (name, next) = split_export_path(names, path)?;
+            item = instance.get_export(engine, name)?;
+            rest = next;
+        }
+        Some(item)
+    }
+
+    /// Compares this component type against `other`, returning the imports
+    /// and exports that were added, removed, or changed.
+    ///
+    /// `self` is treated as the older version of the two types, so for
+    /// example imports present in `other` but not `self` are reported as
+    /// added. See [`TypeDiff`] for how the types of imports and exports are
+    /// compared.
+    ///
+    /// [`TypeDiff`]: crate::component::TypeDiff
+    pub fn diff(&self, engine: &Engine, other: &Component) -> crate::component::TypeDiff {
+        let mut diff = crate::component::TypeDiff::default();
+        let mut resources = Vec::new();
+        for (name, ty) in self.imports(engine) {
+            match other.get_import(engine, name) {
+                Some(new_ty) if ty.equivalent(engine, &new_ty, &mut resources) => {}
+                Some(_) => diff.changed_imports.push(name.to_string()),
+                None => diff.removed_imports.push(name.to_string()),
+            }
+        }
+        for (name, _) in other.imports(engine) {
+            if self.get_import(engine, name).is_none() {
+                diff.added_imports.push(name.to_string());
+            }
+        }
+        for (name, ty) in self.exports(engine) {
+            match other.get_export(engine, name) {
+                Some(new_ty) if ty.equivalent(engine, &new_ty, &mut resources) => {}
+                Some(_) => diff.changed_exports.push(name.to_string()),
+                None => diff.removed_exports.push(name.to_string()),
+            }
+        }
+        for (name, _) in other.exports(engine) {
+            if self.get_export(engine, name).is_none() {
+                diff.added_exports.push(name.to_string());
+            }
+        }
+        diff
+    }
+
     #[doc(hidden)]
     pub fn instance_type(&self) -> InstanceType<'_> {
         InstanceType {
+            component: self.0.component,
             types: &self.0.types,
             resources: &self.0.resources,
         }
@@ -1066,6 +1155,33 @@ impl ComponentInstance {
             )
         })
     }
+
+    /// Iterates over the instances exported from this component instance.
+    ///
+    /// This is a filtered form of [`ComponentInstance::exports`] which can be
+    /// used to walk nested interface hierarchies.
+    pub fn instances<'a>(
+        &'a self,
+        engine: &'a Engine,
+    ) -> impl Iterator<Item = (&'a str, ComponentInstance)> {
+        self.exports(engine).filter_map(|(name, item)| match item {
+            ComponentItem::ComponentInstance(instance) => Some((name, instance)),
+            _ => None,
+        })
+    }
+
+    /// Iterates over the functions exported from this component instance.
+    ///
+    /// This is a filtered form of [`ComponentInstance::exports`].
+    pub fn funcs<'a>(
+        &'a self,
+        engine: &'a Engine,
+    ) -> impl Iterator<Item = (&'a str, ComponentFunc)> {
+        self.exports(engine).filter_map(|(name, item)| match item {
+            ComponentItem::ComponentFunc(func) => Some((name, func)),
+            _ => None,
+        })
+    }
 }
 
 /// Type of an item contained within the component
@@ -1119,12 +1235,14 @@ impl ComponentItem {
                         Some(ty) => *ty,
 
                         // This resource type was not substituted.
-                        None => ResourceType::uninstantiated(&ty.types, resource_index),
+                        None => {
+                            ResourceType::uninstantiated(ty.component, &ty.types, resource_index)
+                        }
                     };
                     Self::Resource(ty)
                 }
                 TypeResourceTable::Abstract(resource_index) => {
-                    Self::Resource(ResourceType::abstract_(&ty.types, resource_index))
+                    Self::Resource(ResourceType::abstract_(ty.component, resource_index))
                 }
             },
         }
@@ -1143,4 +1261,168 @@ impl ComponentItem {
             Export::Type(idx) => Self::from(engine, idx, ty),
         }
     }
+
+    /// Returns whether `self` and `other` describe the same type.
+    ///
+    /// Items with different type information, for example from two separately
+    /// compiled components, are compared structurally. The resources defined
+    /// by two different components are never the same type, so resources
+    /// themselves are always considered equivalent to one another and each
+    /// such pair is recorded in `resources`. Resources used within function
+    /// signatures and other types are then considered the same if they're the
+    /// same type or if they were previously paired up in `resources`.
+    pub(crate) fn equivalent(
+        &self,
+        engine: &Engine,
+        other: &ComponentItem,
+        resources: &mut Vec<(ResourceType, ResourceType)>,
+    ) -> bool {
+        match (self, other) {
+            (Self::ComponentFunc(a), Self::ComponentFunc(b)) => {
+                a.async_() == b.async_()
+                    && a.params().len() == b.params().len()
+                    && a.params()
+                        .zip(b.params())
+                        .all(|((a_name, a), (b_name, b))| {
+                            a_name == b_name && types_equivalent(&a, &b, resources)
+                        })
+                    && a.results().len() == b.results().len()
+                    && a.results()
+                        .zip(b.results())
+                        .all(|(a, b)| types_equivalent(&a, &b, resources))
+            }
+            (Self::CoreFunc(a), Self::CoreFunc(b)) => a.matches(b) && b.matches(a),
+            (Self::Module(a), Self::Module(b)) => {
+                a.imports(engine).len() == b.imports(engine).len()
+                    && a.imports(engine)
+                        .zip(b.imports(engine))
+                        .all(|(a, b)| a.0 == b.0 && extern_types_equivalent(&a.1, &b.1))
+                    && a.exports(engine).len() == b.exports(engine).len()
+                    && a.exports(engine).all(|(name, a)| {
+                        b.exports(engine)
+                            .find(|(n, _)| *n == name)
+                            .is_some_and(|(_, b)| extern_types_equivalent(&a, &b))
+                    })
+            }
+            (Self::Component(a), Self::Component(b)) => {
+                names_and_items_equivalent(
+                    engine,
+                    a.imports(engine),
+                    |name| b.get_import(engine, name),
+                    resources,
+                ) && b.imports(engine).len() == a.imports(engine).len()
+                    && names_and_items_equivalent(
+                        engine,
+                        a.exports(engine),
+                        |name| b.get_export(engine, name),
+                        resources,
+                    )
+                    && b.exports(engine).len() == a.exports(engine).len()
+            }
+            (Self::ComponentInstance(a), Self::ComponentInstance(b)) => {
+                names_and_items_equivalent(
+                    engine,
+                    a.exports(engine),
+                    |name| b.get_export(engine, name),
+                    resources,
+                ) && b.exports(engine).len() == a.exports(engine).len()
+            }
+            (Self::Type(a), Self::Type(b)) => types_equivalent(a, b, resources),
+            (Self::Resource(a), Self::Resource(b)) => {
+                if a != b && !resources.contains(&(*a, *b)) {
+                    resources.push((*a, *b));
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Finds the longest of `names` which `path` is either equal to or starts
+/// with followed by a `.`, returning that prefix of `path` along with the
+/// remainder after the `.`, if any.
+fn split_export_path<'a, 'p>(
+    names: impl Iterator<Item = &'a str>,
+    path: &'p str,
+) -> Option<(&'p str, Option<&'p str>)> {
+    let (len, rest) = names
+        .filter_map(|name| {
+            let rest = path.strip_prefix(name)?;
+            if rest.is_empty() {
+                Some((name.len(), None))
+            } else {
+                Some((name.len(), Some(rest.strip_prefix('.')?)))
+            }
+        })
+        .max_by_key(|(len, _)| *len)?;
+    Some((&path[..len], rest))
+}
+
+fn names_and_items_equivalent<'a>(
+    engine: &Engine,
+    mut items: impl Iterator<Item = (&'a str, ComponentItem)>,
+    lookup: impl Fn(&str) -> Option<ComponentItem>,
+    resources: &mut Vec<(ResourceType, ResourceType)>,
+) -> bool {
+    items.all(|(name, a)| lookup(name).is_some_and(|b| a.equivalent(engine, &b, resources)))
+}
+
+/// Same as `Type`'s `PartialEq` implementation, except that the pairs of
+/// resources in `resources` are considered the same type.
+fn types_equivalent(a: &Type, b: &Type, resources: &[(ResourceType, ResourceType)]) -> bool {
+    let eq = |a: &Type, b: &Type| types_equivalent(a, b, resources);
+    let opt_eq = |a: Option<Type>, b: Option<Type>| match (a, b) {
+        (Some(a), Some(b)) => eq(&a, &b),
+        (None, None) => true,
+        (Some(_), None) | (None, Some(_)) => false,
+    };
+    match (a, b) {
+        (Type::List(a), Type::List(b)) => eq(&a.ty(), &b.ty()),
+        (Type::Record(a), Type::Record(b)) => {
+            a.fields().len() == b.fields().len()
+                && a.fields()
+                    .zip(b.fields())
+                    .all(|(a, b)| a.name == b.name && eq(&a.ty, &b.ty))
+        }
+        (Type::Tuple(a), Type::Tuple(b)) => {
+            a.types().len() == b.types().len() && a.types().zip(b.types()).all(|(a, b)| eq(&a, &b))
+        }
+        (Type::Variant(a), Type::Variant(b)) => {
+            a.cases().len() == b.cases().len()
+                && a.cases()
+                    .zip(b.cases())
+                    .all(|(a, b)| a.name == b.name && opt_eq(a.ty, b.ty))
+        }
+        (Type::Option(a), Type::Option(b)) => eq(&a.ty(), &b.ty()),
+        (Type::Result(a), Type::Result(b)) => opt_eq(a.ok(), b.ok()) && opt_eq(a.err(), b.err()),
+        (Type::Own(a), Type::Own(b)) | (Type::Borrow(a), Type::Borrow(b)) => {
+            a == b || resources.contains(&(*a, *b))
+        }
+        (Type::Future(a), Type::Future(b)) => opt_eq(a.ty(), b.ty()),
+        (Type::Stream(a), Type::Stream(b)) => opt_eq(a.ty(), b.ty()),
+        _ => a == b,
+    }
+}
+
+fn extern_types_equivalent(a: &ExternType, b: &ExternType) -> bool {
+    match (a, b) {
+        (ExternType::Func(a), ExternType::Func(b)) => a.matches(b) && b.matches(a),
+        (ExternType::Global(a), ExternType::Global(b)) => {
+            a.mutability() == b.mutability()
+                && a.content().matches(b.content())
+                && b.content().matches(a.content())
+        }
+        (ExternType::Table(a), ExternType::Table(b)) => {
+            a.minimum() == b.minimum()
+                && a.maximum() == b.maximum()
+                && a.element().matches(b.element())
+                && b.element().matches(a.element())
+        }
+        (ExternType::Memory(a), ExternType::Memory(b)) => a == b,
+        (ExternType::Tag(a), ExternType::Tag(b)) => {
+            a.ty().matches(b.ty()) && b.ty().matches(a.ty())
+        }
+        _ => false,
+    }
 }
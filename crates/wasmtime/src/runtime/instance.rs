@@ -253,22 +253,53 @@ impl Instance {
             unsafe { Instance::new_raw(store, limiter.as_mut(), module, imports, asyncness).await? }
         };
         if let Some(start) = start {
-            if asyncness == Asyncness::No {
-                instance.start_raw(store, start)?;
-            } else {
-                #[cfg(feature = "async")]
-                {
-                    store
-                        .on_fiber(|store| instance.start_raw(store, start))
-                        .await??;
-                }
-                #[cfg(not(feature = "async"))]
-                unreachable!();
-            }
+            instance.run_start(store, start, asyncness).await?;
         }
         Ok(instance)
     }
 
+    /// Same as `new_started`, except that the `start` function of `module`, if
+    /// any, is returned instead of being run.
+    ///
+    /// The start function may later be run with `Instance::run_start`.
+    ///
+    /// This function's unsafety is the same as `Instance::new_raw`.
+    #[cfg(feature = "component-model")]
+    pub(crate) async unsafe fn new_unstarted<T>(
+        store: &mut StoreContextMut<'_, T>,
+        module: &Module,
+        imports: Imports<'_>,
+        asyncness: Asyncness,
+    ) -> Result<(Instance, Option<FuncIndex>)> {
+        let (mut limiter, store) = store.0.resource_limiter_and_store_opaque();
+        // SAFETY: the safety contract of `new_raw` is the same as this
+        // function.
+        unsafe { Instance::new_raw(store, limiter.as_mut(), module, imports, asyncness).await }
+    }
+
+    /// Runs the `start` function of this instance, on a fiber if `asyncness`
+    /// requires it.
+    pub(crate) async fn run_start<T>(
+        &self,
+        store: &mut StoreContextMut<'_, T>,
+        start: FuncIndex,
+        asyncness: Asyncness,
+    ) -> Result<()> {
+        if asyncness == Asyncness::No {
+            self.start_raw(store, start)?;
+        } else {
+            #[cfg(feature = "async")]
+            {
+                store
+                    .on_fiber(|store| self.start_raw(store, start))
+                    .await??;
+            }
+            #[cfg(not(feature = "async"))]
+            unreachable!();
+        }
+        Ok(())
+    }
+
     /// Internal function to create an instance which doesn't have its `start`
     /// function run yet.
     ///
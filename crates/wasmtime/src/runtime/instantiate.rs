@@ -26,6 +26,9 @@ pub struct CompiledModule {
     index: Arc<CompiledFunctionsTable>,
     /// Sorted list, by function index, of names we have for this module.
     func_names: Vec<FunctionName>,
+    /// Sorted list, by function index, of names attached after compilation
+    /// for functions which have no name in `func_names`.
+    attached_func_names: crate::sync::OnceLock<Vec<(FuncIndex, String)>>,
 }
 
 impl CompiledModule {
@@ -59,6 +62,7 @@ impl CompiledModule {
             meta: info.meta,
             index,
             func_names: info.func_names,
+            attached_func_names: Default::default(),
         };
         ret.register_profiling(profiler)?;
 
@@ -98,8 +102,12 @@ impl CompiledModule {
     /// Looks up the `name` section name for the function index `idx`, if one
     /// was specified in the original wasm module.
     pub fn func_name(&self, idx: FuncIndex) -> Option<&str> {
-        // Find entry for `idx`, if present.
-        let i = self.func_names.binary_search_by_key(&idx, |n| n.idx).ok()?;
+        // Find entry for `idx`, if present, falling back to attached names.
+        let Ok(i) = self.func_names.binary_search_by_key(&idx, |n| n.idx) else {
+            let attached = self.attached_func_names.get()?;
+            let i = attached.binary_search_by_key(&idx, |(i, _)| *i).ok()?;
+            return Some(&attached[i].1);
+        };
         let name = &self.func_names[i];
 
         // Here we `unwrap` the `from_utf8` but this can theoretically be a
@@ -110,6 +118,22 @@ impl CompiledModule {
         Some(str::from_utf8(&data[name.offset as usize..][..name.len as usize]).unwrap())
     }
 
+    /// Attaches names for functions which did not have a name in the original
+    /// wasm module, to be returned from [`CompiledModule::func_name`].
+    ///
+    /// Returns an error if names were already attached to this module.
+    pub fn attach_func_names(&self, mut names: Vec<(FuncIndex, String)>) -> Result<()> {
+        names.retain(|(idx, _)| self.func_name(*idx).is_none());
+        names.sort_by_key(|(idx, _)| *idx);
+        let mut attached = false;
+        self.attached_func_names.get_or_init(|| {
+            attached = true;
+            names
+        });
+        ensure!(attached, "names have already been attached to this module");
+        Ok(())
+    }
+
     /// Returns an iterator over all functions defined within this module with
     /// their index and their offset in the underlying code image.
     #[inline]
@@ -208,6 +232,21 @@ impl CompiledModule {
             .expect("defined function should be present")
     }
 
+    /// Returns the names and contents of the custom sections retained from the
+    /// original wasm module, in their original order.
+    ///
+    /// This is empty unless `Config::retain_custom_sections` was enabled when
+    /// this module was compiled.
+    #[cfg(feature = "component-model")]
+    pub fn custom_sections(&self) -> impl ExactSizeIterator<Item = (&str, &[u8])> + '_ {
+        let data = self.engine_code.wasm_custom_sections();
+        self.meta.custom_sections.iter().map(move |(name, range)| {
+            let start = usize::try_from(range.start).unwrap();
+            let end = usize::try_from(range.end).unwrap();
+            (name.as_str(), &data[start..end])
+        })
+    }
+
     /// Creates a new symbolication context which can be used to further
     /// symbolicate stack traces.
     ///
@@ -13,7 +13,7 @@ use fxprof_processed_profile::{
 use std::ops::Range;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use wasmtime_environ::demangle_function_name_or_index;
+use wasmtime_environ::{FuncIndex, demangle_function_name_or_index};
 
 // TODO: collect more data
 // - On non-Windows, measure thread-local CPU usage between events with
@@ -112,6 +112,18 @@ impl GuestProfiler {
         module_name: &str,
         interval: Duration,
         modules: impl IntoIterator<Item = (String, Module)>,
+    ) -> Result<Self> {
+        Self::new_with_names(engine, module_name, interval, modules, &|_, _| None)
+    }
+
+    /// Same as [`GuestProfiler::new`], but with `resolve_name` consulted for
+    /// functions which have no entry in their module's name section.
+    fn new_with_names(
+        engine: &Engine,
+        module_name: &str,
+        interval: Duration,
+        modules: impl IntoIterator<Item = (String, Module)>,
+        resolve_name: &dyn Fn(&Module, FuncIndex) -> Option<String>,
     ) -> Result<Self> {
         // Check that guest debugging is not enabled. The
         // instrumentation would make profiling results unreliable,
@@ -144,7 +156,7 @@ impl GuestProfiler {
                     start..end
                 };
 
-                module_symbols(name, &module).map(|lib| {
+                module_symbols(name, &module, resolve_name).map(|lib| {
                     let libhandle = profile.add_lib(lib);
                     ProfiledModule {
                         module,
@@ -189,7 +201,13 @@ impl GuestProfiler {
             .static_modules()
             .map(|m| (m.name().unwrap_or("<unknown>").to_string(), m.clone()))
             .chain(extra_modules);
-        Self::new(engine, component_name, interval, modules)
+        Self::new_with_names(
+            engine,
+            component_name,
+            interval,
+            modules,
+            &|module, func| component.resolve_func_name(module, func),
+        )
     }
 
     /// Add a sample to the profile. This function collects a backtrace from
@@ -261,7 +279,11 @@ impl GuestProfiler {
     }
 }
 
-fn module_symbols(name: String, module: &Module) -> Option<LibraryInfo> {
+fn module_symbols(
+    name: String,
+    module: &Module,
+    resolve_name: &dyn Fn(&Module, FuncIndex) -> Option<String>,
+) -> Option<LibraryInfo> {
     let compiled = module.compiled_module();
     let symbols = Vec::from_iter(
         module
@@ -270,10 +292,18 @@ fn module_symbols(name: String, module: &Module) -> Option<LibraryInfo> {
             .map(|defined_idx| {
                 let loc = compiled.func_loc(defined_idx);
                 let func_idx = compiled.module().func_index(defined_idx);
+                let resolved;
+                let func_name = match compiled.func_name(func_idx) {
+                    Some(name) => Some(name),
+                    None => {
+                        resolved = resolve_name(module, func_idx);
+                        resolved.as_deref()
+                    }
+                };
                 let mut name = String::new();
                 demangle_function_name_or_index(
                     &mut name,
-                    compiled.func_name(func_idx),
+                    func_name,
                     defined_idx.as_u32() as usize,
                 )
                 .unwrap();
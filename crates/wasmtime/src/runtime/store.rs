@@ -249,6 +249,11 @@ pub struct StoreInner<T: 'static> {
     epoch_deadline_behavior:
         Option<Box<dyn FnMut(StoreContextMut<T>) -> Result<UpdateDeadline> + Send + Sync>>,
 
+    /// The deadline of the component instantiation in progress, if it was
+    /// configured with `InstancePre::with_instantiation_timeout`.
+    #[cfg(all(feature = "component-model", feature = "std", target_has_atomic = "64"))]
+    instantiation_deadline: Option<InstantiationDeadline>,
+
     /// The user's `T` data.
     ///
     /// Don't actually access it via this field, however! Use the
@@ -387,6 +392,18 @@ enum CallHookInner<T: 'static> {
     },
 }
 
+/// The deadline of a component instantiation configured with
+/// `InstancePre::with_instantiation_timeout`.
+#[cfg(all(feature = "component-model", feature = "std", target_has_atomic = "64"))]
+struct InstantiationDeadline {
+    /// The time at which instantiation times out.
+    at: std::time::Instant,
+    /// The configured timeout, for error messages.
+    timeout: core::time::Duration,
+    /// The store's own epoch deadline, restored once instantiation completes.
+    epoch_deadline: u64,
+}
+
 /// What to do after returning from a callback when the engine epoch reaches
 /// the deadline for a Store during execution of a function using that store.
 #[non_exhaustive]
@@ -785,6 +802,8 @@ impl<T> Store<T> {
             call_hook: None,
             #[cfg(target_has_atomic = "64")]
             epoch_deadline_behavior: None,
+            #[cfg(all(feature = "component-model", feature = "std", target_has_atomic = "64"))]
+            instantiation_deadline: None,
             data_no_provenance: ManuallyDrop::new(data),
             #[cfg(feature = "debug")]
             debug_handler: None,
@@ -2776,6 +2795,23 @@ unsafe impl<T> VMStore for StoreInner<T> {
 
     #[cfg(target_has_atomic = "64")]
     fn new_epoch_updated_deadline(&mut self) -> Result<UpdateDeadline> {
+        // While a component with an instantiation timeout is being
+        // instantiated the timeout is checked on every tick, and the
+        // configured behavior is only invoked once the store's own deadline is
+        // reached.
+        #[cfg(all(feature = "component-model", feature = "std", target_has_atomic = "64"))]
+        if let Some(deadline) = &self.instantiation_deadline {
+            if std::time::Instant::now() >= deadline.at {
+                bail!(
+                    "component instantiation timed out after {:?}",
+                    deadline.timeout
+                );
+            }
+            if self.inner.engine().current_epoch() < deadline.epoch_deadline {
+                return Ok(UpdateDeadline::Continue(1));
+            }
+        }
+
         // Temporarily take the configured behavior to avoid mutably borrowing
         // multiple times.
         let mut behavior = self.epoch_deadline_behavior.take();
@@ -2786,6 +2822,29 @@ unsafe impl<T> VMStore for StoreInner<T> {
 
         // Put back the original behavior which was replaced by `take`.
         self.epoch_deadline_behavior = behavior;
+
+        // Record the deadline requested by the configured behavior to restore
+        // it once instantiation completes, and keep checking the timeout on
+        // every tick in the meantime.
+        #[cfg(all(feature = "component-model", feature = "std", target_has_atomic = "64"))]
+        if self.instantiation_deadline.is_some() {
+            let current_epoch = self.inner.engine().current_epoch();
+            let deadline = self.instantiation_deadline.as_mut().unwrap();
+            let mut delay = |delta: u64| {
+                deadline.epoch_deadline = current_epoch.saturating_add(delta);
+                1
+            };
+            return Ok(match update? {
+                UpdateDeadline::Interrupt => UpdateDeadline::Interrupt,
+                UpdateDeadline::Continue(delta) => UpdateDeadline::Continue(delay(delta)),
+                #[cfg(feature = "async")]
+                UpdateDeadline::Yield(delta) => UpdateDeadline::Yield(delay(delta)),
+                #[cfg(feature = "async")]
+                UpdateDeadline::YieldCustom(delta, future) => {
+                    UpdateDeadline::YieldCustom(delay(delta), future)
+                }
+            });
+        }
         update
     }
 
@@ -2805,7 +2864,76 @@ unsafe impl<T> VMStore for StoreInner<T> {
     }
 }
 
+/// Enforces the timeout of a component instantiation on a store until it's
+/// dropped, at which point the store's own epoch deadline is restored.
+///
+/// This is dropped even if the instantiation is cancelled, for example by
+/// dropping the future of `InstancePre::instantiate_async`, so the timeout
+/// never outlives the instantiation.
+#[cfg(all(feature = "component-model", feature = "std", target_has_atomic = "64"))]
+pub(crate) struct InstantiationTimeout<'a, 'b, T: 'static>(&'a mut StoreContextMut<'b, T>);
+
+#[cfg(all(feature = "component-model", feature = "std", target_has_atomic = "64"))]
+impl<'a, 'b, T> InstantiationTimeout<'a, 'b, T> {
+    /// Starts enforcing `timeout`, if any, on `store`.
+    pub(crate) fn new(
+        store: &'a mut StoreContextMut<'b, T>,
+        timeout: Option<core::time::Duration>,
+    ) -> Self {
+        if let Some(timeout) = timeout {
+            store.0.begin_instantiation_timeout(timeout);
+        }
+        InstantiationTimeout(store)
+    }
+}
+
+#[cfg(all(feature = "component-model", feature = "std", target_has_atomic = "64"))]
+impl<'b, T> Deref for InstantiationTimeout<'_, 'b, T> {
+    type Target = StoreContextMut<'b, T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+#[cfg(all(feature = "component-model", feature = "std", target_has_atomic = "64"))]
+impl<T> DerefMut for InstantiationTimeout<'_, '_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0
+    }
+}
+
+#[cfg(all(feature = "component-model", feature = "std", target_has_atomic = "64"))]
+impl<T> Drop for InstantiationTimeout<'_, '_, T> {
+    fn drop(&mut self) {
+        self.0.0.end_instantiation_timeout();
+    }
+}
+
 impl<T> StoreInner<T> {
+    /// Starts enforcing `timeout` on the component instantiation about to be
+    /// performed in this store, until
+    /// [`StoreInner::end_instantiation_timeout`] is called.
+    #[cfg(all(feature = "component-model", feature = "std", target_has_atomic = "64"))]
+    fn begin_instantiation_timeout(&mut self, timeout: core::time::Duration) {
+        self.instantiation_deadline = Some(InstantiationDeadline {
+            at: std::time::Instant::now() + timeout,
+            timeout,
+            epoch_deadline: self.inner.get_epoch_deadline(),
+        });
+        self.inner.set_epoch_deadline(1);
+    }
+
+    /// Stops enforcing the timeout started with
+    /// [`StoreInner::begin_instantiation_timeout`], restoring the store's own
+    /// epoch deadline.
+    #[cfg(all(feature = "component-model", feature = "std", target_has_atomic = "64"))]
+    fn end_instantiation_timeout(&mut self) {
+        if let Some(deadline) = self.instantiation_deadline.take() {
+            *self.inner.vm_store_context.epoch_deadline.get_mut() = deadline.epoch_deadline;
+        }
+    }
+
     #[cfg(target_has_atomic = "64")]
     fn epoch_deadline_trap(&mut self) {
         self.epoch_deadline_behavior = None;
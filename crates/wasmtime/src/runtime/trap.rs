@@ -122,12 +122,28 @@ pub(crate) fn from_runtime_box(
     };
 
     if let Some(bt) = backtrace {
-        let bt = WasmBacktrace::from_captured(
+        #[cfg_attr(not(feature = "component-model"), allow(unused_mut))]
+        let mut bt = WasmBacktrace::from_captured(
             store,
             bt,
             pc,
             store.engine().config().wasm_backtrace_max_frames,
         );
+        #[cfg(feature = "component-model")]
+        if store.component_data().has_trap_hooks() {
+            for frame in bt.wasm_trace.iter_mut() {
+                if frame.func_name.is_none() {
+                    frame.func_name = store.component_data().resolve_func_name(frame);
+                }
+            }
+            let trap = error.downcast_ref::<Trap>().copied();
+            store
+                .component_data()
+                .capture_trap(&bt.wasm_trace, trap, pc);
+            if let Some(frame) = bt.wasm_trace.first() {
+                store.component_data().record_trap(frame, trap);
+            }
+        }
         if !bt.wasm_trace.is_empty() {
             error = error.context(bt);
         }
@@ -415,6 +415,15 @@ pub fn host_page_size() -> usize {
     };
 }
 
+/// Returns how many of the host pages overlapping `range` are currently
+/// resident in memory.
+#[cfg(all(has_virtual_memory, feature = "component-model"))]
+pub fn resident_pages(range: core::ops::Range<*const u8>) -> Result<usize> {
+    let start = range.start.addr() & !(host_page_size() - 1);
+    let ptr = range.start.wrapping_sub(range.start.addr() - start);
+    Ok(sys::vm::resident_pages(ptr, range.end.addr() - start)?)
+}
+
 /// Result of `Memory::atomic_wait32` and `Memory::atomic_wait64`
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum WaitResult {
@@ -154,6 +154,10 @@ pub struct ComponentInstance {
     /// duration of the lifetime of this instance.
     imports: Arc<PrimaryMap<RuntimeImportIndex, RuntimeImport>>,
 
+    /// Whether this instance is counted by `Component::live_instance_count`,
+    /// see `ComponentInstance::mark_live`.
+    live: bool,
+
     /// Self-pointer back to `Store<T>` and its functions.
     store: VMStoreRawPtr,
 
@@ -231,6 +235,14 @@ unsafe impl VmSafe for VMLowering {}
 #[repr(align(16))]
 pub struct VMComponentContext;
 
+impl Drop for ComponentInstance {
+    fn drop(&mut self) {
+        if self.live {
+            self.component.instance_dropped();
+        }
+    }
+}
+
 impl ComponentInstance {
     /// Converts the `vmctx` provided into a `ComponentInstance` and runs the
     /// provided closure with that instance.
@@ -343,6 +355,7 @@ impl ComponentInstance {
             component: component.clone(),
             resource_types,
             imports: imports.clone(),
+            live: false,
             store: VMStoreRawPtr(store),
             vmctx: OwnedVMContext::new(),
         })?;
@@ -920,6 +933,21 @@ impl ComponentInstance {
         self.instances[idx]
     }
 
+    /// Counts this instance in `Component::live_instance_count` until it's
+    /// dropped.
+    ///
+    /// This isn't done for throwaway instances which are never handed to the
+    /// embedder, such as those of `Component::dry_run`.
+    pub fn mark_live(self: Pin<&mut Self>) {
+        // SAFETY: we've chosen the `Pin` guarantee of `Self` to not apply to
+        // the flag.
+        let this = unsafe { self.get_unchecked_mut() };
+        if !this.live {
+            this.live = true;
+            this.component.instance_created();
+        }
+    }
+
     fn instances_mut(self: Pin<&mut Self>) -> &mut PrimaryMap<RuntimeInstanceIndex, InstanceId> {
         // SAFETY: we've chosen the `Pin` guarantee of `Self` to not apply to
         // the map returned.
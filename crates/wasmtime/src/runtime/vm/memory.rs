@@ -415,6 +415,17 @@ impl Memory {
         }
     }
 
+    /// Prevents this memory from growing beyond `max_bytes`.
+    ///
+    /// This has no effect on shared memories whose maximum size is fixed at
+    /// creation.
+    pub fn limit_maximum(&mut self, max_bytes: usize) {
+        match self {
+            Memory::Local(mem) => mem.limit_maximum(max_bytes),
+            Memory::Shared(_) => {}
+        }
+    }
+
     /// Return a `VMMemoryDefinition` for exposing the memory to compiled wasm code.
     pub fn vmmemory(&self) -> VMMemoryDefinition {
         match self {
@@ -577,6 +588,16 @@ impl LocalMemory {
         self.ty.page_size()
     }
 
+    /// Lowers the maximum size of this memory, if necessary, such that it
+    /// can't grow beyond `max_bytes`.
+    pub fn limit_maximum(&mut self, max_bytes: usize) {
+        let max_pages = u64::try_from(max_bytes).unwrap_or(u64::MAX) >> self.ty.page_size_log2;
+        self.ty.limits.max = Some(match self.ty.limits.max {
+            Some(max) => max.min(max_pages),
+            None => max_pages,
+        });
+    }
+
     /// Grows a memory by `delta_pages`.
     ///
     /// This performs the necessary checks on the growth before delegating to
@@ -53,6 +53,11 @@ pub fn get_page_size() -> usize {
     unsafe { capi::wasmtime_page_size() }
 }
 
+#[cfg(feature = "component-model")]
+pub fn resident_pages(_ptr: *const u8, _len: usize) -> Result<usize> {
+    bail!("querying page residency is not supported on this platform")
+}
+
 pub fn decommit_behavior() -> DecommitBehavior {
     DecommitBehavior::Zero
 }
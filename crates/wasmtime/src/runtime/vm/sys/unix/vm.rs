@@ -80,6 +80,18 @@ pub fn get_page_size() -> usize {
     unsafe { libc::sysconf(libc::_SC_PAGESIZE).try_into().unwrap() }
 }
 
+/// Returns how many of the pages within `ptr..ptr + len` are resident in
+/// memory, where `ptr` is page-aligned.
+#[cfg(feature = "component-model")]
+pub fn resident_pages(ptr: *const u8, len: usize) -> io::Result<usize> {
+    let mut residency = vec![0u8; len.div_ceil(get_page_size())];
+    let rc = unsafe { libc::mincore(ptr.cast_mut().cast(), len, residency.as_mut_ptr().cast()) };
+    if rc != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(residency.iter().filter(|page| **page & 1 != 0).count())
+}
+
 pub fn decommit_behavior() -> DecommitBehavior {
     if cfg!(target_os = "linux") {
         DecommitBehavior::RestoreOriginalMapping
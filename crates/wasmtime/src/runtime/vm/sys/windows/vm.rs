@@ -52,6 +52,14 @@ pub fn get_page_size() -> usize {
     }
 }
 
+#[cfg(feature = "component-model")]
+pub fn resident_pages(_ptr: *const u8, _len: usize) -> io::Result<usize> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "querying page residency is not supported on this platform",
+    ))
+}
+
 pub fn decommit_behavior() -> DecommitBehavior {
     DecommitBehavior::Zero
 }
@@ -533,6 +533,31 @@ impl Table {
         }
     }
 
+    /// Lowers the runtime maximum of this table, if necessary, such that it
+    /// can't grow beyond `max` elements.
+    ///
+    /// The current size of the table must not already exceed `max`.
+    pub fn limit_maximum(&mut self, max: usize) {
+        assert!(self.size() <= max);
+        fn shrink<T>(data: &mut SendSyncPtr<[T]>, max: usize) {
+            let len = data.len().min(max);
+            *data = SendSyncPtr::new(NonNull::slice_from_raw_parts(
+                data.as_non_null().cast::<T>(),
+                len,
+            ));
+        }
+        match self {
+            Table::Static(StaticTable::Func(StaticFuncTable { data, .. })) => shrink(data, max),
+            Table::Static(StaticTable::GcRef(StaticGcRefTable { data, .. })) => shrink(data, max),
+            Table::Static(StaticTable::Cont(StaticContTable { data, .. })) => shrink(data, max),
+            Table::Dynamic(DynamicTable::Func(DynamicFuncTable { maximum, .. }))
+            | Table::Dynamic(DynamicTable::GcRef(DynamicGcRefTable { maximum, .. }))
+            | Table::Dynamic(DynamicTable::Cont(DynamicContTable { maximum, .. })) => {
+                *maximum = Some(maximum.map_or(max, |m| m.min(max)));
+            }
+        }
+    }
+
     /// Fill `table[dst..dst + len]` with `val`.
     ///
     /// Returns a trap error on out-of-bounds accesses.
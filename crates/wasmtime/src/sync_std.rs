@@ -19,6 +19,11 @@ impl<T> OnceLock<T> {
         OnceLock(OnceCell::new())
     }
 
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        self.0.get()
+    }
+
     #[inline]
     pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
         self.0.get_or_init(f)
@@ -12,6 +12,7 @@ mod r#async;
 mod async_dynamic;
 mod bindgen;
 mod call_hook;
+mod component;
 mod dynamic;
 mod func;
 mod import;
@@ -0,0 +1,2342 @@
+#![cfg(not(miri))]
+
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use wasmtime::component::*;
+use wasmtime::{Config, Engine, FrameInfo, OptLevel, Result, Store, Strategy, Trap, WasmBacktrace};
+use wasmtime_environ::component::StaticModuleIndex;
+
+#[test]
+fn from_reader_matches_from_binary() {
+    let engine = super::engine();
+    let wat = "(component (core module (func (export \"f\"))))";
+    let wasm = wat::parse_str(wat).unwrap();
+    let component = Component::from_reader(&engine, &wasm[..]).unwrap();
+    let expected = Component::from_binary(&engine, &wasm).unwrap();
+    assert_eq!(
+        component.serialize().unwrap(),
+        expected.serialize().unwrap()
+    );
+    assert!(Component::from_reader(&engine, wat.as_bytes()).is_err());
+
+    // Short reads and interruptions are retried until EOF.
+    struct Trickle<'a>(&'a [u8], bool);
+    impl std::io::Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.1 = !self.1;
+            if self.1 {
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+            let n = self.0.len().min(buf.len()).min(3);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+    let component = Component::from_reader(&engine, Trickle(&wasm, false)).unwrap();
+    assert_eq!(
+        component.serialize().unwrap(),
+        expected.serialize().unwrap()
+    );
+
+    struct Broken;
+    impl std::io::Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+    }
+    let err = match Component::from_reader(&engine, Broken) {
+        Ok(_) => panic!("expected an error"),
+        Err(e) => e,
+    };
+    assert!(format!("{err:?}").contains("failed to read component"));
+    assert!(err.downcast_ref::<std::io::Error>().is_some());
+}
+
+#[test]
+fn import_canonical_options() {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "f" (func $f (param "s" string)))
+                (import "i" (instance $i
+                    (export "g" (func))
+                ))
+                (core module $m
+                    (memory (export "memory") 1)
+                    (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+                        unreachable)
+                )
+                (core instance $m (instantiate $m))
+                (core func $f (canon lower (func $f)
+                    (memory $m "memory")
+                    (realloc (func $m "realloc"))
+                    string-encoding=utf16))
+                (core func $g (canon lower (func $i "g")))
+                (core module $n
+                    (import "" "f" (func (param i32 i32)))
+                    (import "" "g" (func))
+                )
+                (core instance (instantiate $n
+                    (with "" (instance
+                        (export "f" (func $f))
+                        (export "g" (func $g))
+                    ))
+                ))
+            )
+        "#,
+    )
+    .unwrap();
+
+    let f = component.import_canonical_options("f").unwrap();
+    assert_eq!(f.string_encoding, StringEncoding::Utf16);
+    assert!(f.memory);
+    assert!(f.realloc);
+    assert!(!f.post_return);
+    assert!(!f.async_);
+
+    let g = component.import_canonical_options("i#g").unwrap();
+    assert_eq!(g.string_encoding, StringEncoding::Utf8);
+    assert!(!g.memory);
+
+    assert!(component.import_canonical_options("i").is_none());
+    assert!(component.import_canonical_options("missing").is_none());
+}
+
+#[test]
+fn exported_instances() {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m
+                    (func (export "f"))
+                )
+                (core instance $m (instantiate $m))
+                (func $f (canon lift (core func $m "f")))
+                (instance $nested (export "g" (func $f)))
+                (instance $a
+                    (export "f" (func $f))
+                    (export "nested" (instance $nested))
+                )
+                (export "a" (instance $a))
+                (export "f" (func $f))
+            )
+        "#,
+    )
+    .unwrap();
+
+    let instances = component.exported_instances(&engine).collect::<Vec<_>>();
+    assert_eq!(instances.len(), 1);
+    let (name, a) = &instances[0];
+    assert_eq!(*name, "a");
+    assert_eq!(a.funcs(&engine).map(|(n, _)| n).collect::<Vec<_>>(), ["f"]);
+    let nested = a.instances(&engine).collect::<Vec<_>>();
+    assert_eq!(nested.len(), 1);
+    assert_eq!(nested[0].0, "nested");
+    assert_eq!(
+        nested[0]
+            .1
+            .funcs(&engine)
+            .map(|(n, _)| n)
+            .collect::<Vec<_>>(),
+        ["g"]
+    );
+}
+
+#[test]
+fn original_wasm() {
+    let wasm = wat::parse_str("(component (core module))").unwrap();
+
+    let engine = super::engine();
+    let component = Component::new(&engine, &wasm).unwrap();
+    assert!(component.original_wasm().is_none());
+
+    let mut config = Config::new();
+    config.preserve_wasm_source(true);
+    let engine = Engine::new(&config).unwrap();
+    let component = Component::new(&engine, &wasm).unwrap();
+    assert_eq!(component.original_wasm(), Some(&wasm[..]));
+
+    let bytes = component.serialize().unwrap();
+    let component = unsafe { Component::deserialize(&engine, &bytes).unwrap() };
+    assert_eq!(component.original_wasm(), Some(&wasm[..]));
+}
+
+#[test]
+fn imported_interfaces() {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "a:b/c" (instance
+                    (export "f" (func))
+                ))
+                (import "f" (func))
+                (import "a:b/d" (instance))
+            )
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        component.imported_interfaces().collect::<Vec<_>>(),
+        ["a:b/c", "a:b/d"]
+    );
+}
+
+#[test]
+fn dry_run() {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "f" (func $f))
+                (core func $f (canon lower (func $f)))
+                (core module $m
+                    (import "" "f" (func $f))
+                    (func $start call $f)
+                    (start $start)
+                )
+                (core instance (instantiate $m
+                    (with "" (instance (export "f" (func $f))))
+                ))
+            )
+        "#,
+    )
+    .unwrap();
+    component.dry_run().unwrap();
+
+    let mut config = Config::new();
+    config.allocation_strategy(
+        wasmtime::PoolingAllocationConfig::default()
+            .total_memories(0)
+            .clone(),
+    );
+    let pooling = Engine::new(&config).unwrap();
+    let component = Component::new(
+        &pooling,
+        "(component (core module $m (memory 1)) (core instance (instantiate $m)))",
+    )
+    .unwrap();
+    assert!(component.dry_run().is_err());
+}
+
+#[test]
+fn export_resource_type() {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (type $t (resource (rep i32)))
+                (export "t" (type $t))
+                (core module $m (func (export "f")))
+                (core instance $m (instantiate $m))
+                (func (export "f") (canon lift (core func $m "f")))
+            )
+        "#,
+    )
+    .unwrap();
+    let t = component.export_resource_type(&engine, "t").unwrap();
+    assert!(matches!(
+        component.component_type().get_export(&engine, "t"),
+        Some(types::ComponentItem::Resource(ty)) if ty == t
+    ));
+    assert!(component.export_resource_type(&engine, "f").is_none());
+    assert!(component.export_resource_type(&engine, "missing").is_none());
+}
+
+#[test]
+fn resource_type_rep() {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "r" (type $r (sub resource)))
+                (type $t (resource (rep i32)))
+                (export "r" (type $r))
+                (export "t" (type $t))
+            )
+        "#,
+    )
+    .unwrap();
+    let exports = component.exports().collect::<Vec<_>>();
+    let rep = |name: &str| match exports.iter().find(|(n, _)| *n == name) {
+        Some((_, types::ComponentItem::Resource(ty))) => ty.rep(),
+        _ => panic!("missing resource export {name}"),
+    };
+    assert!(matches!(rep("t"), Some(wasmtime::ValType::I32)));
+    assert!(rep("r").is_none());
+    assert!(types::ResourceType::host::<u32>().rep().is_none());
+}
+
+#[test]
+fn total_imports() {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "f" (func))
+                (import "a:b/c" (instance
+                    (export "r" (type (sub resource)))
+                    (type $u32 u32)
+                    (export "t" (type (eq $u32)))
+                    (export "f" (func))
+                    (export "nested" (instance
+                        (export "g" (func))
+                        (export "h" (func))
+                    ))
+                ))
+                (import "a:b/empty" (instance))
+            )
+        "#,
+    )
+    .unwrap();
+    assert_eq!(component.total_imports(), 5);
+
+    let component = Component::new(&engine, "(component)").unwrap();
+    assert_eq!(component.total_imports(), 0);
+}
+
+#[test]
+fn uses_memory64() {
+    let mut config = Config::new();
+    config.wasm_memory64(true);
+    let engine = Engine::new(&config).unwrap();
+
+    let component = Component::new(&engine, "(component (core module (memory i64 1)))").unwrap();
+    assert!(component.uses_memory64());
+
+    let component = Component::new(&engine, "(component (core module (memory 1)))").unwrap();
+    assert!(!component.uses_memory64());
+
+    let component = Component::new(&engine, "(component)").unwrap();
+    assert!(!component.uses_memory64());
+}
+
+#[test]
+fn with_limits() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m
+                    (memory 1)
+                    (table 1 funcref)
+                    (func (export "grow-memory") (param i32) (result i32)
+                        (memory.grow (local.get 0)))
+                    (func (export "grow-table") (param i32) (result i32)
+                        (table.grow (ref.null func) (local.get 0)))
+                )
+                (core instance $i (instantiate $m))
+                (func (export "grow-memory") (param "n" u32) (result s32)
+                    (canon lift (core func $i "grow-memory")))
+                (func (export "grow-table") (param "n" u32) (result s32)
+                    (canon lift (core func $i "grow-table")))
+            )
+        "#,
+    )?;
+    let linker = Linker::<()>::new(&engine);
+    let pre = linker.instantiate_pre(&component)?;
+    assert!(pre.limits().is_none());
+
+    let limits = ComponentResourceLimits::new()
+        .memory_size(2 << 16)
+        .table_elements(2);
+    let limited = pre.with_limits(limits);
+    assert!(limited.limits().is_some());
+
+    let mut store = Store::new(&engine, ());
+    let instance = limited.instantiate(&mut store)?;
+    let grow_memory = instance.get_typed_func::<(u32,), (i32,)>(&mut store, "grow-memory")?;
+    let grow_table = instance.get_typed_func::<(u32,), (i32,)>(&mut store, "grow-table")?;
+    for (func, limit) in [(grow_memory, 2), (grow_table, 2)] {
+        let mut grow = |n| -> Result<i32> { Ok(func.call(&mut store, (n,))?.0) };
+        assert_eq!(grow(limit - 1)?, 1);
+        assert_eq!(grow(1)?, -1);
+    }
+
+    // The original `InstancePre` is unaffected by the limits.
+    let instance = pre.instantiate(&mut store)?;
+    let grow_memory = instance.get_typed_func::<(u32,), (i32,)>(&mut store, "grow-memory")?;
+    assert_eq!(grow_memory.call(&mut store, (2,))?, (1,));
+
+    for limits in [
+        ComponentResourceLimits::new().memory_size(0),
+        ComponentResourceLimits::new().table_elements(0),
+        ComponentResourceLimits::new().instances(0),
+        ComponentResourceLimits::new().memories(0),
+        ComponentResourceLimits::new().tables(0),
+    ] {
+        assert!(pre.with_limits(limits).instantiate(&mut store).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn diff_type() {
+    let engine = super::engine();
+    let old_wat = r#"
+            (component
+                (import "a" (func))
+                (import "b" (func))
+                (core module $m
+                    (func (export "f") (param i32))
+                    (func (export "g"))
+                )
+                (core instance $i (instantiate $m))
+                (func (export "f") (param "x" u32) (canon lift (core func $i "f")))
+                (func (export "g") (canon lift (core func $i "g")))
+            )
+        "#;
+    let old = Component::new(&engine, old_wat).unwrap();
+    let new = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "a" (func))
+                (import "b" (func (param "x" u32)))
+                (import "c" (func))
+                (core module $m
+                    (func (export "f") (param i64))
+                    (func (export "h"))
+                )
+                (core instance $i (instantiate $m))
+                (func (export "f") (param "x" u64) (canon lift (core func $i "f")))
+                (func (export "h") (canon lift (core func $i "h")))
+            )
+        "#,
+    )
+    .unwrap();
+
+    let diff = old.diff_type(&engine, &new);
+    assert_eq!(diff.added_imports, ["c"]);
+    assert!(diff.removed_imports.is_empty());
+    assert_eq!(diff.changed_imports, ["b"]);
+    assert_eq!(diff.added_exports, ["h"]);
+    assert_eq!(diff.removed_exports, ["g"]);
+    assert_eq!(diff.changed_exports, ["f"]);
+    assert!(!diff.is_empty());
+
+    let diff = new.diff_type(&engine, &old);
+    assert!(diff.added_imports.is_empty());
+    assert_eq!(diff.removed_imports, ["c"]);
+
+    assert!(old.diff_type(&engine, &old).is_empty());
+    let copy = Component::new(&engine, old_wat).unwrap();
+    assert!(old.diff_type(&engine, &copy).is_empty());
+}
+
+#[test]
+fn component_type_diff_resources() {
+    let engine = super::engine();
+    let wat = |param: &str| {
+        format!(
+            r#"
+                (component
+                    (import "host" (instance
+                        (export "r" (type (sub resource)))
+                        (export "s" (type (sub resource)))
+                    ))
+                    (alias export 0 "r" (type $r))
+                    (alias export 0 "s" (type $s))
+                    (import "f" (func (param "x" {param})))
+                )
+            "#
+        )
+    };
+    let a = Component::new(&engine, wat("(own $r)")).unwrap();
+    let b = Component::new(&engine, wat("(own $r)")).unwrap();
+    let c = Component::new(&engine, wat("(own $s)")).unwrap();
+    let d = Component::new(&engine, wat("(list (borrow $r))")).unwrap();
+
+    let a_ty = a.component_type();
+    assert!(a_ty.diff(&engine, &b.component_type()).is_empty());
+    assert!(a.diff_type(&engine, &b).is_empty());
+
+    let diff = a_ty.diff(&engine, &c.component_type());
+    assert_eq!(diff.changed_imports, ["f"]);
+    let diff = a_ty.diff(&engine, &d.component_type());
+    assert_eq!(diff.changed_imports, ["f"]);
+
+    let bytes = postcard::to_allocvec(&diff).unwrap();
+    let decoded: TypeDiff = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, diff);
+}
+
+#[test]
+fn export_module() {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "imported" (core module $i))
+                (core module $m (func (export "f")))
+                (export "m" (core module $m))
+                (export "i" (core module $i))
+                (type $t u32)
+                (export "t" (type $t))
+            )
+        "#,
+    )
+    .unwrap();
+
+    let module = component.export_module("m").unwrap();
+    assert_eq!(
+        module.exports().map(|e| e.name()).collect::<Vec<_>>(),
+        ["f"]
+    );
+    assert!(component.export_module("i").is_none());
+    assert!(component.export_module("t").is_none());
+    assert!(component.export_module("missing").is_none());
+}
+
+#[test]
+fn uses_floating_point() {
+    let engine = super::engine();
+    for (body, expected) in [
+        ("i32.const 1 drop", false),
+        ("local.get 0 drop", false),
+        ("f32.const 1 drop", false),
+        ("i32.const 0 f32.load drop", false),
+        ("i32.const 0 f32.reinterpret_i32 drop", false),
+        ("local.get 0 f64.neg drop", false),
+        ("local.get 0 i32.trunc_f64_s drop", false),
+        ("local.get 0 local.get 0 f64.add drop", true),
+        ("local.get 0 f32.demote_f64 drop", true),
+        ("v128.const i64x2 0 0 f64x2.sqrt drop", true),
+    ] {
+        let wat = format!("(component (core module (memory 1) (func (param f64) {body})))");
+        let component = Component::new(&engine, &wat).unwrap();
+        assert_eq!(component.uses_floating_point(), expected, "{body}");
+    }
+
+    let component = Component::new(&engine, "(component)").unwrap();
+    assert!(!component.uses_floating_point());
+}
+
+#[test]
+fn component_instantiate_observer() -> Result<()> {
+    let count = Arc::new(AtomicUsize::new(0));
+    let mut config = Config::new();
+    let observed = count.clone();
+    config.component_instantiate_observer(move |component, _instance| {
+        assert!(component.export_module("m").is_some());
+        observed.fetch_add(1, Ordering::SeqCst);
+    });
+    let engine = Engine::new(&config)?;
+    let component = Component::new(
+        &engine,
+        "(component (core module $m) (export \"m\" (core module $m)))",
+    )?;
+
+    component.dry_run()?;
+    component.warmup()?;
+    assert_eq!(count.load(Ordering::SeqCst), 0);
+
+    let linker = Linker::<()>::new(&engine);
+    let mut store = Store::new(&engine, ());
+    linker.instantiate(&mut store, &component)?;
+    linker
+        .instantiate_pre(&component)?
+        .instantiate(&mut store)?;
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    Ok(())
+}
+
+#[test]
+fn detach() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(&engine, "(component (core module (memory 1)))")?;
+    let expected = component.serialize()?;
+    let detached = component.detach()?;
+    assert_eq!(detached.bytes(), expected);
+    drop(engine);
+
+    let engine = super::engine();
+    let component = detached.attach(&engine)?;
+    assert!(Engine::same(component.engine(), &engine));
+    assert_eq!(component.serialize()?, expected);
+
+    let mut config = Config::new();
+    config.memory_reservation(1 << 20);
+    assert!(detached.attach(&Engine::new(&config)?).is_err());
+    Ok(())
+}
+
+#[test]
+fn memory_config_summary() -> Result<()> {
+    let mut config = Config::new();
+    config.wasm_memory64(true);
+    let engine = Engine::new(&config)?;
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module (memory 1))
+                (core module (memory i64 1))
+            )
+        "#,
+    )?;
+    let summary = component.memory_config_summary();
+    let lines = summary.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("module 0 memory 0: "));
+    assert!(lines[0].ends_with("index64 false"));
+    assert!(lines[1].starts_with("module 1 memory 0: dynamic bounds checks"));
+    assert!(lines[1].ends_with("index64 true"));
+
+    let empty = Component::new(&engine, "(component)")?;
+    assert_eq!(empty.memory_config_summary(), "");
+    Ok(())
+}
+
+#[test]
+fn exports_recursive() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m
+                    (func (export "read"))
+                    (func (export "write"))
+                )
+                (core instance $i (instantiate $m))
+                (func $read (canon lift (core func $i "read")))
+                (func $write (canon lift (core func $i "write")))
+                (instance $streams
+                    (export "read" (func $read))
+                    (export "write" (func $write))
+                )
+                (instance $io (export "wasi:io/streams" (instance $streams)))
+                (export "io" (instance $io))
+                (export "f" (func $read))
+            )
+        "#,
+    )?;
+    let paths = component
+        .exports_recursive(&engine)
+        .map(|(path, item)| {
+            let is_func = matches!(item, types::ComponentItem::ComponentFunc(_));
+            (path.join("/"), is_func)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            ("io".to_string(), false),
+            ("io/wasi:io/streams".to_string(), false),
+            ("io/wasi:io/streams/read".to_string(), true),
+            ("io/wasi:io/streams/write".to_string(), true),
+            ("f".to_string(), true),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn compiled_with() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(&engine, "(component)")?;
+    assert_eq!(component.compiled_with(), Strategy::Cranelift);
+
+    if cfg!(target_arch = "x86_64") {
+        let mut config = Config::new();
+        config.strategy(Strategy::Winch);
+        let engine = Engine::new(&config)?;
+        let component = Component::new(&engine, "(component)")?;
+        assert_eq!(component.compiled_with(), Strategy::Winch);
+    }
+    Ok(())
+}
+
+#[test]
+fn abi_summary() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "a" (func $a))
+                (import "b" (func $b (param "x" string)))
+                (core module $m
+                    (memory (export "memory") 1)
+                    (func (export "realloc") (param i32 i32 i32 i32) (result i32) i32.const 0)
+                    (func (export "f") (result i32) i32.const 0)
+                    (func (export "post") (param i32))
+                    (func (export "g"))
+                )
+                (core instance $i (instantiate $m))
+                (core func $a' (canon lower (func $a)))
+                (core func $b' (canon lower (func $b)
+                    (memory $i "memory") (realloc (func $i "realloc"))))
+                (core module $n
+                    (import "" "a" (func))
+                    (import "" "b" (func (param i32 i32)))
+                )
+                (core instance (instantiate $n
+                    (with "" (instance
+                        (export "a" (func $a'))
+                        (export "b" (func $b'))
+                    ))
+                ))
+                (func (export "f") (result string)
+                    (canon lift (core func $i "f") (memory $i "memory")
+                        (post-return (func $i "post"))))
+                (func (export "g") (canon lift (core func $i "g")))
+            )
+        "#,
+    )?;
+    assert_eq!(
+        component.abi_summary(),
+        AbiSummary {
+            lifts: 2,
+            lowers: 2,
+            reallocs: 1,
+            post_returns: 1,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn entry_points() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m
+                    (func (export "run") (result i32) i32.const 0)
+                    (func (export "nested"))
+                )
+                (core instance $i (instantiate $m))
+                (func $run (result u32) (canon lift (core func $i "run")))
+                (func $nested (canon lift (core func $i "nested")))
+                (instance $inst (export "nested" (func $nested)))
+                (export "run" (func $run))
+                (export "inst" (instance $inst))
+                (export "m" (core module $m))
+            )
+        "#,
+    )?;
+    let entry_points = component.entry_points(&engine).collect::<Vec<_>>();
+    assert_eq!(entry_points.len(), 1);
+    let (name, func) = &entry_points[0];
+    assert_eq!(name, "run");
+    assert_eq!(func.params().len(), 0);
+    assert_eq!(func.results().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn assert_compatible_with() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(&engine, "(component)")?;
+    component.assert_compatible_with(&engine)?;
+    component.assert_compatible_with(&Engine::default())?;
+
+    let mut config = Config::new();
+    config.memory_guard_size(0);
+    let err = component
+        .assert_compatible_with(&Engine::new(&config)?)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("memory guard size"), "{err}");
+
+    config.wasm_simd(false).wasm_relaxed_simd(false);
+    let err = component
+        .assert_compatible_with(&Engine::new(&config)?)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("multiple incompatible settings"), "{err}");
+    assert!(err.contains("memory guard size"), "{err}");
+    assert!(err.contains("`simd`"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn artifact_compatible() -> Result<()> {
+    let engine = super::engine();
+    let bytes = Component::new(&engine, "(component)")?.serialize()?;
+    assert_eq!(
+        Component::artifact_compatible(&engine, &bytes)?,
+        Compatibility::Compatible
+    );
+
+    let mut config = Config::new();
+    config.memory_guard_size(0);
+    let Compatibility::Incompatible { reason } =
+        Component::artifact_compatible(&Engine::new(&config)?, &bytes)?
+    else {
+        panic!("expected an incompatible artifact");
+    };
+    assert!(reason.contains("memory guard size"), "{reason}");
+
+    let mut config = Config::new();
+    config.module_version(wasmtime::ModuleVersionStrategy::Custom("other".to_string()))?;
+    let Compatibility::Incompatible { reason } =
+        Component::artifact_compatible(&Engine::new(&config)?, &bytes)?
+    else {
+        panic!("expected an incompatible artifact");
+    };
+    assert!(reason.contains("incompatible version"), "{reason}");
+
+    let module = wasmtime::Module::new(&engine, "(module)")?.serialize()?;
+    assert!(Component::artifact_compatible(&engine, &module).is_err());
+    assert!(Component::artifact_compatible(&engine, b"not an artifact").is_err());
+    Ok(())
+}
+
+#[test]
+fn with_trap_recorder() -> Result<()> {
+    #[derive(Default)]
+    struct Recorded(Mutex<Vec<(Option<String>, Option<Trap>)>>);
+
+    impl TrapRecorder for Recorded {
+        fn record(&self, frame: &FrameInfo, trap: Option<Trap>) {
+            let name = frame.func_name().map(|s| s.to_string());
+            self.0.lock().unwrap().push((name, trap));
+        }
+    }
+
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m
+                    (func $boom (export "f") unreachable)
+                )
+                (core instance $i (instantiate $m))
+                (func (export "f") (canon lift (core func $i "f")))
+            )
+        "#,
+    )?;
+    let other = Component::new(&engine, "(component)")?;
+
+    let recorded = Arc::new(Recorded::default());
+    let linker = Linker::new(&engine);
+    let pre = linker
+        .instantiate_pre(&component)?
+        .with_trap_recorder(recorded.clone());
+    let other = linker
+        .instantiate_pre(&other)?
+        .with_trap_recorder(Arc::new(Recorded::default()));
+
+    // Instances created without the recorder don't record anything.
+    let mut store = Store::new(&engine, ());
+    let instance = linker.instantiate(&mut store, &component)?;
+    let f = instance.get_typed_func::<(), ()>(&mut store, "f")?;
+    assert!(f.call(&mut store, ()).is_err());
+    assert!(recorded.0.lock().unwrap().is_empty());
+
+    let mut store = Store::new(&engine, ());
+    other.instantiate(&mut store)?;
+    let instance = pre.instantiate(&mut store)?;
+    let f = instance.get_typed_func::<(), ()>(&mut store, "f")?;
+    assert!(f.call(&mut store, ()).is_err());
+
+    assert_eq!(
+        *recorded.0.lock().unwrap(),
+        [(Some("boom".to_string()), Some(Trap::UnreachableCodeReached))]
+    );
+    Ok(())
+}
+
+#[test]
+fn imported_shared_memories() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "threaded" (core module
+                    (import "env" "memory" (memory 1 10 shared))
+                    (import "env" "private" (memory 1))
+                ))
+                (import "plain" (core module
+                    (import "env" "memory" (memory 1))
+                ))
+                (core module $m
+                    (memory (export "memory") 1 1 shared)
+                )
+                (core instance (instantiate $m))
+            )
+        "#,
+    )?;
+    let memories = component.imported_shared_memories().collect::<Vec<_>>();
+    assert_eq!(memories.len(), 1);
+    let (import, spec) = &memories[0];
+    assert_eq!(*import, "threaded");
+    assert_eq!(spec.module, "env");
+    assert_eq!(spec.name, "memory");
+    assert!(spec.ty.is_shared());
+    assert_eq!(spec.ty.maximum(), Some(10));
+    Ok(())
+}
+
+#[test]
+fn validate_determinism() -> Result<()> {
+    let component = |engine: &Engine, body: &str| {
+        Component::new(
+            engine,
+            format!(
+                r#"
+                    (component
+                        (core module
+                            (memory 1 1)
+                            (func {body})
+                        )
+                    )
+                "#
+            ),
+        )
+    };
+
+    let engine = super::engine();
+    component(&engine, "")?.validate_determinism()?;
+    let err = component(&engine, "v128.const i64x2 0 0 f32x4.sqrt drop")?
+        .validate_determinism()
+        .unwrap_err();
+    assert!(err.to_string().contains("NaN canonicalization"), "{err}");
+    let err = component(&engine, "f32.const 1 f32.sqrt drop")?
+        .validate_determinism()
+        .unwrap_err();
+    assert!(err.to_string().contains("NaN canonicalization"), "{err}");
+
+    let mut config = Config::new();
+    config.cranelift_nan_canonicalization(true);
+    let engine = Engine::new(&config)?;
+    component(&engine, "f32.const 1 f32.sqrt drop")?.validate_determinism()?;
+    let simd = "v128.const i64x2 0 0 v128.const i64x2 0 0 i8x16.add drop";
+    component(&engine, simd)?.validate_determinism()?;
+    let relaxed = "v128.const i64x2 0 0 v128.const i64x2 0 0 i8x16.relaxed_swizzle drop";
+    let err = component(&engine, relaxed)?
+        .validate_determinism()
+        .unwrap_err();
+    assert!(err.to_string().contains("relaxed SIMD"), "{err}");
+
+    config.relaxed_simd_deterministic(true);
+    let engine = Engine::new(&config)?;
+    component(&engine, relaxed)?.validate_determinism()?;
+
+    let shared = Component::new(&engine, "(component (core module (memory 1 1 shared)))")?;
+    let err = shared.validate_determinism().unwrap_err();
+    assert!(err.to_string().contains("shared memory"), "{err}");
+
+    let imported = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "m" (core module
+                    (import "env" "memory" (memory 1 1 shared))
+                ))
+            )
+        "#,
+    )?;
+    let err = imported.validate_determinism().unwrap_err();
+    assert!(err.to_string().contains("env::memory"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn module_graph() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "host" (func $host))
+                (core func $host (canon lower (func $host)))
+                (core module $a
+                    (memory (export "memory") 1)
+                    (func (export "f"))
+                )
+                (core module $b
+                    (import "a" "mem" (memory 1))
+                    (import "a" "f" (func))
+                    (import "host" "f" (func))
+                )
+                (core instance $a (instantiate $a))
+                (core instance (instantiate $b
+                    (with "a" (instance
+                        (export "mem" (memory $a "memory"))
+                        (export "f" (func $a "f"))
+                    ))
+                    (with "host" (instance (export "f" (func $host))))
+                ))
+            )
+        "#,
+    )?;
+    let graph = component.module_graph();
+    assert_eq!(
+        graph.nodes,
+        [
+            ModuleGraphNode {
+                static_module: Some(StaticModuleIndex::from_u32(0)),
+                name: Some("a".to_string()),
+            },
+            ModuleGraphNode {
+                static_module: Some(StaticModuleIndex::from_u32(1)),
+                name: Some("b".to_string()),
+            },
+        ]
+    );
+    let edge = |export: &str, field: &str| ModuleGraphEdge {
+        from: 0,
+        export: Some(export.to_string()),
+        to: 1,
+        import: ("a".to_string(), field.to_string()),
+    };
+    assert_eq!(graph.edges, [edge("memory", "mem"), edge("f", "f")]);
+    Ok(())
+}
+
+#[test]
+fn warmup() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (type $t u32)
+                (import "t" (type (eq $t)))
+                (core module $m
+                    (memory 1)
+                    (func $start i32.const 0 i32.const 1 i32.store)
+                    (start $start)
+                )
+                (core instance (instantiate $m))
+            )
+        "#,
+    )?;
+    component.warmup()?;
+    assert_eq!(component.live_instance_count(), 0);
+
+    let trapping = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m (func $start unreachable) (start $start))
+                (core instance (instantiate $m))
+            )
+        "#,
+    )?;
+    assert!(trapping.warmup().is_err());
+
+    let importing = Component::new(&engine, r#"(component (import "f" (func)))"#)?;
+    let err = importing.warmup().unwrap_err();
+    assert!(err.to_string().contains("1 import(s)"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn live_instance_count() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(&engine, "(component)")?;
+    let linker = Linker::new(&engine);
+    assert_eq!(component.live_instance_count(), 0);
+
+    let mut store1 = Store::new(&engine, ());
+    linker.instantiate(&mut store1, &component)?;
+    linker.instantiate(&mut store1, &component)?;
+    let mut store2 = Store::new(&engine, ());
+    linker.instantiate(&mut store2, &component.clone())?;
+    assert_eq!(component.live_instance_count(), 3);
+
+    drop(store1);
+    assert_eq!(component.live_instance_count(), 1);
+    drop(store2);
+    assert_eq!(component.live_instance_count(), 0);
+
+    // Instances whose instantiation fails aren't counted, even while the
+    // store which holds their partially-initialized state is alive.
+    let trapping = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m
+                    (func $start unreachable)
+                    (start $start)
+                )
+                (core instance (instantiate $m))
+            )
+        "#,
+    )?;
+    let mut store = Store::new(&engine, ());
+    assert!(linker.instantiate(&mut store, &trapping).is_err());
+    assert_eq!(trapping.live_instance_count(), 0);
+    Ok(())
+}
+
+#[test]
+fn has_realloc() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m
+                    (memory (export "memory") 1)
+                    (func (export "realloc") (param i32 i32 i32 i32) (result i32) i32.const 0)
+                    (func (export "f") (param i32 i32))
+                )
+                (core instance $i (instantiate $m))
+                (func (export "f") (param "x" string)
+                    (canon lift (core func $i "f") (memory $i "memory")
+                        (realloc (func $i "realloc"))))
+            )
+        "#,
+    )?;
+    assert!(component.has_realloc());
+
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m (func (export "f") (param i32)))
+                (core instance $i (instantiate $m))
+                (func (export "f") (param "x" u32) (canon lift (core func $i "f")))
+            )
+        "#,
+    )?;
+    assert!(!component.has_realloc());
+    Ok(())
+}
+
+#[test]
+fn serialize_split() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m (func (export "f")))
+                (core instance $i (instantiate $m))
+                (func (export "f") (canon lift (core func $i "f")))
+            )
+        "#,
+    )?;
+    let (code, metadata) = component.serialize_split()?;
+    assert!(!metadata.is_empty());
+    assert_eq!(code.len(), component.serialize()?.len());
+
+    let split = unsafe { Component::from_parts_split(&engine, &code, &metadata)? };
+    assert!(split.get_export_index(None, "f").is_some());
+    assert_eq!(split.serialize()?, code);
+
+    assert!(unsafe { Component::from_parts_split(&engine, &code, b"bad") }.is_err());
+    Ok(())
+}
+
+#[test]
+fn type_of() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m (func (export "f") (param i32)))
+                (core instance $i (instantiate $m))
+                (func $f (param "x" u32) (canon lift (core func $i "f")))
+                (instance $inner (export "f" (func $f)))
+                (instance $outer (export "inner" (instance $inner)))
+                (export "outer" (instance $outer))
+                (export "g" (func $f))
+            )
+        "#,
+    )?;
+    let Some(types::ComponentItem::ComponentFunc(f)) =
+        component.type_of(&engine, &["outer", "inner", "f"])
+    else {
+        panic!("expected a function");
+    };
+    assert_eq!(f.params().len(), 1);
+    assert!(matches!(
+        component.type_of(&engine, &["outer", "inner"]),
+        Some(types::ComponentItem::ComponentInstance(_))
+    ));
+    assert!(component.type_of(&engine, &["outer", "missing"]).is_none());
+    assert!(component.type_of(&engine, &["g", "f"]).is_none());
+    assert!(component.type_of(&engine, &[]).is_none());
+    Ok(())
+}
+
+#[test]
+fn get_export_path() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m (func (export "f") (param i32)))
+                (core instance $i (instantiate $m))
+                (func $f (param "x" u32) (canon lift (core func $i "f")))
+                (instance $inner (export "fields" (func $f)))
+                (instance $outer (export "inner" (instance $inner)))
+                (export "wasi:http/types@0.2.0" (instance $inner))
+                (export "outer" (instance $outer))
+                (export "g" (func $f))
+            )
+        "#,
+    )?;
+    let ty = component.component_type();
+    assert!(matches!(
+        ty.get_export_path(&engine, "wasi:http/types@0.2.0.fields"),
+        Some(types::ComponentItem::ComponentFunc(_))
+    ));
+    assert!(matches!(
+        ty.get_export_path(&engine, "wasi:http/types@0.2.0"),
+        Some(types::ComponentItem::ComponentInstance(_))
+    ));
+    assert!(matches!(
+        ty.get_export_path(&engine, "outer.inner.fields"),
+        Some(types::ComponentItem::ComponentFunc(_))
+    ));
+    assert!(matches!(
+        ty.get_export_path(&engine, "g"),
+        Some(types::ComponentItem::ComponentFunc(_))
+    ));
+    assert!(ty.get_export_path(&engine, "outer.missing").is_none());
+    assert!(ty.get_export_path(&engine, "outer.inner.").is_none());
+    assert!(ty.get_export_path(&engine, "g.f").is_none());
+    assert!(ty.get_export_path(&engine, "wasi:http/types").is_none());
+    assert!(ty.get_export_path(&engine, "").is_none());
+    Ok(())
+}
+
+#[test]
+fn core_instance() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $a (func (export "f") (result i32) i32.const 1))
+                (core module $unused)
+                (core module $b (func (export "f") (result i32) i32.const 2))
+                (core instance (instantiate $b))
+                (core instance (instantiate $a))
+            )
+        "#,
+    )?;
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine).instantiate(&mut store, &component)?;
+    for (module, expected) in [(0, 1), (2, 2)] {
+        let module = StaticModuleIndex::from_u32(module);
+        let core = instance.core_instance(&mut store, module).unwrap();
+        let f = core.get_typed_func::<(), i32>(&mut store, "f")?;
+        assert_eq!(f.call(&mut store, ())?, expected);
+    }
+    for module in [1, 3] {
+        let module = StaticModuleIndex::from_u32(module);
+        assert!(instance.core_instance(&mut store, module).is_none());
+    }
+    Ok(())
+}
+
+#[test]
+fn estimate_compile_memory() -> Result<()> {
+    let mut config = Config::new();
+    config.parallel_compilation(false);
+    let engine = Engine::new(&config)?;
+    let small = wat::parse_str(
+        r#"
+            (component
+                (core module (func))
+            )
+        "#,
+    )?;
+    let large = wat::parse_str(
+        r#"
+            (component
+                (core module
+                    (func (result i32)
+                        i32.const 1 i32.const 2 i32.add
+                        i32.const 3 i32.add i32.const 4 i32.add)
+                    (func)
+                    (func)
+                )
+                (core module (func))
+            )
+        "#,
+    )?;
+    let small_estimate = Component::estimate_compile_memory(&engine, &small)?;
+    let large_estimate = Component::estimate_compile_memory(&engine, &large)?;
+    assert!(small_estimate > 0);
+    assert!(large_estimate > small_estimate);
+
+    let module = wat::parse_str("(module)")?;
+    assert!(Component::estimate_compile_memory(&engine, &module).is_err());
+    assert!(Component::estimate_compile_memory(&engine, b"not wasm").is_err());
+    Ok(())
+}
+
+#[test]
+fn set_name_resolver() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m
+                    (func $named (export "g") unreachable)
+                    (func (export "f") call $named)
+                )
+                (core instance $i (instantiate $m))
+                (func (export "f") (canon lift (core func $i "f")))
+            )
+        "#,
+    )?;
+    component.clone().set_name_resolver(|module, func| {
+        Some(format!("resolved-{}-{}", module.as_u32(), func.as_u32()))
+    });
+
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine).instantiate(&mut store, &component)?;
+    let f = instance.get_typed_func::<(), ()>(&mut store, "f")?;
+    let err = f.call(&mut store, ()).unwrap_err();
+    let bt = err.downcast_ref::<WasmBacktrace>().unwrap();
+    let names = bt
+        .frames()
+        .iter()
+        .map(|f| f.func_name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, [Some("named"), Some("resolved-0-1")]);
+    Ok(())
+}
+
+#[test]
+fn with_fuzz_trap_capture() -> Result<()> {
+    #[derive(Default)]
+    struct Halt(Mutex<Vec<(Option<Trap>, Vec<Option<String>>)>>);
+
+    impl TrapSnapshotSink for Halt {
+        fn capture(&self, snapshot: &TrapSnapshot<'_>) -> ! {
+            let names = snapshot
+                .frames
+                .iter()
+                .map(|f| f.func_name().map(|s| s.to_string()))
+                .collect();
+            self.0.lock().unwrap().push((snapshot.trap, names));
+            panic!("halted");
+        }
+    }
+
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m
+                    (func $boom (export "f") unreachable)
+                )
+                (core instance $i (instantiate $m))
+                (func (export "f") (canon lift (core func $i "f")))
+            )
+        "#,
+    )?;
+    let sink = Arc::new(Halt::default());
+    let pre = Linker::new(&engine).instantiate_pre(&component)?;
+    let fuzzed = pre.with_fuzz_trap_capture(sink.clone());
+
+    // Traps of instances created without the sink still propagate.
+    let mut store = Store::new(&engine, ());
+    let instance = pre.instantiate(&mut store)?;
+    let f = instance.get_typed_func::<(), ()>(&mut store, "f")?;
+    assert!(f.call(&mut store, ()).is_err());
+    assert!(sink.0.lock().unwrap().is_empty());
+
+    let mut store = Store::new(&engine, ());
+    let instance = fuzzed.instantiate(&mut store)?;
+    let f = instance.get_typed_func::<(), ()>(&mut store, "f")?;
+    assert!(catch_unwind(AssertUnwindSafe(|| f.call(&mut store, ()))).is_err());
+    assert_eq!(
+        *sink.0.lock().unwrap(),
+        [(
+            Some(Trap::UnreachableCodeReached),
+            vec![Some("boom".to_string())]
+        )]
+    );
+
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m
+                    (func $start unreachable)
+                    (start $start)
+                )
+                (core instance (instantiate $m))
+            )
+        "#,
+    )?;
+    let sink = Arc::new(Halt::default());
+    let fuzzed = Linker::new(&engine)
+        .instantiate_pre(&component)?
+        .with_fuzz_trap_capture(sink.clone());
+    let mut store = Store::new(&engine, ());
+    assert!(catch_unwind(AssertUnwindSafe(|| fuzzed.instantiate(&mut store))).is_err());
+    assert_eq!(sink.0.lock().unwrap().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn num_nested_components() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(&engine, "(component (core module))")?;
+    assert_eq!(component.num_nested_components(), 0);
+
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (component $leaf)
+                (component $inner
+                    (component $leaf)
+                    (instance (instantiate $leaf))
+                )
+                (component $unused)
+                (instance (instantiate $inner))
+                (instance (instantiate $leaf))
+                (instance (instantiate $leaf))
+            )
+        "#,
+    )?;
+    assert_eq!(component.num_nested_components(), 4);
+    Ok(())
+}
+
+#[test]
+fn validate_resource_counts() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(&engine, "(component (core module))")?;
+    component.validate_resource_counts(0, 0)?;
+
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "a" (type $a (sub resource)))
+                (type $b (resource (rep i32)))
+                (type $c (resource (rep i32)))
+                (export "b" (type $b))
+                (export "c" (type $c))
+            )
+        "#,
+    )?;
+    component.validate_resource_counts(3, 1)?;
+    let err = component.validate_resource_counts(2, 1).unwrap_err();
+    assert!(
+        format!("{err}").contains("3 resource type(s), exceeding the limit of 2"),
+        "{err}"
+    );
+    let err = component.validate_resource_counts(3, 0).unwrap_err();
+    assert!(
+        format!("{err}").contains("1 handle table(s), exceeding the limit of 0"),
+        "{err}"
+    );
+    Ok(())
+}
+
+#[test]
+fn lowered_export_signatures() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m
+                    (memory (export "memory") 1)
+                    (func (export "add") (param i32 i32) (result i32) local.get 0)
+                    (func (export "len") (param i32 i32) (result i32) local.get 0)
+                    (func (export "realloc") (param i32 i32 i32 i32) (result i32) unreachable)
+                )
+                (core instance $i (instantiate $m))
+                (func (export "add") (param "a" u32) (param "b" u32) (result u32)
+                    (canon lift (core func $i "add")))
+                (func $len (param "s" string) (result u32)
+                    (canon lift (core func $i "len")
+                        (memory $i "memory") (realloc (func $i "realloc"))))
+                (instance (export "strings") (export "len" (func $len)))
+            )
+        "#,
+    )?;
+    let signatures = component
+        .lowered_export_signatures(&engine)
+        .into_iter()
+        .map(|(name, ty)| {
+            let params = ty.params().map(|p| p.to_string()).collect::<Vec<_>>();
+            let results = ty.results().map(|r| r.to_string()).collect::<Vec<_>>();
+            (name, params, results)
+        })
+        .collect::<Vec<_>>();
+    let i32s = |n| vec!["i32".to_string(); n];
+    assert_eq!(
+        signatures,
+        [
+            ("add".to_string(), i32s(2), i32s(1)),
+            ("strings#len".to_string(), i32s(2), i32s(1)),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn assert_wx() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module (func (export "f")))
+            )
+        "#,
+    )?;
+    component.assert_wx()?;
+    let bytes = component.serialize()?;
+    unsafe { Component::deserialize(&engine, &bytes)? }.assert_wx()?;
+
+    let component = Component::new(&engine, "(component)")?;
+    let err = component.assert_wx().unwrap_err();
+    assert!(format!("{err}").contains("contains no code"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn trampoline_kind_counts() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(&engine, "(component (core module))")?;
+    assert_eq!(
+        component.trampoline_kind_counts(),
+        TrampolineCounts::default()
+    );
+
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "f" (func $f))
+                (import "g" (func $g))
+                (type $r (resource (rep i32)))
+                (core func $f (canon lower (func $f)))
+                (core func $g (canon lower (func $g)))
+                (core func $drop (canon resource.drop $r))
+                (core func $new (canon resource.new $r))
+                (core func $rep (canon resource.rep $r))
+                (core module $m
+                    (import "" "f" (func))
+                    (import "" "g" (func))
+                    (import "" "drop" (func (param i32)))
+                    (import "" "new" (func (param i32) (result i32)))
+                    (import "" "rep" (func (param i32) (result i32)))
+                )
+                (core instance (instantiate $m
+                    (with "" (instance
+                        (export "f" (func $f))
+                        (export "g" (func $g))
+                        (export "drop" (func $drop))
+                        (export "new" (func $new))
+                        (export "rep" (func $rep))
+                    ))
+                ))
+            )
+        "#,
+    )?;
+    assert_eq!(
+        component.trampoline_kind_counts(),
+        TrampolineCounts {
+            lowerings: 2,
+            resource_drops: 1,
+            always_trap: 0,
+            other: 2,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn module_digest() -> Result<()> {
+    let engine = super::engine();
+    let adapter = r#"
+        (core module $adapter
+            (func (export "f") (param i32) (result i32)
+                local.get 0 i32.const 1 i32.add)
+        )
+    "#;
+    let a = Component::new(
+        &engine,
+        format!("(component {adapter} (core module (func)))"),
+    )?;
+    let b = Component::new(
+        &engine,
+        format!("(component (core module (memory 1)) {adapter})"),
+    )?;
+    let first = StaticModuleIndex::from_u32(0);
+    let second = StaticModuleIndex::from_u32(1);
+    assert_eq!(a.module_digest(first), b.module_digest(second));
+    assert_ne!(a.module_digest(first), a.module_digest(second));
+    assert_eq!(a.module_digest(first), a.clone().module_digest(first));
+    Ok(())
+}
+
+#[test]
+fn always_trap_trampolines() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "f" (func $f))
+                (core func (canon lower (func $f)))
+            )
+        "#,
+    )?;
+    assert_eq!(component.always_trap_trampolines().count(), 0);
+
+    // Adapters between two components which pass strings use the trap
+    // intrinsic to reject invalid string encodings and sizes.
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (component $callee
+                    (core module $m
+                        (memory (export "memory") 1)
+                        (func (export "f") (param i32 i32))
+                        (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+                            unreachable)
+                    )
+                    (core instance $i (instantiate $m))
+                    (func (export "f") (param "s" string)
+                        (canon lift (core func $i "f")
+                            (memory $i "memory") (realloc (func $i "realloc"))))
+                )
+                (instance $callee (instantiate $callee))
+                (core module $libc (memory (export "memory") 1))
+                (core instance $libc (instantiate $libc))
+                (core func $f (canon lower (func $callee "f") (memory $libc "memory")))
+                (core module $caller (import "" "f" (func (param i32 i32))))
+                (core instance (instantiate $caller
+                    (with "" (instance (export "f" (func $f))))))
+            )
+        "#,
+    )?;
+    let kinds = component.trampoline_kind_counts();
+    assert_eq!(
+        component.always_trap_trampolines().count(),
+        kinds.always_trap
+    );
+    assert!(kinds.always_trap > 0);
+    Ok(())
+}
+
+#[test]
+fn trampoline_signature() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(&engine, "(component)")?;
+    assert_eq!(component.num_trampolines(), 0);
+    assert!(component.trampoline_signature(0).is_none());
+
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "f" (func $f (param "x" u32) (result u64)))
+                (import "g" (func $g (param "x" f32)))
+                (core func $f (canon lower (func $f)))
+                (core func $g (canon lower (func $g)))
+                (core module $m
+                    (import "" "f" (func (param i32) (result i64)))
+                    (import "" "g" (func (param f32)))
+                )
+                (core instance $i (instantiate $m
+                    (with "" (instance
+                        (export "f" (func $f))
+                        (export "g" (func $g))
+                    ))
+                ))
+            )
+        "#,
+    )?;
+    assert_eq!(component.num_trampolines(), 2);
+    let ty = component.trampoline_signature(0).unwrap();
+    let params = ty.params().collect::<Vec<_>>();
+    let results = ty.results().collect::<Vec<_>>();
+    assert!(matches!(params[..], [wasmtime::ValType::I32]));
+    assert!(matches!(results[..], [wasmtime::ValType::I64]));
+    let ty = component.trampoline_signature(1).unwrap();
+    let params = ty.params().collect::<Vec<_>>();
+    assert!(matches!(params[..], [wasmtime::ValType::F32]));
+    assert_eq!(ty.results().len(), 0);
+    assert!(component.trampoline_signature(2).is_none());
+    assert!(component.trampoline_signature(usize::MAX).is_none());
+    Ok(())
+}
+
+#[test]
+fn abi_digest() -> Result<()> {
+    let engine = super::engine();
+    let component = |encoding: &str, export: &str| {
+        Component::new(
+            &engine,
+            format!(
+                r#"
+                    (component
+                        (import "log" (func $log (param "msg" string)))
+                        (core module $m
+                            (memory (export "memory") 1)
+                            (func (export "f") (param i32 i32))
+                            (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+                                unreachable)
+                        )
+                        (core instance $i (instantiate $m))
+                        (core func $log (canon lower (func $log)
+                            (memory $i "memory") string-encoding={encoding}))
+                        (core module $n (import "" "log" (func (param i32 i32))))
+                        (core instance (instantiate $n
+                            (with "" (instance (export "log" (func $log))))))
+                        (func (export "{export}") (param "s" string)
+                            (canon lift (core func $i "f")
+                                (memory $i "memory") (realloc (func $i "realloc"))))
+                    )
+                "#
+            ),
+        )
+    };
+    let a = component("utf8", "a")?;
+    let b = component("utf8", "b")?;
+    let c = component("utf16", "a")?;
+    assert_eq!(a.abi_digest(), b.abi_digest());
+    assert_ne!(a.abi_digest(), c.abi_digest());
+    let empty = Component::new(&engine, "(component)")?;
+    assert_ne!(a.abi_digest(), empty.abi_digest());
+    Ok(())
+}
+
+#[test]
+fn rebuild_with_config() -> Result<()> {
+    let wat = r#"
+        (component
+            (core module $m (func (export "f") (result i32) i32.const 42))
+            (core instance $i (instantiate $m))
+            (func (export "f") (result u32) (canon lift (core func $i "f")))
+        )
+    "#;
+    let err = Component::new(&Engine::default(), wat)?
+        .rebuild_with_config(&Config::new())
+        .err()
+        .unwrap();
+    assert!(format!("{err}").contains("preserve_wasm_source"), "{err}");
+
+    let mut config = Config::new();
+    config.preserve_wasm_source(true);
+    let component = Component::new(&Engine::new(&config)?, wat)?;
+
+    config.cranelift_opt_level(OptLevel::None);
+    let rebuilt = component.rebuild_with_config(&config)?;
+    assert!(!Engine::same(rebuilt.engine(), component.engine()));
+    assert_eq!(rebuilt.original_wasm(), component.original_wasm());
+
+    let mut store = Store::new(rebuilt.engine(), ());
+    let instance = Linker::new(rebuilt.engine()).instantiate(&mut store, &rebuilt)?;
+    let f = instance.get_typed_func::<(), (u32,)>(&mut store, "f")?;
+    assert_eq!(f.call(&mut store, ())?, (42,));
+    Ok(())
+}
+
+#[test]
+fn get_export_memory() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m
+                    (memory (export "memory") 1)
+                    (func (export "s") (result i32) i32.const 0)
+                    (func (export "nop"))
+                )
+                (core instance $i (instantiate $m))
+                (func (export "s") (result string)
+                    (canon lift (core func $i "s") (memory $i "memory")))
+                (func (export "nop") (canon lift (core func $i "nop")))
+            )
+        "#,
+    )?;
+
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine).instantiate(&mut store, &component)?;
+    let memory = instance.get_export_memory(&mut store, "s").unwrap();
+    assert_eq!(memory.data_size(&store), 65536);
+    memory.data_mut(&mut store)[100] = 42;
+    let core = instance
+        .core_instance(&mut store, StaticModuleIndex::from_u32(0))
+        .unwrap();
+    let core_memory = core.get_memory(&mut store, "memory").unwrap();
+    assert_eq!(core_memory.data(&store)[100], 42);
+
+    assert!(instance.get_export_memory(&mut store, "nop").is_none());
+    assert!(instance.get_export_memory(&mut store, "missing").is_none());
+    Ok(())
+}
+
+#[test]
+fn serialize_to_writer() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(&engine, "(component (core module))")?;
+    let mut bytes = Vec::new();
+    component.serialize_to_writer(&mut bytes)?;
+    assert_eq!(bytes, component.serialize()?);
+
+    let mut short = [0; 16];
+    let err = component.serialize_to_writer(&mut short[..]).unwrap_err();
+    assert!(format!("{err:?}").contains("failed to write component"));
+    Ok(())
+}
+
+#[test]
+fn serialize_into() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(&engine, "(component (core module))")?;
+    let mut buf = vec![0xff; 4];
+    component.serialize_into(&mut buf)?;
+    assert_eq!(buf, component.serialize()?);
+
+    let capacity = buf.capacity();
+    component.serialize_into(&mut buf)?;
+    assert_eq!(buf, component.serialize()?);
+    assert_eq!(buf.capacity(), capacity);
+    Ok(())
+}
+
+#[tokio::test]
+async fn new_async() -> Result<()> {
+    let engine = super::engine();
+    let wat = "(component (core module (func (export \"f\"))))";
+    let component = Component::new_async(&engine, wat).await?;
+    assert_eq!(
+        component.serialize()?,
+        Component::new(&engine, wat)?.serialize()?
+    );
+
+    let err = Component::new_async(&engine, "(component (core module (func (result i32))))")
+        .await
+        .err()
+        .unwrap();
+    let expected = Component::new(&engine, "(component (core module (func (result i32))))")
+        .err()
+        .unwrap();
+    assert_eq!(format!("{err:?}"), format!("{expected:?}"));
+    Ok(())
+}
+
+#[test]
+fn deserialize_unchecked() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        "(component (core module $m (memory 1)) (core instance (instantiate $m)))",
+    )?;
+    let bytes = component.serialize()?;
+
+    let mut config = Config::new();
+    config.allocation_strategy(
+        wasmtime::PoolingAllocationConfig::default()
+            .max_memories_per_component(0)
+            .clone(),
+    );
+    let pooling = Engine::new(&config)?;
+    assert!(unsafe { Component::deserialize(&pooling, &bytes) }.is_err());
+    let unchecked = unsafe { Component::deserialize_unchecked(&pooling, &bytes)? };
+    assert!(unchecked.validate_for_allocator(&pooling).is_err());
+    assert_eq!(unchecked.serialize()?, bytes);
+
+    assert!(unsafe { Component::deserialize_unchecked(&engine, b"not an artifact") }.is_err());
+    Ok(())
+}
+
+#[test]
+fn compilation_id() -> Result<()> {
+    let wat = "(component (core module (func (export \"f\") (result i32) i32.const 1)))";
+    let engine = super::engine();
+    let a = Component::new(&engine, wat)?;
+    let b = Component::new(&Engine::default(), wat)?;
+    assert_eq!(a.compilation_id(), b.compilation_id());
+    assert_ne!(
+        a.compilation_id(),
+        Component::new(&engine, "(component (core module))")?.compilation_id()
+    );
+
+    let deserialized = unsafe { Component::deserialize(&engine, a.serialize()?)? };
+    assert_eq!(deserialized.compilation_id(), a.compilation_id());
+    let (code, metadata) = a.serialize_split()?;
+    let split = unsafe { Component::from_parts_split(&engine, code, metadata)? };
+    assert_eq!(split.compilation_id(), a.compilation_id());
+
+    let mut config = Config::new();
+    config.cranelift_opt_level(wasmtime::OptLevel::None);
+    let unoptimized = Component::new(&Engine::new(&config)?, wat)?;
+    assert_ne!(unoptimized.compilation_id(), a.compilation_id());
+    Ok(())
+}
+
+#[test]
+fn deserialize_file_with() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        "(component (core module (func (export \"f\") (result i32) i32.const 1)))",
+    )?;
+    let dir = tempfile::TempDir::new()?;
+    let path = dir.path().join("component.cwasm");
+    std::fs::write(&path, component.serialize()?)?;
+
+    for mmap in [true, false] {
+        let options = DeserializeFileOptions::new().mmap(mmap);
+        let loaded = unsafe { Component::deserialize_file_with(&engine, &path, &options)? };
+        assert_eq!(loaded.serialize()?, component.serialize()?);
+    }
+
+    // The file isn't needed once it's been read into memory.
+    let options = DeserializeFileOptions::new().mmap(false);
+    let loaded = unsafe { Component::deserialize_file_with(&engine, &path, &options)? };
+    std::fs::write(&path, b"overwritten")?;
+    assert_eq!(loaded.serialize()?, component.serialize()?);
+    assert!(unsafe { Component::deserialize_file_with(&engine, &path, &options) }.is_err());
+    Ok(())
+}
+
+#[test]
+fn required_host_imports() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "f" (func))
+                (import "a:b/c" (instance
+                    (export "r" (type (sub resource)))
+                    (export "g" (func))
+                    (export "nested" (instance
+                        (export "h" (func))
+                    ))
+                ))
+                (import "empty" (instance))
+            )
+        "#,
+    )?;
+    let imports = component
+        .required_host_imports()
+        .into_iter()
+        .map(|import| {
+            let kind = match import.item {
+                types::ComponentItem::ComponentFunc(_) => "func",
+                types::ComponentItem::Resource(_) => "resource",
+                types::ComponentItem::ComponentInstance(_) => "instance",
+                _ => "other",
+            };
+            (import.instance, import.name, kind)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        imports,
+        [
+            (None, "f".to_string(), "func"),
+            (Some("a:b/c".to_string()), "r".to_string(), "resource"),
+            (Some("a:b/c".to_string()), "g".to_string(), "func"),
+            (Some("a:b/c.nested".to_string()), "h".to_string(), "func"),
+            (None, "empty".to_string(), "instance"),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn peek_type() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "f" (func (param "x" u32)))
+                (import "i" (instance $i (export "r" (type (sub resource)))))
+                (core module $m (func (export "g") (param i32)))
+                (export "m" (core module $m))
+                (export "i2" (instance $i))
+            )
+        "#,
+    )?;
+    let bytes = component.serialize()?;
+    let ty = Component::peek_type(&engine, &bytes)?;
+    let names = |items: Vec<(&str, types::ComponentItem)>| {
+        items
+            .into_iter()
+            .map(|(name, item)| format!("{name}: {item:?}"))
+            .collect::<Vec<_>>()
+    };
+    let expected = component.component_type();
+    assert_eq!(
+        names(ty.imports(&engine).collect()),
+        names(expected.imports(&engine).collect())
+    );
+    assert_eq!(
+        names(ty.exports(&engine).collect()),
+        names(expected.exports(&engine).collect())
+    );
+    let Some(types::ComponentItem::Module(module)) = ty.get_export(&engine, "m") else {
+        panic!("expected a module export");
+    };
+    assert_eq!(module.exports(&engine).len(), 1);
+
+    let module = wasmtime::Module::new(&engine, "(module)")?.serialize()?;
+    let err = Component::peek_type(&engine, &module).unwrap_err();
+    assert!(format!("{err}").contains("core wasm module"), "{err}");
+    assert!(Component::peek_type(&engine, b"not an artifact").is_err());
+    Ok(())
+}
+
+#[test]
+fn memory_usage() -> Result<()> {
+    let engine = super::engine();
+    let empty = Component::new(&engine, "(component)")?.memory_usage();
+    assert_eq!(empty.num_static_modules, 0);
+    assert_eq!(empty.static_modules_text_size, 0);
+
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module (func (export "a") (result i32) i32.const 1))
+                (core module (func (export "b") (result i64) i64.const 2))
+                (type (record (field "x" u32)))
+            )
+        "#,
+    )?;
+    let usage = component.memory_usage();
+    assert_eq!(usage.num_static_modules, 2);
+    assert!(usage.static_modules_text_size > 0);
+    assert!(usage.static_modules_text_size <= usage.text_size);
+    assert!(usage.text_size <= usage.image_size);
+    let range = component.image_range();
+    assert_eq!(usage.image_size, range.end.addr() - range.start.addr());
+    assert!(usage.types_size > empty.types_size);
+    Ok(())
+}
+
+#[test]
+fn modules() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "m" (core module))
+                (core module $a (func (export "a")))
+                (component
+                    (core module $b (memory (export "b") 1))
+                )
+            )
+        "#,
+    )?;
+    let modules = component.modules();
+    assert_eq!(modules.len(), 2);
+    let exports = modules
+        .map(|m| {
+            m.exports()
+                .map(|e| e.name().to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(exports, [["a"], ["b"]]);
+    Ok(())
+}
+
+#[test]
+fn name() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(&engine, "(component $outer (component $inner))")?;
+    assert_eq!(component.name(), Some("outer"));
+    let deserialized = unsafe { Component::deserialize(&engine, component.serialize()?)? };
+    assert_eq!(deserialized.name(), Some("outer"));
+
+    let unnamed = Component::new(&engine, "(component (component $inner))")?;
+    assert_eq!(unnamed.name(), None);
+    Ok(())
+}
+
+#[test]
+fn resources_required_with_hints() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "m" (core module $m))
+                (core module $local (memory 2))
+                (core instance (instantiate $m))
+                (core instance (instantiate $m))
+                (core instance (instantiate $local))
+            )
+        "#,
+    )?;
+    assert!(component.resources_required().is_none());
+
+    let mut hints = std::collections::HashMap::new();
+    assert!(component.resources_required_with(&hints).is_err());
+
+    hints.insert(
+        "m".to_string(),
+        wasmtime::ResourcesRequired {
+            num_memories: 1,
+            max_initial_memory_size: Some(5),
+            num_tables: 1,
+            max_initial_table_size: Some(10),
+        },
+    );
+    let resources = component.resources_required_with(&hints)?;
+    assert_eq!(resources.num_memories, 3);
+    assert_eq!(resources.max_initial_memory_size, Some(5));
+    assert_eq!(resources.num_tables, 2);
+    assert_eq!(resources.max_initial_table_size, Some(10));
+    Ok(())
+}
+
+#[test]
+fn resources_required_report() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module $m (memory 1))
+                (core instance (instantiate $m))
+            )
+        "#,
+    )?;
+    let resources = component.resources_required_report().unwrap();
+    let expected = component.resources_required().unwrap();
+    assert_eq!(resources.num_memories, expected.num_memories);
+    assert_eq!(
+        resources.max_initial_memory_size,
+        expected.max_initial_memory_size
+    );
+
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "a" (core module $a))
+                (import "i" (instance $i (export "b" (core module))))
+                (alias export $i "b" (core module $b))
+                (core instance (instantiate $a))
+                (core instance (instantiate $b))
+                (core instance (instantiate $a))
+            )
+        "#,
+    )?;
+    let reason = component.resources_required_report().err().unwrap();
+    assert_eq!(reason.imported_modules().collect::<Vec<_>>(), ["a", "i"]);
+    let message = reason.to_string();
+    assert!(
+        message.contains("`a`") && message.contains("`i`"),
+        "{message}"
+    );
+    Ok(())
+}
+
+#[test]
+fn imports_and_exports() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "a" (func))
+                (import "b" (instance $b))
+                (import "r" (type (sub resource)))
+                (export "c" (instance $b))
+            )
+        "#,
+    )?;
+    let ty = component.component_type();
+    let kinds = |items: Vec<(&str, types::ComponentItem)>| {
+        items
+            .into_iter()
+            .map(|(name, item)| (name.to_string(), format!("{item:?}")))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(component.imports().len(), 3);
+    assert_eq!(
+        kinds(component.imports().collect()),
+        kinds(ty.imports(&engine).collect())
+    );
+    assert_eq!(
+        kinds(component.exports().collect()),
+        kinds(ty.exports(&engine).collect())
+    );
+    Ok(())
+}
+
+#[test]
+fn validate() -> Result<()> {
+    let engine = super::engine();
+    let valid = wat::parse_str(
+        r#"
+            (component
+                (core module (func (export "f") (result i32) i32.const 1))
+            )
+        "#,
+    )?;
+    Component::validate(&engine, &valid)?;
+
+    let invalid = [
+        r#"(component (core module (func (result i32))))"#,
+        r#"(component (core module) (core module $m (func $f (result i32) i64.const 0)))"#,
+        r#"(component (import "f" (func)) (import "f" (func)))"#,
+    ];
+    for wat in invalid {
+        let binary = wat::parse_str(wat)?;
+        let err = Component::validate(&engine, &binary).unwrap_err();
+        let expected = Component::from_binary(&engine, &binary).err().unwrap();
+        assert_eq!(format!("{err:#}"), format!("{expected:#}"));
+    }
+
+    let module = wat::parse_str("(module)")?;
+    assert!(Component::validate(&engine, &module).is_err());
+    assert!(Component::validate(&engine, b"(component)").is_err());
+    Ok(())
+}
+
+#[test]
+fn from_module() -> Result<()> {
+    let engine = super::engine();
+    let module = wat::parse_str(
+        r#"
+            (module
+                (memory (export "memory") 1)
+                (func (export "add") (param i32 i32) (result i32)
+                    local.get 0
+                    local.get 1
+                    i32.add)
+                (func (export "nothing"))
+            )
+        "#,
+    )?;
+    let component = Component::from_module(&engine, &module)?;
+    assert_eq!(
+        component
+            .exports()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+        ["add", "nothing"]
+    );
+    assert_eq!(component.imports().count(), 0);
+
+    // The module is embedded, so a deserialized component is instantiable
+    // on its own.
+    let bytes = component.serialize()?;
+    let component = unsafe { Component::deserialize(&engine, &bytes)? };
+    let linker = Linker::<()>::new(&engine);
+    let mut store = Store::new(&engine, ());
+    let instance = linker.instantiate(&mut store, &component)?;
+    let add = instance.get_typed_func::<(u32, u32), (u32,)>(&mut store, "add")?;
+    assert_eq!(add.call(&mut store, (1, 2))?, (3,));
+
+    let module = wat::parse_str(
+        r#"
+            (module
+                (func (export "ok"))
+                (func (export "not_kebab"))
+                (func (export "multi") (result i32 i32) i32.const 0 i32.const 0)
+                (func (export "simd") (param v128))
+            )
+        "#,
+    )?;
+    let err = match Component::from_module(&engine, &module) {
+        Ok(_) => panic!("expected an error"),
+        Err(e) => e.to_string(),
+    };
+    assert!(err.contains("`not_kebab`"), "{err}");
+    assert!(err.contains("`multi`"), "{err}");
+    assert!(err.contains("`simd`"), "{err}");
+    assert!(!err.contains("`ok`"), "{err}");
+
+    let module = wat::parse_str(r#"(module (import "" "" (func)))"#)?;
+    assert!(Component::from_module(&engine, &module).is_err());
+    let component = wat::parse_str("(component)")?;
+    assert!(Component::from_module(&engine, &component).is_err());
+    Ok(())
+}
+
+#[test]
+fn module_image_ranges() -> Result<()> {
+    let engine = super::engine();
+    let component = Component::new(
+        &engine,
+        r#"
+            (component
+                (core module (func (export "f")) (func (export "g")))
+                (core module)
+                (core module (func (export "h") (param i32) (result i32) local.get 0))
+            )
+        "#,
+    )?;
+    let ranges = component.module_image_ranges();
+    assert_eq!(
+        ranges.iter().map(|(i, _)| i.as_u32()).collect::<Vec<_>>(),
+        [0, 2]
+    );
+    let image = component.image_range();
+    for (i, (_, a)) in ranges.iter().enumerate() {
+        assert!(a.start < a.end);
+        assert!(image.start <= a.start && a.end <= image.end);
+        for (_, b) in &ranges[i + 1..] {
+            assert!(a.end <= b.start || b.end <= a.start);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn module_custom_sections() -> Result<()> {
+    let wat = r#"
+        (component
+            (core module
+                (@custom "provenance" "ci-1234")
+                (@custom "sourcemap" "{}")
+            )
+            (core module)
+        )
+    "#;
+    let first = StaticModuleIndex::from_u32(0);
+    let second = StaticModuleIndex::from_u32(1);
+
+    let component = Component::new(&Engine::default(), wat)?;
+    assert_eq!(component.module_custom_sections(first).count(), 0);
+
+    let mut config = Config::new();
+    config.retain_custom_sections(true);
+    let engine = Engine::new(&config)?;
+    let component = Component::new(&engine, wat)?;
+    let sections = [("provenance", &b"ci-1234"[..]), ("sourcemap", &b"{}"[..])];
+    assert!(component.module_custom_sections(first).eq(sections));
+    assert_eq!(component.module_custom_sections(second).count(), 0);
+
+    let bytes = component.serialize()?;
+    let component = unsafe { Component::deserialize(&engine, &bytes)? };
+    assert!(component.module_custom_sections(first).eq(sections));
+    Ok(())
+}
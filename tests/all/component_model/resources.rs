@@ -105,6 +105,80 @@ fn guest_resource_types() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn guest_resource_types_same_definition() -> Result<()> {
+    let engine = super::engine();
+    let c = Component::new(
+        &engine,
+        r#"
+            (component
+                (type $t (resource (rep i32)))
+                (type $u (resource (rep i32)))
+                (export "t" (type $t))
+                (export "u" (type $u))
+            )
+        "#,
+    )?;
+    let other = Component::new(
+        &engine,
+        r#"
+            (component
+                (type $t (resource (rep i32)))
+                (export "t" (type $t))
+            )
+        "#,
+    )?;
+
+    let mut store = Store::new(&engine, ());
+    let linker = Linker::new(&engine);
+    let i1 = linker.instantiate(&mut store, &c)?;
+    let i2 = linker.instantiate(&mut store, &c)?;
+    let i3 = linker.instantiate(&mut store, &other)?;
+    let t1 = i1.get_resource(&mut store, "t").unwrap();
+    let t2 = i2.get_resource(&mut store, "t").unwrap();
+    let u1 = i1.get_resource(&mut store, "u").unwrap();
+    let t3 = i3.get_resource(&mut store, "t").unwrap();
+
+    assert_ne!(t1, t2);
+    assert!(ResourceType::same_definition(&t1, &t2));
+    assert!(!ResourceType::same_definition(&t1, &u1));
+    assert!(!ResourceType::same_definition(&t1, &t3));
+
+    let uninstantiated = match c.component_type().get_export(&engine, "t") {
+        Some(types::ComponentItem::Resource(ty)) => ty,
+        _ => panic!("expected a resource export"),
+    };
+    assert!(uninstantiated.same_definition(&t1));
+    assert!(uninstantiated.same_definition(&t2));
+
+    struct T;
+    let host = ResourceType::host::<T>();
+    assert!(host.same_definition(&ResourceType::host::<T>()));
+    assert!(!host.same_definition(&t1));
+
+    // Components with identical types may share their type information, but
+    // the resources they define are still distinct.
+    let mut config = Config::new();
+    config.shared_component_types(true);
+    let engine = Engine::new(&config)?;
+    let wat = r#"
+        (component
+            (type $t (resource (rep i32)))
+            (export "t" (type $t))
+        )
+    "#;
+    let a = Component::new(&engine, wat)?;
+    let b = Component::new(&engine, wat)?;
+    let ty = |component: &Component| match component.component_type().get_export(&engine, "t") {
+        Some(types::ComponentItem::Resource(ty)) => ty,
+        _ => panic!("expected a resource export"),
+    };
+    assert!(ty(&a).same_definition(&ty(&a)));
+    assert!(!ty(&a).same_definition(&ty(&b)));
+    assert_ne!(ty(&a), ty(&b));
+    Ok(())
+}
+
 #[test]
 fn resource_any() -> Result<()> {
     let engine = super::engine();
@@ -395,6 +469,66 @@ fn drop_host_twice() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn resource_in_table() -> Result<()> {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+
+    let engine = super::engine();
+    let c = Component::new(
+        &engine,
+        r#"
+            (component
+                (import "t" (type $t (sub resource)))
+
+                (core func $dtor (canon resource.drop $t))
+                (func (export "dtor") (param "x" (own $t))
+                    (canon lift (core func $dtor)))
+            )
+        "#,
+    )?;
+
+    struct MyType {
+        value: u64,
+        drops: Arc<AtomicUsize>,
+    }
+
+    impl Drop for MyType {
+        fn drop(&mut self) {
+            self.drops.fetch_add(1, SeqCst);
+        }
+    }
+
+    let drops = Arc::new(AtomicUsize::new(0));
+    let mut store = Store::new(&engine, ResourceTable::new());
+    let mut linker = Linker::new(&engine);
+    linker
+        .root()
+        .resource_in_table::<MyType>("t", |table| table)?;
+    let i = linker.instantiate(&mut store, &c)?;
+    let dtor = i.get_typed_func::<(Resource<MyType>,), ()>(&mut store, "dtor")?;
+
+    let t = store.data_mut().push(MyType {
+        value: 42,
+        drops: drops.clone(),
+    })?;
+    assert_eq!(store.data().get(&t)?.value, 42);
+    let rep = t.rep();
+    dtor.call(&mut store, (t,))?;
+    assert_eq!(drops.load(SeqCst), 1);
+    assert!(store.data().is_empty());
+
+    let err = dtor
+        .call(&mut store, (Resource::new_own(rep),))
+        .unwrap_err();
+    assert!(
+        err.downcast_ref::<ResourceTableError>().is_some(),
+        "unexpected error: {err:?}"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn manually_destroy() -> Result<()> {
     let engine = super::engine();
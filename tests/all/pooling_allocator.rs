@@ -1078,6 +1078,44 @@ fn component_memories_limit() -> Result<()> {
     Ok(())
 }
 
+#[test]
+#[cfg(feature = "component-model")]
+fn component_validate_for_allocator() -> Result<()> {
+    let mut pool = crate::small_pool_config();
+    pool.max_memories_per_component(1).total_memories(2);
+    let mut config = Config::new();
+    config.wasm_component_model(true);
+    config.allocation_strategy(pool);
+    let pooling = Engine::new(&config)?;
+
+    // Compile with the default on-demand allocator which doesn't have any
+    // limits on the number of memories.
+    let mut config = Config::new();
+    config.wasm_component_model(true);
+    let on_demand = Engine::new(&config)?;
+    let component = wasmtime::component::Component::new(
+        &on_demand,
+        r#"
+            (component
+                (core module $m (memory 1 1))
+                (core instance $a (instantiate $m))
+                (core instance $b (instantiate $m))
+            )
+        "#,
+    )?;
+    component.validate_for_allocator(&on_demand)?;
+
+    match component.validate_for_allocator(&pooling) {
+        Ok(()) => panic!("should have hit limit"),
+        Err(e) => e.assert_contains(
+            "The component transitively contains 2 Wasm linear memories, which exceeds the \
+             configured maximum of 1",
+        ),
+    }
+
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "component-model")]
 fn component_tables_limit() -> Result<()> {
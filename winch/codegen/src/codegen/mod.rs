@@ -24,8 +24,8 @@ use wasmparser::{
 };
 use wasmtime_cranelift::{TRAP_BAD_SIGNATURE, TRAP_HEAP_MISALIGNED, TRAP_TABLE_OUT_OF_BOUNDS};
 use wasmtime_environ::{
-    FUNCREF_MASK, GlobalIndex, MemoryIndex, PtrSize, TableIndex, Tunables, TypeIndex, WasmHeapType,
-    WasmValType,
+    FUNCREF_MASK, GlobalIndex, InstructionUsage, MemoryIndex, PtrSize, TableIndex, Tunables,
+    TypeIndex, WasmHeapType, WasmValType,
 };
 
 mod context;
@@ -117,6 +117,9 @@ where
 
     /// Local counter to track fuel consumption.
     pub fuel_consumed: i64,
+
+    /// The classes of instructions visited so far.
+    pub instructions: InstructionUsage,
     phase: PhantomData<P>,
 }
 
@@ -131,6 +134,10 @@ where
         env: FuncEnv<'a, 'translation, 'data, M::Ptr>,
         sig: ABISig,
     ) -> CodeGen<'a, 'translation, 'data, M, Prologue> {
+        let mut instructions = InstructionUsage::default();
+        for local in context.frame.locals() {
+            instructions.record_local(local.ty);
+        }
         Self {
             sig,
             context,
@@ -141,6 +148,7 @@ where
             control_frames: Default::default(),
             // Empty functions should consume at least 1 fuel unit.
             fuel_consumed: 1,
+            instructions,
             phase: PhantomData,
         }
     }
@@ -201,6 +209,7 @@ where
             source_location: self.source_location,
             control_frames: self.control_frames,
             fuel_consumed: self.fuel_consumed,
+            instructions: self.instructions,
             phase: PhantomData,
         })
     }
@@ -359,6 +368,7 @@ where
                     fn $visit(&mut self $($(,$arg: $argty)*)?) -> Self::Output {
                         self.0.$visit($($($arg.clone()),*)?)?;
                         let op = Operator::$op $({ $($arg: $arg.clone()),* })?;
+                        self.1.record_instruction(&op);
                         if self.1.visit(&op) {
                             self.1.before_visit_op(&op, self.2)?;
                             let res = self.1.$visit($($($arg),*)?)?;
@@ -383,6 +393,9 @@ where
         /// Trait to handle hooks that must happen before and after visiting an
         /// operator.
         trait VisitorHooks {
+            /// Hook for every operator, including those which aren't visited
+            /// because they're unreachable.
+            fn record_instruction(&mut self, operator: &Operator);
             /// Hook prior to visiting an operator.
             fn before_visit_op(&mut self, operator: &Operator, offset: usize) -> Result<()>;
             /// Hook after visiting an operator.
@@ -402,6 +415,10 @@ where
         impl<'a, 'translation, 'data, M: MacroAssembler> VisitorHooks
             for CodeGen<'a, 'translation, 'data, M, Emission>
         {
+            fn record_instruction(&mut self, operator: &Operator) {
+                self.instructions.record(operator);
+            }
+
             fn visit(&self, op: &Operator) -> bool {
                 self.context.reachable || visit_op_when_unreachable(op)
             }
@@ -11,7 +11,9 @@ use std::{
 use target_lexicon::{Architecture, Triple};
 use wasmparser::{FuncValidator, FunctionBody, ValidatorResources};
 use wasmtime_cranelift::CompiledFunction;
-use wasmtime_environ::{ModuleTranslation, ModuleTypesBuilder, Tunables, WasmFuncType};
+use wasmtime_environ::{
+    InstructionUsage, ModuleTranslation, ModuleTypesBuilder, Tunables, WasmFuncType,
+};
 
 #[cfg(feature = "x64")]
 pub(crate) mod x64;
@@ -164,7 +166,8 @@ pub trait TargetIsa: Send + Sync {
         false
     }
 
-    /// Compile a function.
+    /// Compile a function, returning it along with the classes of
+    /// instructions its body uses.
     fn compile_function(
         &self,
         sig: &WasmFuncType,
@@ -174,7 +177,7 @@ pub trait TargetIsa: Send + Sync {
         builtins: &mut BuiltinFunctions,
         validator: &mut FuncValidator<ValidatorResources>,
         tunables: &Tunables,
-    ) -> Result<CompiledFunction>;
+    ) -> Result<(CompiledFunction, InstructionUsage)>;
 
     /// Get the default calling convention of the underlying target triple.
     fn default_call_conv(&self) -> CallConv {
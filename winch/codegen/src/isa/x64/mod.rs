@@ -15,7 +15,9 @@ use cranelift_codegen::{MachTextSectionBuilder, TextSectionBuilder};
 use target_lexicon::Triple;
 use wasmparser::{FuncValidator, FunctionBody, ValidatorResources};
 use wasmtime_cranelift::CompiledFunction;
-use wasmtime_environ::{ModuleTranslation, ModuleTypesBuilder, Tunables, VMOffsets, WasmFuncType};
+use wasmtime_environ::{
+    InstructionUsage, ModuleTranslation, ModuleTypesBuilder, Tunables, VMOffsets, WasmFuncType,
+};
 
 use self::regs::{fpr_bit_set, gpr_bit_set};
 
@@ -91,7 +93,7 @@ impl TargetIsa for X64 {
         builtins: &mut BuiltinFunctions,
         validator: &mut FuncValidator<ValidatorResources>,
         tunables: &Tunables,
-    ) -> Result<CompiledFunction> {
+    ) -> Result<(CompiledFunction, InstructionUsage)> {
         let pointer_bytes = self.pointer_bytes();
         let vmoffsets = VMOffsets::new(pointer_bytes, &translation.module);
 
@@ -127,11 +129,9 @@ impl TargetIsa for X64 {
         let base = body_codegen.source_location.base;
 
         let names = body_codegen.env.take_name_map();
-        Ok(CompiledFunction::new(
-            masm.finalize(base)?,
-            names,
-            self.function_alignment(),
-        ))
+        let instructions = body_codegen.instructions;
+        let func = CompiledFunction::new(masm.finalize(base)?, names, self.function_alignment());
+        Ok((func, instructions))
     }
 
     fn text_section_builder(&self, num_funcs: usize) -> Box<dyn TextSectionBuilder> {